@@ -0,0 +1,127 @@
+//! Compares the derived field dispatch against the naive chain of string
+//! comparisons it replaces, on a struct with many similarly named fields.
+//!
+//!     cargo bench --bench field_dispatch
+
+use rson::{from_value, Deserialize, Deserializer, MapAccess, Rson, Value, Visitor};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+macro_rules! wide_struct {
+    ($name:ident { $($field:ident),* $(,)? }) => {
+        #[allow(dead_code)]
+        #[derive(Deserialize)]
+        struct $name {
+            $($field: u64,)*
+        }
+
+        /// Same shape, matching keys with `match key.as_str()`.
+        #[allow(dead_code)]
+        struct Naive {
+            $($field: u64,)*
+        }
+
+        impl Deserialize for Naive {
+            fn deserialize<D>(deserializer: D) -> rson::Result<Self>
+            where
+                D: Deserializer,
+            {
+                struct NaiveVisitor;
+
+                impl Visitor for NaiveVisitor {
+                    type Value = Naive;
+
+                    fn expecting(&self) -> &'static str {
+                        "struct Naive"
+                    }
+
+                    fn visit_map<A>(self, mut map: A) -> rson::Result<Naive>
+                    where
+                        A: MapAccess,
+                    {
+                        $(let mut $field = None;)*
+                        while let Some(key) = map.next_key()? {
+                            match key.as_str() {
+                                $(stringify!($field) => $field = Some(map.next_value()?),)*
+                                _ => {
+                                    map.next_value::<Value>()?;
+                                }
+                            }
+                        }
+                        Ok(Naive {
+                            $($field: $field.ok_or_else(|| rson::Error::missing_field(stringify!($field)))?,)*
+                        })
+                    }
+                }
+
+                deserializer.deserialize_any(NaiveVisitor)
+            }
+        }
+
+        fn document() -> String {
+            let members: Vec<String> = vec![$(format!("\"{}\": 1", stringify!($field))),*];
+            format!("{{{}}}", members.join(", "))
+        }
+    };
+}
+
+wide_struct!(Derived {
+    request_id,
+    request_ts,
+    request_len,
+    request_host,
+    request_path,
+    response_id,
+    response_ts,
+    response_len,
+    response_code,
+    response_bytes,
+    upstream_id,
+    upstream_ts,
+    upstream_len,
+    upstream_host,
+    upstream_port,
+    client_id,
+    client_ts,
+    client_len,
+    client_addr,
+    client_port,
+    server_id,
+    server_ts,
+    server_len,
+    server_addr,
+    server_port,
+});
+
+fn bench<T: Deserialize>(name: &str, value: &Value, iterations: u32) -> Duration {
+    // Clone the inputs up front so the timed loop is the dispatch, not
+    // copying the document.
+    let inputs: Vec<Value> = (0..iterations).map(|_| value.clone()).collect();
+    let start = Instant::now();
+    for input in inputs {
+        black_box(from_value::<T>(black_box(input)).unwrap());
+    }
+    let elapsed = start.elapsed();
+    println!(
+        "{:<10} {:>10.0} ns/iter",
+        name,
+        elapsed.as_nanos() as f64 / f64::from(iterations)
+    );
+    elapsed
+}
+
+fn main() {
+    let value = Rson::from_reader(document().as_bytes());
+    let iterations = 20_000;
+
+    // Warm up both paths before measuring.
+    bench::<Derived>("warmup", &value, iterations / 10);
+    bench::<Naive>("warmup", &value, iterations / 10);
+
+    let naive = bench::<Naive>("naive", &value, iterations);
+    let derived = bench::<Derived>("derived", &value, iterations);
+    println!(
+        "derived dispatch takes {:.2}x the time of the string match chain",
+        derived.as_secs_f64() / naive.as_secs_f64()
+    );
+}
@@ -1,16 +1,116 @@
-use crate::value::Value;
+use rson_core::Rson;
+use rson_core::{Error, Result};
+use rson_core::{Literal, Map, Number, RsonMap, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::marker::PhantomData;
+use std::str::FromStr;
 
 // *************** Visitor *****************
 pub trait EnumAccess {}
 
-pub trait Visitor {
+/// Receives the shape a `Deserializer` found and builds `Self::Value` from
+/// it. Every `visit_*` method rejects its input unless overridden, so a
+/// visitor only implements the shapes it accepts.
+pub trait Visitor: Sized {
     type Value;
 
-    fn visit_enum<A>(self, a: A) -> Self::Value
+    /// What this visitor accepts, used in error messages, e.g. `a string`.
+    fn expecting(&self) -> &'static str;
+
+    fn visit_null(self) -> Result<Self::Value> {
+        Err(Error::invalid_type("null", self.expecting()))
+    }
+
+    fn visit_bool(self, v: bool) -> Result<Self::Value> {
+        Err(Error::invalid_type(
+            &format!("boolean `{}`", v),
+            self.expecting(),
+        ))
+    }
+
+    fn visit_number(self, v: Number) -> Result<Self::Value> {
+        Err(Error::invalid_type(
+            &format!("number `{}`", v.as_str()),
+            self.expecting(),
+        ))
+    }
+
+    fn visit_string(self, v: String) -> Result<Self::Value> {
+        Err(Error::invalid_type(
+            &format!("string {:?}", v),
+            self.expecting(),
+        ))
+    }
+
+    fn visit_seq<A>(self, _seq: A) -> Result<Self::Value>
+    where
+        A: SeqAccess,
+    {
+        Err(Error::invalid_type("array", self.expecting()))
+    }
+
+    fn visit_map<A>(self, _map: A) -> Result<Self::Value>
+    where
+        A: MapAccess,
+    {
+        Err(Error::invalid_type("object", self.expecting()))
+    }
+
+    /// Called by `deserialize_option` for `null`.
+    fn visit_none(self) -> Result<Self::Value> {
+        self.visit_null()
+    }
+
+    /// Called by `deserialize_option` for anything but `null`.
+    fn visit_some<D>(self, _deserializer: D) -> Result<Self::Value>
+    where
+        D: Deserializer,
+    {
+        Err(Error::invalid_type("optional value", self.expecting()))
+    }
+
+    fn visit_enum<A>(self, _a: A) -> Result<Self::Value>
+    where
+        A: EnumAccess,
+    {
+        Err(Error::invalid_type("enum", self.expecting()))
+    }
+}
+
+/// Hands out the elements of an array one at a time.
+pub trait SeqAccess {
+    fn next_element<T>(&mut self) -> Result<Option<T>>
+    where
+        T: Deserialize;
+
+    fn size_hint(&self) -> Option<usize> {
+        None
+    }
+}
+
+/// Hands out the members of an object one at a time. `next_value` must be
+/// called after every key returned by `next_key`.
+pub trait MapAccess {
+    fn next_key(&mut self) -> Result<Option<String>>;
+
+    fn next_value<T>(&mut self) -> Result<T>
     where
-        A: EnumAccess;
+        T: Deserialize;
 
-    fn visit_string(self, v: String) -> Self::Value;
+    /// Reads the next value with a function instead of a `Deserialize`
+    /// impl, which is how `#[rson(with = "...")]` fields are read.
+    fn next_value_with<T, F>(&mut self, f: F) -> Result<T>
+    where
+        F: FnOnce(ValueDeserializer) -> Result<T>,
+    {
+        let value: Value = self.next_value()?;
+        f(ValueDeserializer::new(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        None
+    }
 }
 
 struct ValueVisitor;
@@ -18,15 +118,46 @@ struct ValueVisitor;
 impl Visitor for ValueVisitor {
     type Value = Value;
 
-    fn visit_string(self, v: String) -> Self::Value {
-        unimplemented!()
+    fn expecting(&self) -> &'static str {
+        "any JSON value"
+    }
+
+    fn visit_null(self) -> Result<Self::Value> {
+        Ok(Value::Literal(Literal::Null))
     }
 
-    fn visit_enum<A>(self, a: A) -> Self::Value
+    fn visit_bool(self, v: bool) -> Result<Self::Value> {
+        Ok(Value::Literal(Literal::Bool(v)))
+    }
+
+    fn visit_number(self, v: Number) -> Result<Self::Value> {
+        Ok(Value::Number(v))
+    }
+
+    fn visit_string(self, v: String) -> Result<Self::Value> {
+        Ok(Value::String(v))
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value>
     where
-        A: EnumAccess,
+        A: SeqAccess,
+    {
+        let mut array = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(value) = seq.next_element()? {
+            array.push(value);
+        }
+        Ok(Value::Array(array))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value>
+    where
+        A: MapAccess,
     {
-        panic!()
+        let mut object = Map::with_capacity(map.size_hint().unwrap_or(0));
+        while let Some(key) = map.next_key()? {
+            object.insert(key, map.next_value()?);
+        }
+        Ok(Value::Object(RsonMap(object)))
     }
 }
 
@@ -39,31 +170,409 @@ impl Visitor for ValueVisitor {
 ///     own_business: bool,
 ///     address: Option<String>
 /// }
-
-pub trait Deserialize {
-    fn deserialize<D>(deserializer: D) -> Self
+pub trait Deserialize: Sized {
+    fn deserialize<D>(deserializer: D) -> Result<Self>
     where
         D: Deserializer;
 }
 
+/// Converts an already parsed `Value` into `T`.
+pub fn from_value<T: Deserialize>(value: Value) -> Result<T> {
+    T::deserialize(ValueDeserializer::new(value))
+}
+
+/// Parses `text` and converts it into `T`.
+pub fn from_str<T: Deserialize>(text: &str) -> Result<T> {
+    from_value(Rson::try_from_reader(text.as_bytes())?)
+}
+
+/// Options for converting JSON into Rust types, for input that doesn't
+/// quite match them. `from_str` and `from_value` use the defaults.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct DeserializeOptions {
+    /// Match object keys to struct fields ignoring ASCII case, e.g.
+    /// `Accept` or `ACCEPT` for a field named `accept`. An exact match
+    /// wins, and two keys matching the same field are a duplicate field.
+    pub case_insensitive_fields: bool,
+    /// Let fields marked `#[rson(number_fallback = "other_field")]` accept
+    /// numbers their type can't hold, like a 20-digit identifier read into
+    /// a `u64`. The field is left at its `Default` and the number goes to
+    /// `other_field`, which can be a `Number`, `String` or `Value`, or an
+    /// `Option` of one. Without this those numbers are an error as usual.
+    pub number_fallback: bool,
+}
+
+impl DeserializeOptions {
+    pub fn from_value<T: Deserialize>(&self, value: Value) -> Result<T> {
+        T::deserialize(ValueDeserializer::with_options(value, *self))
+    }
+
+    pub fn from_str<T: Deserialize>(&self, text: &str) -> Result<T> {
+        self.from_value(Rson::try_from_reader(text.as_bytes())?)
+    }
+}
+
 impl Deserialize for Value {
-    fn deserialize<D>(deserializer: D) -> Self
+    fn deserialize<D>(deserializer: D) -> Result<Self>
     where
         D: Deserializer,
     {
-        let variants = vec!["Literal", "Number", "Array", "RsonMap"];
-        deserializer.deserialize_enum("Value", variants.as_slice(), ValueVisitor)
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+/// Any number, keeping its source text.
+impl Deserialize for Number {
+    fn deserialize<D>(deserializer: D) -> Result<Self>
+    where
+        D: Deserializer,
+    {
+        struct NumberVisitor;
+
+        impl Visitor for NumberVisitor {
+            type Value = Number;
+
+            fn expecting(&self) -> &'static str {
+                "a number"
+            }
+
+            fn visit_number(self, v: Number) -> Result<Number> {
+                Ok(v)
+            }
+        }
+
+        deserializer.deserialize_any(NumberVisitor)
+    }
+}
+
+impl Deserialize for () {
+    fn deserialize<D>(deserializer: D) -> Result<Self>
+    where
+        D: Deserializer,
+    {
+        struct UnitVisitor;
+
+        impl Visitor for UnitVisitor {
+            type Value = ();
+
+            fn expecting(&self) -> &'static str {
+                "null"
+            }
+
+            fn visit_null(self) -> Result<()> {
+                Ok(())
+            }
+        }
+
+        deserializer.deserialize_any(UnitVisitor)
+    }
+}
+
+impl Deserialize for bool {
+    fn deserialize<D>(deserializer: D) -> Result<Self>
+    where
+        D: Deserializer,
+    {
+        struct BoolVisitor;
+
+        impl Visitor for BoolVisitor {
+            type Value = bool;
+
+            fn expecting(&self) -> &'static str {
+                "a boolean"
+            }
+
+            fn visit_bool(self, v: bool) -> Result<bool> {
+                Ok(v)
+            }
+        }
+
+        deserializer.deserialize_any(BoolVisitor)
+    }
+}
+
+impl Deserialize for String {
+    fn deserialize<D>(deserializer: D) -> Result<Self>
+    where
+        D: Deserializer,
+    {
+        struct StringVisitor;
+
+        impl Visitor for StringVisitor {
+            type Value = String;
+
+            fn expecting(&self) -> &'static str {
+                "a string"
+            }
+
+            fn visit_string(self, v: String) -> Result<String> {
+                Ok(v)
+            }
+        }
+
+        deserializer.deserialize_string(StringVisitor)
+    }
+}
+
+impl Deserialize for char {
+    fn deserialize<D>(deserializer: D) -> Result<Self>
+    where
+        D: Deserializer,
+    {
+        let s = String::deserialize(deserializer)?;
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(Error::invalid_value(
+                &format!("string {:?}", s),
+                "a single character",
+            )),
+        }
+    }
+}
+
+/// Numbers keep their source text, so each primitive parses it with its
+/// own `FromStr` and out-of-range or fractional values are rejected
+/// instead of being truncated. Floats also reject what would round to
+/// infinity, like `1e400`, or isn't a number at all.
+struct PrimitiveVisitor<T>(PhantomData<T>);
+
+macro_rules! deserialize_primitive {
+    ($($ty:ty => $expecting:expr $(, if $valid:path)?);* $(;)?) => {
+        $(
+            impl Visitor for PrimitiveVisitor<$ty> {
+                type Value = $ty;
+
+                fn expecting(&self) -> &'static str {
+                    $expecting
+                }
+
+                fn visit_number(self, v: Number) -> Result<$ty> {
+                    match v.as_str().parse::<$ty>() {
+                        Ok(n) if true $(&& $valid(n))? => Ok(n),
+                        _ => Err(Error::invalid_value(
+                            &format!("number `{}`", v.as_str()),
+                            $expecting,
+                        )),
+                    }
+                }
+            }
+
+            impl Deserialize for $ty {
+                fn deserialize<D>(deserializer: D) -> Result<Self>
+                where
+                    D: Deserializer,
+                {
+                    deserializer.deserialize_any(PrimitiveVisitor::<$ty>(PhantomData))
+                }
+            }
+        )*
+    };
+}
+
+deserialize_primitive! {
+    i8 => "an i8";
+    i16 => "an i16";
+    i32 => "an i32";
+    i64 => "an i64";
+    i128 => "an i128";
+    isize => "an isize";
+    u8 => "a u8";
+    u16 => "a u16";
+    u32 => "a u32";
+    u64 => "a u64";
+    u128 => "a u128";
+    usize => "a usize";
+    f32 => "an f32", if f32::is_finite;
+    f64 => "an f64", if f64::is_finite;
+}
+
+impl<T: Deserialize> Deserialize for Option<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self>
+    where
+        D: Deserializer,
+    {
+        struct OptionVisitor<T>(PhantomData<T>);
+
+        impl<T: Deserialize> Visitor for OptionVisitor<T> {
+            type Value = Option<T>;
+
+            fn expecting(&self) -> &'static str {
+                "an optional value"
+            }
+
+            fn visit_none(self) -> Result<Self::Value> {
+                Ok(None)
+            }
+
+            fn visit_some<D>(self, deserializer: D) -> Result<Self::Value>
+            where
+                D: Deserializer,
+            {
+                T::deserialize(deserializer).map(Some)
+            }
+        }
+
+        deserializer.deserialize_option(OptionVisitor(PhantomData))
+    }
+}
+
+impl<T: Deserialize> Deserialize for Box<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self>
+    where
+        D: Deserializer,
+    {
+        T::deserialize(deserializer).map(Box::new)
+    }
+}
+
+impl<T: Deserialize> Deserialize for Vec<T> {
+    fn deserialize<D>(deserializer: D) -> Result<Self>
+    where
+        D: Deserializer,
+    {
+        struct VecVisitor<T>(PhantomData<T>);
+
+        impl<T: Deserialize> Visitor for VecVisitor<T> {
+            type Value = Vec<T>;
+
+            fn expecting(&self) -> &'static str {
+                "an array"
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value>
+            where
+                A: SeqAccess,
+            {
+                let mut values = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(value) = seq.next_element()? {
+                    values.push(value);
+                }
+                Ok(values)
+            }
+        }
+
+        deserializer.deserialize_any(VecVisitor(PhantomData))
+    }
+}
+
+/// Keys are parsed with `FromStr`, the reverse of the `Display` used by
+/// `Serialize`, so maps keyed by numbers, addresses and the like work.
+impl<K, V> Deserialize for HashMap<K, V>
+where
+    K: FromStr + Eq + Hash,
+    V: Deserialize,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self>
+    where
+        D: Deserializer,
+    {
+        deserializer.deserialize_any(MapVisitor(PhantomData))
+    }
+}
+
+impl<K, V> Deserialize for BTreeMap<K, V>
+where
+    K: FromStr + Ord,
+    V: Deserialize,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self>
+    where
+        D: Deserializer,
+    {
+        deserializer.deserialize_any(MapVisitor(PhantomData))
+    }
+}
+
+/// Builds any map type `M` from an object, parsing keys into `K`.
+struct MapVisitor<M, K, V>(PhantomData<(M, K, V)>);
+
+impl<M, K, V> Visitor for MapVisitor<M, K, V>
+where
+    M: Default + Extend<(K, V)>,
+    K: FromStr,
+    V: Deserialize,
+{
+    type Value = M;
+
+    fn expecting(&self) -> &'static str {
+        "an object"
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value>
+    where
+        A: MapAccess,
+    {
+        let mut values = M::default();
+        while let Some(key) = map.next_key()? {
+            let key = parse_key(&key)?;
+            values.extend(Some((key, map.next_value()?)));
+        }
+        Ok(values)
+    }
+}
+
+fn parse_key<K: FromStr>(key: &str) -> Result<K> {
+    key.parse().map_err(|_| {
+        Error::invalid_value(
+            &format!("key {:?}", key),
+            &format!("a key of type `{}`", std::any::type_name::<K>()),
+        )
+    })
+}
+
+impl<K, V> Deserialize for RsonMap<K, V>
+where
+    K: FromStr + Eq + Hash + Ord,
+    V: Deserialize,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self>
+    where
+        D: Deserializer,
+    {
+        deserializer
+            .deserialize_any(MapVisitor(PhantomData))
+            .map(RsonMap)
     }
 }
 
 // *************** Deserializer *****************
 
-pub trait Deserializer {
-    fn deserialize_string<V>(self, v: V) -> V::Value
+/// A source of data that a `Deserialize` type reads itself from. The hints
+/// (`deserialize_string`, `deserialize_struct`, ...) tell a deserializer
+/// what the caller expects; a self-describing format like JSON may ignore
+/// them and forward to `deserialize_any`.
+pub trait Deserializer: Sized {
+    fn deserialize_any<V>(self, v: V) -> Result<V::Value>
     where
         V: Visitor;
 
-    fn deserialize_enum<V>(self, name: &str, variants: &[&str], v: V) -> V::Value
+    fn deserialize_string<V>(self, v: V) -> Result<V::Value>
+    where
+        V: Visitor,
+    {
+        self.deserialize_any(v)
+    }
+
+    /// Calls `visit_none` for `null` and `visit_some` otherwise.
+    fn deserialize_option<V>(self, v: V) -> Result<V::Value>
+    where
+        V: Visitor;
+
+    /// `fields` lists the keys the struct knows about.
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        v: V,
+    ) -> Result<V::Value>
+    where
+        V: Visitor,
+    {
+        self.deserialize_any(v)
+    }
+
+    fn deserialize_enum<V>(self, name: &str, variants: &[&str], v: V) -> Result<V::Value>
     where
         V: Visitor;
 }
@@ -73,35 +582,335 @@ struct StringDeserializer {
 }
 
 impl Deserializer for StringDeserializer {
-    fn deserialize_string<V>(self, v: V) -> V::Value
+    fn deserialize_any<V>(self, v: V) -> Result<V::Value>
     where
         V: Visitor,
     {
         v.visit_string(self.value)
     }
 
-    fn deserialize_enum<V>(self, name: &str, variants: &[&str], v: V) -> V::Value
+    fn deserialize_option<V>(self, v: V) -> Result<V::Value>
+    where
+        V: Visitor,
+    {
+        v.visit_some(self)
+    }
+
+    fn deserialize_enum<V>(self, _name: &str, _variants: &[&str], v: V) -> Result<V::Value>
     where
         V: Visitor,
     {
-        unimplemented!()
+        self.deserialize_any(v)
     }
 }
 
-struct ValueDeserializer;
+/// Deserializes Rust types out of a parsed `Value`.
+pub struct ValueDeserializer {
+    value: Value,
+    options: DeserializeOptions,
+}
+
+impl ValueDeserializer {
+    pub fn new(value: Value) -> Self {
+        Self::with_options(value, DeserializeOptions::default())
+    }
+
+    /// A deserializer applying `options` to the value and everything in
+    /// it.
+    pub fn with_options(value: Value, options: DeserializeOptions) -> Self {
+        Self { value, options }
+    }
+}
 
 impl Deserializer for ValueDeserializer {
-    fn deserialize_string<V>(self, v: V) -> V::Value
+    fn deserialize_any<V>(self, v: V) -> Result<V::Value>
+    where
+        V: Visitor,
+    {
+        match self.value {
+            Value::Literal(Literal::Null) => v.visit_null(),
+            Value::Literal(Literal::Bool(b)) => v.visit_bool(b),
+            Value::Number(n) => v.visit_number(n),
+            Value::String(s) => v.visit_string(s),
+            Value::Array(array) => v.visit_seq(SeqDeserializer {
+                iter: array.into_iter(),
+                options: self.options,
+            }),
+            Value::Object(map) => v.visit_map(MapDeserializer {
+                iter: map.0.into_iter(),
+                value: None,
+                options: self.options,
+            }),
+        }
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        v: V,
+    ) -> Result<V::Value>
     where
         V: Visitor,
     {
-        unimplemented!()
+        match self.value {
+            Value::Object(map) if self.options.case_insensitive_fields => {
+                v.visit_map(MapDeserializer {
+                    iter: fold_field_case(map.0, fields)?.into_iter(),
+                    value: None,
+                    options: self.options,
+                })
+            }
+            _ => self.deserialize_any(v),
+        }
     }
 
-    fn deserialize_enum<V>(self, name: &str, variants: &[&str], v: V) -> V::Value
+    fn deserialize_option<V>(self, v: V) -> Result<V::Value>
     where
         V: Visitor,
     {
-        panic!()
+        match self.value {
+            Value::Literal(Literal::Null) => v.visit_none(),
+            _ => v.visit_some(self),
+        }
+    }
+
+    fn deserialize_enum<V>(self, _name: &str, _variants: &[&str], v: V) -> Result<V::Value>
+    where
+        V: Visitor,
+    {
+        self.deserialize_any(v)
+    }
+}
+
+/// Renames the keys of `map` that match one of `fields` ignoring ASCII
+/// case to that field's key. Only done when asked for, so the generated
+/// field lookup stays an exact match.
+fn fold_field_case(map: Map<String, Value>, fields: &[&str]) -> Result<Map<String, Value>> {
+    let mut folded = Map::with_capacity(map.len());
+    for (key, value) in map {
+        let field = fields
+            .iter()
+            .find(|field| **field == key)
+            .or_else(|| fields.iter().find(|field| field.eq_ignore_ascii_case(&key)));
+        let key = match field {
+            Some(field) => field.to_string(),
+            None => key,
+        };
+        if folded.contains_key(&key) {
+            return Err(Error::duplicate_field(&key));
+        }
+        folded.insert(key, value);
+    }
+    Ok(folded)
+}
+
+struct SeqDeserializer {
+    iter: std::vec::IntoIter<Value>,
+    options: DeserializeOptions,
+}
+
+impl SeqAccess for SeqDeserializer {
+    fn next_element<T>(&mut self) -> Result<Option<T>>
+    where
+        T: Deserialize,
+    {
+        let options = self.options;
+        self.iter
+            .next()
+            .map(|value| options.from_value(value))
+            .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+struct MapDeserializer {
+    iter: <Map<String, Value> as IntoIterator>::IntoIter,
+    value: Option<Value>,
+    options: DeserializeOptions,
+}
+
+impl MapAccess for MapDeserializer {
+    fn next_key(&mut self) -> Result<Option<String>> {
+        Ok(self.iter.next().map(|(key, value)| {
+            self.value = Some(value);
+            key
+        }))
+    }
+
+    fn next_value<T>(&mut self) -> Result<T>
+    where
+        T: Deserialize,
+    {
+        match self.value.take() {
+            Some(value) => self.options.from_value(value),
+            None => Err(Error::custom("next_value called before next_key")),
+        }
+    }
+
+    fn next_value_with<T, F>(&mut self, f: F) -> Result<T>
+    where
+        F: FnOnce(ValueDeserializer) -> Result<T>,
+    {
+        match self.value.take() {
+            Some(value) => f(ValueDeserializer::with_options(value, self.options)),
+            None => Err(Error::custom("next_value called before next_key")),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.iter.len())
+    }
+}
+
+/// Support code for the derives and the `rson!` macro. Not part of the
+/// public API.
+#[doc(hidden)]
+pub mod __private {
+    use super::*;
+
+    /// A field absent from the input is only an error if the field type
+    /// can't be built from `null`, which lets `Option` fields default to
+    /// `None`.
+    pub fn missing_field<T: Deserialize>(field: &'static str) -> Result<T> {
+        from_value(Value::Literal(Literal::Null)).map_err(|_| Error::missing_field(field))
+    }
+
+    /// `missing_field` for fields read by a `with` module.
+    pub fn missing_field_with<T, F>(field: &'static str, f: F) -> Result<T>
+    where
+        F: FnOnce(ValueDeserializer) -> Result<T>,
+    {
+        f(ValueDeserializer::new(Value::Literal(Literal::Null)))
+            .map_err(|_| Error::missing_field(field))
+    }
+
+    /// Reads a field marked `#[rson(number_fallback = "...")]`. A number
+    /// `T` can't hold gives `T::default()` and the number, when the
+    /// options allow it.
+    pub fn number_with_fallback<T>(deserializer: ValueDeserializer) -> Result<(T, Option<Number>)>
+    where
+        T: Deserialize + Default,
+    {
+        let ValueDeserializer { value, options } = deserializer;
+        match value {
+            Value::Number(n) if options.number_fallback => {
+                match options.from_value(Value::Number(n.clone())) {
+                    Ok(value) => Ok((value, None)),
+                    Err(_) => Ok((T::default(), Some(n))),
+                }
+            }
+            value => options.from_value(value).map(|value| (value, None)),
+        }
+    }
+
+    /// Converts a number captured by `number_with_fallback` for the field
+    /// that keeps it, which may hold it as a number or as a string.
+    pub fn number_fallback_value<T: Deserialize>(number: Number) -> Result<T> {
+        from_value(Value::Number(number.clone()))
+            .or_else(|_| from_value(Value::String(number.as_str().to_string())))
+    }
+
+    /// What `..expr` accepts inside an `rson!` object: anything holding
+    /// members. Later members overwrite earlier ones with the same key.
+    pub trait Spread {
+        fn spread_into(self, map: &mut Map<String, Value>) -> Result<()>;
+    }
+
+    impl Spread for Value {
+        fn spread_into(self, map: &mut Map<String, Value>) -> Result<()> {
+            let unexpected = match self {
+                Value::Object(object) => return object.spread_into(map),
+                Value::Literal(Literal::Null) => "null",
+                Value::Literal(Literal::Bool(_)) => "boolean",
+                Value::Number(_) => "number",
+                Value::String(_) => "string",
+                Value::Array(_) => "array",
+            };
+            Err(Error::invalid_type(unexpected, "an object to spread"))
+        }
+    }
+
+    impl Spread for RsonMap<String, Value> {
+        fn spread_into(self, map: &mut Map<String, Value>) -> Result<()> {
+            map.extend(self.0);
+            Ok(())
+        }
+    }
+
+    impl<V: Into<Value>> Spread for HashMap<String, V> {
+        fn spread_into(self, map: &mut Map<String, Value>) -> Result<()> {
+            map.extend(self.into_iter().map(|(k, v)| (k, v.into())));
+            Ok(())
+        }
+    }
+
+    impl<V: Into<Value>> Spread for BTreeMap<String, V> {
+        fn spread_into(self, map: &mut Map<String, Value>) -> Result<()> {
+            map.extend(self.into_iter().map(|(k, v)| (k, v.into())));
+            Ok(())
+        }
+    }
+
+    pub fn spread<S: Spread>(map: &mut Map<String, Value>, members: S) -> Result<()> {
+        members.spread_into(map)
+    }
+
+    /// Attaches a doc comment to a generated schema. Draft-07 ignores
+    /// keywords next to `$ref`, so references are wrapped in `allOf`.
+    pub fn describe(schema: Value, description: &str) -> Value {
+        let mut map = match schema {
+            Value::Object(map) if !map.0.contains_key("$ref") => map.0,
+            other => {
+                let mut map = Map::new();
+                map.insert("allOf".to_string(), Value::Array(vec![other]));
+                map
+            }
+        };
+        map.insert("description".to_string(), Value::from(description));
+        Value::Object(RsonMap(map))
+    }
+
+    /// Builds the schema of a derived struct with named fields. The
+    /// `description` is left out when empty.
+    pub fn struct_schema(
+        title: &str,
+        description: &str,
+        properties: HashMap<String, Value>,
+        required: Vec<Value>,
+    ) -> Value {
+        let mut map = Map::new();
+        map.insert("title".to_string(), Value::from(title));
+        map.insert("type".to_string(), Value::from("object"));
+        map.insert("properties".to_string(), Value::from(properties));
+        map.insert("required".to_string(), Value::Array(required));
+        if !description.is_empty() {
+            map.insert("description".to_string(), Value::from(description));
+        }
+        Value::Object(RsonMap(map))
+    }
+
+    /// `std::any::type_name` without module paths, e.g. `List<String>`
+    /// rather than `app::List<alloc::string::String>`.
+    pub fn short_type_name<T: ?Sized>() -> String {
+        let full = std::any::type_name::<T>();
+        let mut name = String::with_capacity(full.len());
+        let mut segment_start = 0;
+        let mut chars = full.chars().peekable();
+        while let Some(c) = chars.next() {
+            if c == ':' && chars.peek() == Some(&':') {
+                chars.next();
+                name.truncate(segment_start);
+                continue;
+            }
+            name.push(c);
+            if !(c.is_alphanumeric() || c == '_') {
+                segment_start = name.len();
+            }
+        }
+        name
     }
 }
@@ -0,0 +1,510 @@
+//! Typed GeoJSON (RFC 7946) documents: geometries, features and feature
+//! collections that are checked when read, instead of poking at a raw
+//! `Value`.
+//!
+//!     let collection: FeatureCollection = from_str(&text)?;
+//!     for feature in &collection.features {
+//!         if let Some(Geometry::Point(position)) = &feature.geometry {
+//!             println!("{:?} at {:?}", feature.property("name"), position);
+//!         }
+//!     }
+//!
+//! Errors name the JSON Pointer of the offending member, e.g.
+//! ``at `/features/3/geometry/coordinates/0`: a linear ring needs at least
+//! 4 positions``.
+
+use crate::deserialize::{Deserialize, Deserializer};
+use crate::serialize::{Serialize, SerializeMap, Serializer};
+use rson_core::{Error, Literal, Map, Number, Result, Value};
+use std::collections::BTreeMap;
+
+/// Longitude, latitude and optionally altitude, in that order.
+pub type Position = Vec<f64>;
+
+/// A geometry object. Coordinates are checked when read: positions have
+/// at least two finite numbers, line strings at least two positions and
+/// polygon rings at least four, ending where they start. A `bbox` or
+/// foreign members on a geometry are dropped.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Geometry {
+    Point(Position),
+    MultiPoint(Vec<Position>),
+    LineString(Vec<Position>),
+    MultiLineString(Vec<Vec<Position>>),
+    /// The exterior ring first, then any holes.
+    Polygon(Vec<Vec<Position>>),
+    MultiPolygon(Vec<Vec<Vec<Position>>>),
+    GeometryCollection(Vec<Geometry>),
+}
+
+/// The `id` of a feature, a string or a number.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum FeatureId {
+    String(String),
+    Number(Number),
+}
+
+/// A spatially bounded thing: a geometry and its properties.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Feature {
+    pub id: Option<FeatureId>,
+    pub bbox: Option<Vec<f64>>,
+    /// `None` for a feature without a location (`"geometry": null`).
+    pub geometry: Option<Geometry>,
+    /// `None` for `"properties": null`.
+    pub properties: Option<BTreeMap<String, Value>>,
+    /// Members GeoJSON doesn't define, kept as they were.
+    pub foreign_members: BTreeMap<String, Value>,
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FeatureCollection {
+    pub bbox: Option<Vec<f64>>,
+    pub features: Vec<Feature>,
+    pub foreign_members: BTreeMap<String, Value>,
+}
+
+/// Any GeoJSON document, for input whose top-level type isn't known.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GeoJson {
+    Geometry(Geometry),
+    Feature(Feature),
+    FeatureCollection(FeatureCollection),
+}
+
+impl Feature {
+    pub fn new(geometry: Geometry) -> Self {
+        Self {
+            geometry: Some(geometry),
+            ..Self::default()
+        }
+    }
+
+    pub fn property(&self, key: &str) -> Option<&Value> {
+        self.properties.as_ref()?.get(key)
+    }
+
+    pub fn set_property(&mut self, key: impl Into<String>, value: impl Into<Value>) {
+        self.properties
+            .get_or_insert_with(BTreeMap::new)
+            .insert(key.into(), value.into());
+    }
+}
+
+impl Geometry {
+    /// The GeoJSON `type` of this geometry, e.g. `"Point"`.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Geometry::Point(_) => "Point",
+            Geometry::MultiPoint(_) => "MultiPoint",
+            Geometry::LineString(_) => "LineString",
+            Geometry::MultiLineString(_) => "MultiLineString",
+            Geometry::Polygon(_) => "Polygon",
+            Geometry::MultiPolygon(_) => "MultiPolygon",
+            Geometry::GeometryCollection(_) => "GeometryCollection",
+        }
+    }
+}
+
+// Reading: walk the parsed `Value`, keeping the pointer for errors.
+
+const GEOMETRY_TYPES: &[&str] = &[
+    "Point",
+    "MultiPoint",
+    "LineString",
+    "MultiLineString",
+    "Polygon",
+    "MultiPolygon",
+    "GeometryCollection",
+];
+
+fn object<'a>(value: &'a Value, at: &str) -> Result<&'a Map<String, Value>> {
+    match value {
+        Value::Object(map) => Ok(&map.0),
+        _ => Err(Error::invalid_type(&value.unexpected(), "an object").at(at)),
+    }
+}
+
+fn array<'a>(value: &'a Value, at: &str, expected: &str) -> Result<&'a [Value]> {
+    match value {
+        Value::Array(array) => Ok(array),
+        _ => Err(Error::invalid_type(&value.unexpected(), expected).at(at)),
+    }
+}
+
+fn member<'a>(map: &'a Map<String, Value>, key: &str, at: &str) -> Result<&'a Value> {
+    map.get(key).ok_or_else(|| Error::missing_field(key).at(at))
+}
+
+fn type_name<'a>(map: &'a Map<String, Value>, at: &str) -> Result<&'a str> {
+    match member(map, "type", at)? {
+        Value::String(s) => Ok(s),
+        other => {
+            Err(Error::invalid_type(&other.unexpected(), "a string").at(&format!("{}/type", at)))
+        }
+    }
+}
+
+fn number(value: &Value, at: &str) -> Result<f64> {
+    match value {
+        Value::Number(n) => n
+            .as_str()
+            .parse::<f64>()
+            .ok()
+            .filter(|n| n.is_finite())
+            .ok_or_else(|| Error::invalid_value(&value.unexpected(), "a finite number").at(at)),
+        _ => Err(Error::invalid_type(&value.unexpected(), "a number").at(at)),
+    }
+}
+
+fn position(value: &Value, at: &str) -> Result<Position> {
+    let numbers = array(value, at, "a position")?;
+    if numbers.len() < 2 {
+        return Err(
+            Error::invalid_length(numbers.len(), "a position of at least 2 numbers").at(at),
+        );
+    }
+    numbers
+        .iter()
+        .enumerate()
+        .map(|(i, n)| number(n, &format!("{}/{}", at, i)))
+        .collect()
+}
+
+fn each<T>(
+    value: &Value,
+    at: &str,
+    expected: &str,
+    f: impl Fn(&Value, &str) -> Result<T>,
+) -> Result<Vec<T>> {
+    array(value, at, expected)?
+        .iter()
+        .enumerate()
+        .map(|(i, v)| f(v, &format!("{}/{}", at, i)))
+        .collect()
+}
+
+fn line_string(value: &Value, at: &str) -> Result<Vec<Position>> {
+    let positions = each(value, at, "an array of positions", position)?;
+    if positions.len() < 2 {
+        return Err(Error::invalid_length(
+            positions.len(),
+            "a line string of at least 2 positions",
+        )
+        .at(at));
+    }
+    Ok(positions)
+}
+
+fn ring(value: &Value, at: &str) -> Result<Vec<Position>> {
+    let positions = each(value, at, "an array of positions", position)?;
+    if positions.len() < 4 {
+        return Err(Error::custom("a linear ring needs at least 4 positions").at(at));
+    }
+    if positions.first() != positions.last() {
+        return Err(Error::custom("a linear ring must end where it starts").at(at));
+    }
+    Ok(positions)
+}
+
+fn polygon(value: &Value, at: &str) -> Result<Vec<Vec<Position>>> {
+    each(value, at, "an array of linear rings", ring)
+}
+
+fn bbox(map: &Map<String, Value>, at: &str) -> Result<Option<Vec<f64>>> {
+    let value = match map.get("bbox") {
+        Some(value) => value,
+        None => return Ok(None),
+    };
+    let at = format!("{}/bbox", at);
+    let numbers = each(value, &at, "an array of numbers", number)?;
+    if !matches!(numbers.len(), 4 | 6) {
+        return Err(Error::invalid_length(numbers.len(), "a bbox of 4 or 6 numbers").at(&at));
+    }
+    Ok(Some(numbers))
+}
+
+fn foreign_members(map: &Map<String, Value>, known: &[&str]) -> BTreeMap<String, Value> {
+    map.iter()
+        .filter(|(k, _)| !known.contains(&k.as_str()))
+        .map(|(k, v)| (k.clone(), v.clone()))
+        .collect()
+}
+
+fn geometry(value: &Value, at: &str) -> Result<Geometry> {
+    let map = object(value, at)?;
+    let kind = type_name(map, at)?;
+    if kind == "GeometryCollection" {
+        let geometries = member(map, "geometries", at)?;
+        return each(
+            geometries,
+            &format!("{}/geometries", at),
+            "an array of geometries",
+            geometry,
+        )
+        .map(Geometry::GeometryCollection);
+    }
+    if !GEOMETRY_TYPES.contains(&kind) {
+        return Err(Error::unknown_variant(kind, GEOMETRY_TYPES).at(&format!("{}/type", at)));
+    }
+
+    let coordinates = member(map, "coordinates", at)?;
+    let at = format!("{}/coordinates", at);
+    Ok(match kind {
+        "Point" => Geometry::Point(position(coordinates, &at)?),
+        "MultiPoint" => {
+            Geometry::MultiPoint(each(coordinates, &at, "an array of positions", position)?)
+        }
+        "LineString" => Geometry::LineString(line_string(coordinates, &at)?),
+        "MultiLineString" => Geometry::MultiLineString(each(
+            coordinates,
+            &at,
+            "an array of line strings",
+            line_string,
+        )?),
+        "Polygon" => Geometry::Polygon(polygon(coordinates, &at)?),
+        _ => Geometry::MultiPolygon(each(coordinates, &at, "an array of polygons", polygon)?),
+    })
+}
+
+fn expect_type(map: &Map<String, Value>, at: &str, expected: &str) -> Result<()> {
+    let kind = type_name(map, at)?;
+    if kind != expected {
+        return Err(Error::invalid_value(
+            &format!("string {:?}", kind),
+            &format!("\"{}\"", expected),
+        )
+        .at(&format!("{}/type", at)));
+    }
+    Ok(())
+}
+
+fn feature(value: &Value, at: &str) -> Result<Feature> {
+    let map = object(value, at)?;
+    expect_type(map, at, "Feature")?;
+
+    let id = match map.get("id") {
+        None => None,
+        Some(Value::String(s)) => Some(FeatureId::String(s.clone())),
+        Some(Value::Number(n)) => Some(FeatureId::Number(n.clone())),
+        Some(other) => {
+            return Err(
+                Error::invalid_type(&other.unexpected(), "a string or number")
+                    .at(&format!("{}/id", at)),
+            )
+        }
+    };
+    let geometry = match member(map, "geometry", at)? {
+        Value::Literal(Literal::Null) => None,
+        value => Some(geometry(value, &format!("{}/geometry", at))?),
+    };
+    let properties = match member(map, "properties", at)? {
+        Value::Literal(Literal::Null) => None,
+        value => Some(
+            object(value, &format!("{}/properties", at))?
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect(),
+        ),
+    };
+
+    Ok(Feature {
+        id,
+        bbox: bbox(map, at)?,
+        geometry,
+        properties,
+        foreign_members: foreign_members(map, &["type", "id", "bbox", "geometry", "properties"]),
+    })
+}
+
+fn feature_collection(value: &Value, at: &str) -> Result<FeatureCollection> {
+    let map = object(value, at)?;
+    expect_type(map, at, "FeatureCollection")?;
+    let features = member(map, "features", at)?;
+
+    Ok(FeatureCollection {
+        bbox: bbox(map, at)?,
+        features: each(
+            features,
+            &format!("{}/features", at),
+            "an array of features",
+            feature,
+        )?,
+        foreign_members: foreign_members(map, &["type", "bbox", "features"]),
+    })
+}
+
+fn geojson(value: &Value) -> Result<GeoJson> {
+    match type_name(object(value, "")?, "")? {
+        "Feature" => feature(value, "").map(GeoJson::Feature),
+        "FeatureCollection" => feature_collection(value, "").map(GeoJson::FeatureCollection),
+        _ => geometry(value, "").map(GeoJson::Geometry),
+    }
+}
+
+macro_rules! deserialize_via_value {
+    ($($ty:ty => $read:expr;)*) => {
+        $(
+            impl Deserialize for $ty {
+                fn deserialize<D>(deserializer: D) -> Result<Self>
+                where
+                    D: Deserializer,
+                {
+                    let value = Value::deserialize(deserializer)?;
+                    $read(&value)
+                }
+            }
+        )*
+    };
+}
+
+deserialize_via_value! {
+    Geometry => |value| geometry(value, "");
+    Feature => |value| feature(value, "");
+    FeatureCollection => |value| feature_collection(value, "");
+    GeoJson => geojson;
+}
+
+// Writing.
+
+/// Coordinates, refusing the non-finite numbers JSON can't hold rather
+/// than writing them as `null`.
+struct Coordinates<'a>(&'a [f64]);
+
+impl Serialize for Coordinates<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        if let Some(n) = self.0.iter().find(|n| !n.is_finite()) {
+            return Err(Error::invalid_value(
+                &format!("`{}`", n),
+                "a finite coordinate",
+            ));
+        }
+        self.0.serialize(serializer)
+    }
+}
+
+/// Nested arrays of positions, at any depth.
+struct Nested<'a, T>(&'a [T]);
+
+impl Serialize for Nested<'_, Position> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        let positions: Vec<_> = self.0.iter().map(|p| Coordinates(p)).collect();
+        positions.serialize(serializer)
+    }
+}
+
+impl Serialize for Nested<'_, Vec<Position>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        let lines: Vec<_> = self.0.iter().map(|l| Nested(l)).collect();
+        lines.serialize(serializer)
+    }
+}
+
+impl Serialize for Nested<'_, Vec<Vec<Position>>> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        let polygons: Vec<_> = self.0.iter().map(|p| Nested(p)).collect();
+        polygons.serialize(serializer)
+    }
+}
+
+impl Serialize for Geometry {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("type", self.type_name())?;
+        match self {
+            Geometry::Point(p) => map.serialize_entry("coordinates", &Coordinates(p))?,
+            Geometry::MultiPoint(p) | Geometry::LineString(p) => {
+                map.serialize_entry("coordinates", &Nested(p))?
+            }
+            Geometry::MultiLineString(p) | Geometry::Polygon(p) => {
+                map.serialize_entry("coordinates", &Nested(p))?
+            }
+            Geometry::MultiPolygon(p) => map.serialize_entry("coordinates", &Nested(p))?,
+            Geometry::GeometryCollection(geometries) => {
+                map.serialize_entry("geometries", geometries)?
+            }
+        }
+        map.end()
+    }
+}
+
+impl Serialize for FeatureId {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        match self {
+            FeatureId::String(s) => serializer.serialize_str(s),
+            FeatureId::Number(n) => serializer.serialize_number(n.clone()),
+        }
+    }
+}
+
+/// `geometry` and `properties` are always written, as `null` when unset.
+impl Serialize for Feature {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", "Feature")?;
+        if let Some(id) = &self.id {
+            map.serialize_entry("id", id)?;
+        }
+        if let Some(bbox) = &self.bbox {
+            map.serialize_entry("bbox", &Coordinates(bbox))?;
+        }
+        map.serialize_entry("geometry", &self.geometry)?;
+        map.serialize_entry("properties", &self.properties)?;
+        for (key, value) in &self.foreign_members {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl Serialize for FeatureCollection {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", "FeatureCollection")?;
+        if let Some(bbox) = &self.bbox {
+            map.serialize_entry("bbox", &Coordinates(bbox))?;
+        }
+        map.serialize_entry("features", &self.features)?;
+        for (key, value) in &self.foreign_members {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+impl Serialize for GeoJson {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        match self {
+            GeoJson::Geometry(geometry) => geometry.serialize(serializer),
+            GeoJson::Feature(feature) => feature.serialize(serializer),
+            GeoJson::FeatureCollection(collection) => collection.serialize(serializer),
+        }
+    }
+}
@@ -0,0 +1,256 @@
+//! Reading and writing the JSON inside JSON Web Tokens: base64url-encoded
+//! headers and claims (RFC 7515, RFC 7519).
+//!
+//!     let token = jwt::Token::decode_unverified(bearer)?;
+//!     let claims: jwt::Claims = token.claims_as()?;
+//!     if !claims.is_valid_at(SystemTime::now(), Duration::from_secs(30)) { ... }
+//!
+//! Nothing here checks signatures. Verify the token with a JOSE library
+//! before trusting what it says.
+
+use crate::deserialize::{from_value, Deserialize, Deserializer, ValueDeserializer};
+use crate::serialize::{to_value, Serialize, SerializeMap, Serializer};
+use crate::with::system_time_secs;
+use rson_core::{to_string, Error, Result, Rson, Value};
+use std::collections::BTreeMap;
+use std::time::{Duration, SystemTime};
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Encodes `bytes` as base64url without padding, as JWTs use it.
+pub fn base64url_encode(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity((bytes.len() * 4).div_ceil(3));
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &byte)| n | (byte as u32) << (16 - 8 * i));
+        for i in 0..=chunk.len() {
+            text.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+    }
+    text
+}
+
+/// Decodes unpadded base64url. Padding, the `+` and `/` of plain base64
+/// and leftover bits that aren't zero are rejected.
+pub fn base64url_decode(text: &str) -> Result<Vec<u8>> {
+    if text.len() % 4 == 1 {
+        return Err(Error::custom(format!(
+            "invalid base64url length {}",
+            text.len()
+        )));
+    }
+
+    let mut bytes = Vec::with_capacity(text.len() * 3 / 4);
+    let (mut n, mut bits) = (0u32, 0);
+    for (i, c) in text.bytes().enumerate() {
+        let sextet = match c {
+            b'A'..=b'Z' => c - b'A',
+            b'a'..=b'z' => c - b'a' + 26,
+            b'0'..=b'9' => c - b'0' + 52,
+            b'-' => 62,
+            b'_' => 63,
+            _ => {
+                return Err(Error::custom(format!(
+                    "invalid base64url character {:?} at {}",
+                    text[i..].chars().next().unwrap_or_default(),
+                    i
+                )))
+            }
+        };
+        n = n << 6 | sextet as u32;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((n >> bits) as u8);
+            n &= (1 << bits) - 1;
+        }
+    }
+    if n != 0 {
+        return Err(Error::custom(
+            "invalid base64url: trailing bits are not zero",
+        ));
+    }
+    Ok(bytes)
+}
+
+/// Decodes one base64url segment of a token into JSON.
+pub fn decode_segment(segment: &str) -> Result<Value> {
+    Rson::try_from_reader(&base64url_decode(segment)?[..])
+}
+
+/// Like `decode_segment`, converting the JSON into `T`.
+pub fn decode_segment_as<T: Deserialize>(segment: &str) -> Result<T> {
+    from_value(decode_segment(segment)?)
+}
+
+/// Encodes `value` as compact JSON in base64url, a header or claims
+/// segment ready to be signed.
+pub fn encode_segment<T: Serialize + ?Sized>(value: &T) -> Result<String> {
+    Ok(base64url_encode(to_string(&to_value(value)?).as_bytes()))
+}
+
+/// The three parts of a compact JWS, `header.claims.signature`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Token {
+    pub header: Value,
+    pub claims: Value,
+    pub signature: Vec<u8>,
+}
+
+impl Token {
+    /// Decodes a token without verifying its signature, for logging,
+    /// routing or inspecting tokens that were verified elsewhere.
+    pub fn decode_unverified(token: &str) -> Result<Self> {
+        let parts: Vec<_> = token.trim().split('.').collect();
+        if parts.len() != 3 {
+            return Err(Error::custom(format!(
+                "a JWS has 3 parts separated by `.`, found {}",
+                parts.len()
+            )));
+        }
+        Ok(Self {
+            header: decode_segment(parts[0])?,
+            claims: decode_segment(parts[1])?,
+            signature: base64url_decode(parts[2])?,
+        })
+    }
+
+    /// The `alg` header, e.g. `"RS256"`.
+    pub fn algorithm(&self) -> Option<&str> {
+        match self.header.pointer("/alg")? {
+            Value::String(alg) => Some(alg),
+            _ => None,
+        }
+    }
+
+    pub fn claims_as<T: Deserialize>(&self) -> Result<T> {
+        from_value(self.claims.clone())
+    }
+}
+
+/// The registered claims of RFC 7519 and any others. `exp`, `nbf` and
+/// `iat` are NumericDates, seconds since the Unix epoch that may have a
+/// fraction; in claims types of your own, read them with
+/// `#[rson(with = "rson::with::system_time_secs")]`.
+#[derive(Debug, Clone, Eq, PartialEq, Default)]
+pub struct Claims {
+    pub iss: Option<String>,
+    pub sub: Option<String>,
+    /// Read from a single string or an array of them. One audience is
+    /// written as a string.
+    pub aud: Vec<String>,
+    pub exp: Option<SystemTime>,
+    pub nbf: Option<SystemTime>,
+    pub iat: Option<SystemTime>,
+    pub jti: Option<String>,
+    /// Every other claim.
+    pub extra: BTreeMap<String, Value>,
+}
+
+impl Claims {
+    /// Whether `exp` has passed at `now`, allowing for clocks that are
+    /// `leeway` apart.
+    pub fn is_expired_at(&self, now: SystemTime, leeway: Duration) -> bool {
+        self.exp
+            .is_some_and(|exp| exp.checked_add(leeway).is_some_and(|t| t <= now))
+    }
+
+    /// Whether the token may be used at `now`: not expired and not before
+    /// `nbf`, both with `leeway`.
+    pub fn is_valid_at(&self, now: SystemTime, leeway: Duration) -> bool {
+        !self.is_expired_at(now, leeway)
+            && self
+                .nbf
+                .is_none_or(|nbf| now.checked_add(leeway).is_none_or(|t| nbf <= t))
+    }
+}
+
+const REGISTERED: &[&str] = &["iss", "sub", "aud", "exp", "nbf", "iat", "jti"];
+
+/// A NumericDate, written by `system_time_secs`.
+struct NumericDate<'a>(&'a SystemTime);
+
+impl Serialize for NumericDate<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        system_time_secs::serialize(self.0, serializer)
+    }
+}
+
+impl Serialize for Claims {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        for (key, value) in [("iss", &self.iss), ("sub", &self.sub)] {
+            if let Some(value) = value {
+                map.serialize_entry(key, value)?;
+            }
+        }
+        match &self.aud[..] {
+            [] => {}
+            [aud] => map.serialize_entry("aud", aud)?,
+            aud => map.serialize_entry("aud", aud)?,
+        }
+        for (key, time) in [("exp", &self.exp), ("nbf", &self.nbf), ("iat", &self.iat)] {
+            if let Some(time) = time {
+                map.serialize_entry(key, &NumericDate(time))?;
+            }
+        }
+        if let Some(jti) = &self.jti {
+            map.serialize_entry("jti", jti)?;
+        }
+        for (key, value) in &self.extra {
+            if !REGISTERED.contains(&key.as_str()) {
+                map.serialize_entry(key, value)?;
+            }
+        }
+        map.end()
+    }
+}
+
+impl Deserialize for Claims {
+    fn deserialize<D>(deserializer: D) -> Result<Self>
+    where
+        D: Deserializer,
+    {
+        let map = match Value::deserialize(deserializer)? {
+            Value::Object(map) => map.0,
+            other => return Err(Error::invalid_type(&other.unexpected(), "a claims object")),
+        };
+
+        let mut claims = Claims::default();
+        for (key, value) in map {
+            let at = format!("/{}", key);
+            match key.as_str() {
+                "iss" => claims.iss = Some(from_value(value).map_err(|e| e.at(&at))?),
+                "sub" => claims.sub = Some(from_value(value).map_err(|e| e.at(&at))?),
+                "jti" => claims.jti = Some(from_value(value).map_err(|e| e.at(&at))?),
+                "aud" => {
+                    claims.aud = match value {
+                        Value::String(aud) => vec![aud],
+                        value => from_value(value).map_err(|e| e.at(&at))?,
+                    }
+                }
+                "exp" | "nbf" | "iat" => {
+                    let time = system_time_secs::deserialize(ValueDeserializer::new(value))
+                        .map_err(|e| e.at(&at))?;
+                    *match key.as_str() {
+                        "exp" => &mut claims.exp,
+                        "nbf" => &mut claims.nbf,
+                        _ => &mut claims.iat,
+                    } = Some(time);
+                }
+                _ => {
+                    claims.extra.insert(key, value);
+                }
+            }
+        }
+        Ok(claims)
+    }
+}
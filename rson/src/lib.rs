@@ -1,9 +1,22 @@
 #![allow(dead_code)]
 mod deserialize;
-mod rson;
-mod value;
+#[cfg(feature = "geojson")]
+pub mod geojson;
+pub mod jwt;
+pub mod ndjson;
+mod problem;
+mod schema;
+mod serialize;
+mod std_types;
+pub mod testing;
+mod urlencoded;
+pub mod with;
 
 pub use deserialize::*;
-pub use rson::*;
+pub use problem::*;
+pub use rson_core::*;
+#[cfg(feature = "derive")]
 pub use rson_derive::*;
-pub use value::*;
+pub use schema::*;
+pub use serialize::*;
+pub use urlencoded::*;
@@ -0,0 +1,347 @@
+//! Reading newline-delimited JSON (JSON Lines), one value per line, as
+//! written by most structured loggers.
+//!
+//!     for record in ndjson::filter(file, "$.level == 'error'")? {
+//!         println!("{}", record?.expect_str("/message")?);
+//!     }
+
+use rson_core::{Error, ErrorKind, ParseOptions, Result, Rson, Value};
+use std::cmp::Ordering;
+use std::io::{self, BufRead, BufReader, Read};
+
+/// Iterates over the records of `reader` as they are read. Blank lines
+/// are skipped. A record that doesn't parse, or isn't UTF-8, yields an
+/// error positioned at its line and reading goes on with the next one;
+/// an I/O error ends the iteration.
+pub fn lines<R: Read>(reader: R) -> Lines<R> {
+    Lines {
+        reader: BufReader::new(reader),
+        parser: Rson::with_options(ParseOptions::default()),
+        buf: Vec::new(),
+        line: 0,
+        done: false,
+    }
+}
+
+/// Like `lines`, yielding only the records that match `expression`, see
+/// `Filter`. Errors are passed through so a bad record isn't mistaken for
+/// a non-matching one.
+pub fn filter<R: Read>(reader: R, expression: &str) -> Result<impl Iterator<Item = Result<Value>>> {
+    let filter = Filter::new(expression)?;
+    Ok(lines(reader).filter(move |record| match record {
+        Ok(value) => filter.matches(value),
+        Err(_) => true,
+    }))
+}
+
+/// The iterator returned by `lines`.
+pub struct Lines<R> {
+    reader: BufReader<R>,
+    /// Parses every record, reusing its buffers.
+    parser: Rson<'static, io::Empty>,
+    buf: Vec<u8>,
+    line: usize,
+    done: bool,
+}
+
+impl<R: Read> Iterator for Lines<R> {
+    type Item = Result<Value>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            self.buf.clear();
+            match self.reader.read_until(b'\n', &mut self.buf) {
+                Ok(0) => self.done = true,
+                Ok(_) => {
+                    self.line += 1;
+                    let record = match std::str::from_utf8(&self.buf) {
+                        Ok(record) => record.trim(),
+                        Err(e) => {
+                            return Some(Err(Error::syntax(
+                                ErrorKind::InvalidUtf8,
+                                "invalid UTF-8",
+                                self.line,
+                                e.valid_up_to() + 1,
+                            )))
+                        }
+                    };
+                    if !record.is_empty() {
+                        return Some(
+                            self.parser
+                                .parse_str(record)
+                                .map_err(|e| e.on_line(self.line)),
+                        );
+                    }
+                }
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(Error::custom(format!("I/O error: {}", e))));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// A test of one value in a record, in a small subset of JSONPath filter
+/// syntax: a path made of `.name`, `['name']` and `[index]` steps from
+/// `$`, optionally compared with `==`, `!=`, `<`, `<=`, `>` or `>=` to a
+/// literal (a string in single or double quotes, a number, `true`,
+/// `false` or `null`).
+///
+///     $.level == 'error'
+///     $.http['status'] >= 500
+///     $.tags[0]
+///
+/// A path alone matches records where it exists. A record where the path
+/// is missing never matches a comparison, not even `!=`. Numbers compare
+/// by value, strings by their characters; ordering anything else doesn't
+/// match.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Filter {
+    path: Vec<Step>,
+    test: Option<(Op, Value)>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Step {
+    Key(String),
+    Index(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Op {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl Filter {
+    /// Parses `expression`. Errors give the column in the expression.
+    pub fn new(expression: &str) -> Result<Self> {
+        let mut parser = Parser {
+            chars: expression.chars().collect(),
+            at: 0,
+        };
+        let filter = parser.filter()?;
+        parser.skip_white();
+        if parser.at < parser.chars.len() {
+            return Err(parser.error("trailing characters in the expression"));
+        }
+        Ok(filter)
+    }
+
+    pub fn matches(&self, record: &Value) -> bool {
+        let found = self
+            .path
+            .iter()
+            .try_fold(record, |value, step| match (value, step) {
+                (Value::Object(map), Step::Key(key)) => map.0.get(key),
+                (Value::Array(array), Step::Index(i)) => array.get(*i),
+                _ => None,
+            });
+        let (found, (op, literal)) = match (found, &self.test) {
+            (None, _) => return false,
+            (Some(_), None) => return true,
+            (Some(found), Some(test)) => (found, test),
+        };
+
+        let ordering = match (found, literal) {
+            (Value::Number(a), Value::Number(b)) => {
+                cmp_integers(a.as_str(), b.as_str()).or_else(|| {
+                    match (a.as_str().parse::<f64>(), b.as_str().parse::<f64>()) {
+                        (Ok(a), Ok(b)) => a.partial_cmp(&b),
+                        _ => None,
+                    }
+                })
+            }
+            (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+            _ => None,
+        };
+        match (op, ordering) {
+            (Op::Eq, Some(ordering)) => ordering == Ordering::Equal,
+            (Op::Ne, Some(ordering)) => ordering != Ordering::Equal,
+            (Op::Eq, None) => found == literal,
+            (Op::Ne, None) => found != literal,
+            (_, None) => false,
+            (Op::Lt, Some(ordering)) => ordering == Ordering::Less,
+            (Op::Le, Some(ordering)) => ordering != Ordering::Greater,
+            (Op::Gt, Some(ordering)) => ordering == Ordering::Greater,
+            (Op::Ge, Some(ordering)) => ordering != Ordering::Less,
+        }
+    }
+}
+
+/// Orders two integers written in decimal exactly, however many digits
+/// they have, or `None` if either isn't one.
+fn cmp_integers(a: &str, b: &str) -> Option<Ordering> {
+    fn parts(text: &str) -> Option<(bool, &str)> {
+        let (negative, digits) = match text.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, text),
+        };
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        let digits = digits.trim_start_matches('0');
+        Some((negative && !digits.is_empty(), digits))
+    }
+
+    let (a_negative, a) = parts(a)?;
+    let (b_negative, b) = parts(b)?;
+    let magnitude = a.len().cmp(&b.len()).then_with(|| a.cmp(b));
+    Some(match (a_negative, b_negative) {
+        (false, false) => magnitude,
+        (true, true) => magnitude.reverse(),
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+    })
+}
+
+struct Parser {
+    chars: Vec<char>,
+    at: usize,
+}
+
+impl Parser {
+    fn filter(&mut self) -> Result<Filter> {
+        self.skip_white();
+        if !self.eat('$') {
+            return Err(self.error("expected `$` at the start of the path"));
+        }
+
+        let mut path = vec![];
+        loop {
+            if self.eat('.') {
+                let name = self.take_while(|c| c.is_alphanumeric() || c == '_' || c == '-');
+                if name.is_empty() {
+                    return Err(self.error("expected a name after `.`"));
+                }
+                path.push(Step::Key(name));
+            } else if self.eat('[') {
+                path.push(match self.peek() {
+                    Some(quote @ ('\'' | '"')) => Step::Key(self.quoted(quote)?),
+                    _ => {
+                        let index = self.take_while(|c| c.is_ascii_digit());
+                        Step::Index(
+                            index
+                                .parse()
+                                .map_err(|_| self.error("expected an index or a quoted name"))?,
+                        )
+                    }
+                });
+                if !self.eat(']') {
+                    return Err(self.error("expected `]`"));
+                }
+            } else {
+                break;
+            }
+        }
+
+        self.skip_white();
+        if self.at == self.chars.len() {
+            return Ok(Filter { path, test: None });
+        }
+        let op = self.op()?;
+        self.skip_white();
+        let literal = self.literal()?;
+        Ok(Filter {
+            path,
+            test: Some((op, literal)),
+        })
+    }
+
+    fn op(&mut self) -> Result<Op> {
+        let start = self.at;
+        let op = self.take_while(|c| "=!<>".contains(c));
+        Ok(match op.as_str() {
+            "==" => Op::Eq,
+            "!=" => Op::Ne,
+            "<" => Op::Lt,
+            "<=" => Op::Le,
+            ">" => Op::Gt,
+            ">=" => Op::Ge,
+            _ => {
+                self.at = start;
+                return Err(self.error("expected `==`, `!=`, `<`, `<=`, `>` or `>=`"));
+            }
+        })
+    }
+
+    fn literal(&mut self) -> Result<Value> {
+        match self.peek() {
+            Some(quote @ ('\'' | '"')) => self.quoted(quote).map(Value::String),
+            _ => {
+                let start = self.at;
+                let token = self.take_while(|c| c.is_alphanumeric() || "+-.".contains(c));
+                match Rson::try_from_reader_unrecorded(token.as_bytes()) {
+                    Ok(value @ (Value::Literal(_) | Value::Number(_))) => Ok(value),
+                    _ => {
+                        self.at = start;
+                        Err(self.error("expected a string, number, `true`, `false` or `null`"))
+                    }
+                }
+            }
+        }
+    }
+
+    /// A string in `quote`s, where `\` takes the next character as is.
+    fn quoted(&mut self, quote: char) -> Result<String> {
+        self.at += 1;
+        let mut s = String::new();
+        loop {
+            match self.next() {
+                Some(c) if c == quote => return Ok(s),
+                Some('\\') => match self.next() {
+                    Some(c) => s.push(c),
+                    None => break,
+                },
+                Some(c) => s.push(c),
+                None => break,
+            }
+        }
+        Err(Error::syntax(
+            ErrorKind::UnexpectedEof,
+            "EOF in a string",
+            1,
+            self.at + 1,
+        ))
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.at).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.at += 1;
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.at += 1;
+            return true;
+        }
+        false
+    }
+
+    fn take_while(&mut self, f: impl Fn(char) -> bool) -> String {
+        let start = self.at;
+        while self.peek().is_some_and(&f) {
+            self.at += 1;
+        }
+        self.chars[start..self.at].iter().collect()
+    }
+
+    fn skip_white(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    fn error(&self, message: &str) -> Error {
+        Error::syntax(ErrorKind::UnexpectedToken, message, 1, self.at + 1)
+    }
+}
@@ -0,0 +1,234 @@
+//! Error bodies for HTTP APIs in the shape of RFC 7807, "Problem Details
+//! for HTTP APIs".
+
+use crate::deserialize::{from_value, Deserialize, Deserializer, MapAccess, Visitor};
+use crate::serialize::{Serialize, SerializeMap, Serializer};
+use rson_core::{Error, Result, Value};
+use std::collections::BTreeMap;
+
+/// The media type of a `ProblemDetails` body.
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// A problem details object, served as `application/problem+json`:
+///
+///     let problem = ProblemDetails::new(404)
+///         .with_detail(format!("no order {}", id))
+///         .with_extension("order_id", id);
+///     respond(problem.status, PROBLEM_JSON, to_string(&to_value(&problem)?));
+///
+/// Members of the wrong type are ignored when reading, as the RFC asks,
+/// and unknown members are kept in `extensions`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ProblemDetails {
+    /// A URI reference identifying the kind of problem, `about:blank` when
+    /// the status code says it all.
+    pub r#type: String,
+    /// A short summary of the kind of problem, the same for every
+    /// occurrence.
+    pub title: Option<String>,
+    pub status: Option<u16>,
+    /// What went wrong this time.
+    pub detail: Option<String>,
+    /// A URI reference identifying this occurrence.
+    pub instance: Option<String>,
+    /// Members beyond the standard ones, e.g. the fields that failed
+    /// validation.
+    pub extensions: BTreeMap<String, Value>,
+}
+
+const ABOUT_BLANK: &str = "about:blank";
+
+impl Default for ProblemDetails {
+    fn default() -> Self {
+        Self {
+            r#type: ABOUT_BLANK.to_string(),
+            title: None,
+            status: None,
+            detail: None,
+            instance: None,
+            extensions: BTreeMap::new(),
+        }
+    }
+}
+
+impl ProblemDetails {
+    /// An `about:blank` problem for an HTTP status code, titled with the
+    /// code's reason phrase if it is a common one.
+    pub fn new(status: u16) -> Self {
+        Self {
+            title: reason_phrase(status).map(str::to_string),
+            status: Some(status),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_type(self, r#type: impl Into<String>) -> Self {
+        Self {
+            r#type: r#type.into(),
+            ..self
+        }
+    }
+
+    pub fn with_title(self, title: impl Into<String>) -> Self {
+        Self {
+            title: Some(title.into()),
+            ..self
+        }
+    }
+
+    pub fn with_detail(self, detail: impl Into<String>) -> Self {
+        Self {
+            detail: Some(detail.into()),
+            ..self
+        }
+    }
+
+    pub fn with_instance(self, instance: impl Into<String>) -> Self {
+        Self {
+            instance: Some(instance.into()),
+            ..self
+        }
+    }
+
+    /// Adds an extension member. Standard member names are ignored here,
+    /// set the fields instead.
+    pub fn with_extension(mut self, key: impl Into<String>, value: impl Into<Value>) -> Self {
+        let key = key.into();
+        if !is_standard(&key) {
+            self.extensions.insert(key, value.into());
+        }
+        self
+    }
+}
+
+/// A `400 Bad Request` for a body that didn't parse or didn't fit the
+/// expected type, with the error's line and column as extensions when it
+/// has them.
+impl From<&Error> for ProblemDetails {
+    fn from(err: &Error) -> Self {
+        let problem = ProblemDetails::new(400).with_detail(err.to_string());
+        if err.line() == 0 {
+            return problem;
+        }
+        problem
+            .with_extension("line", err.line())
+            .with_extension("column", err.column())
+    }
+}
+
+impl From<Error> for ProblemDetails {
+    fn from(err: Error) -> Self {
+        ProblemDetails::from(&err)
+    }
+}
+
+fn is_standard(key: &str) -> bool {
+    ["type", "title", "status", "detail", "instance"].contains(&key)
+}
+
+fn reason_phrase(status: u16) -> Option<&'static str> {
+    Some(match status {
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        402 => "Payment Required",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        406 => "Not Acceptable",
+        408 => "Request Timeout",
+        409 => "Conflict",
+        410 => "Gone",
+        411 => "Length Required",
+        412 => "Precondition Failed",
+        413 => "Payload Too Large",
+        415 => "Unsupported Media Type",
+        422 => "Unprocessable Entity",
+        428 => "Precondition Required",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        501 => "Not Implemented",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => return None,
+    })
+}
+
+/// `type` is always written, the other standard members only when set.
+impl Serialize for ProblemDetails {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", &self.r#type)?;
+        if let Some(title) = &self.title {
+            map.serialize_entry("title", title)?;
+        }
+        if let Some(status) = &self.status {
+            map.serialize_entry("status", status)?;
+        }
+        if let Some(detail) = &self.detail {
+            map.serialize_entry("detail", detail)?;
+        }
+        if let Some(instance) = &self.instance {
+            map.serialize_entry("instance", instance)?;
+        }
+        for (key, value) in &self.extensions {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+struct ProblemVisitor;
+
+impl Visitor for ProblemVisitor {
+    type Value = ProblemDetails;
+
+    fn expecting(&self) -> &'static str {
+        "a problem details object"
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<ProblemDetails>
+    where
+        A: MapAccess,
+    {
+        let mut problem = ProblemDetails::default();
+        while let Some(key) = map.next_key()? {
+            let value: Value = map.next_value()?;
+            let string = match &value {
+                Value::String(s) => Some(s.clone()),
+                _ => None,
+            };
+            match key.as_str() {
+                "type" => {
+                    if let Some(s) = string {
+                        problem.r#type = s;
+                    }
+                }
+                "title" => problem.title = string.or(problem.title),
+                "detail" => problem.detail = string.or(problem.detail),
+                "instance" => problem.instance = string.or(problem.instance),
+                "status" => {
+                    if let Value::Number(_) = value {
+                        problem.status = from_value(value).ok().or(problem.status);
+                    }
+                }
+                _ => {
+                    problem.extensions.insert(key, value);
+                }
+            }
+        }
+        Ok(problem)
+    }
+}
+
+impl Deserialize for ProblemDetails {
+    fn deserialize<D>(deserializer: D) -> Result<Self>
+    where
+        D: Deserializer,
+    {
+        deserializer.deserialize_any(ProblemVisitor)
+    }
+}
@@ -0,0 +1,253 @@
+use rson_core::{Literal, Number, RsonMap, Value};
+use std::collections::{BTreeMap, HashMap};
+
+/// The JSON Schema dialect `schema_for` declares in `$schema`.
+pub const SCHEMA_DIALECT: &str = "http://json-schema.org/draft-07/schema#";
+
+/// Types that can describe their JSON representation as a JSON Schema.
+/// Usually derived with `#[derive(RsonSchema)]`, which turns doc comments
+/// into `description`s.
+pub trait RsonSchema {
+    /// The schema of `Self`. Schemas of nested types should be built with
+    /// `gen.subschema_for::<T>()` so named types are only described once.
+    fn schema(gen: &mut SchemaGenerator) -> Value;
+
+    /// Named types are stored once under `definitions` and referenced with
+    /// `$ref`, which is also what makes recursive types finite. Types
+    /// returning `None` are inlined wherever they appear.
+    fn schema_name() -> Option<String> {
+        None
+    }
+
+    /// Whether a struct field of this type may be left out, which keeps it
+    /// out of the parent's `required` list.
+    fn is_optional() -> bool {
+        false
+    }
+}
+
+/// Collects the `definitions` of named types while a schema is built.
+#[derive(Debug, Default)]
+pub struct SchemaGenerator {
+    definitions: HashMap<String, Value>,
+}
+
+impl SchemaGenerator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the schema to use for a value of type `T`: a `$ref` for
+    /// named types, which are added to the definitions on first use, and
+    /// the schema itself otherwise.
+    pub fn subschema_for<T: RsonSchema + ?Sized>(&mut self) -> Value {
+        let name = match T::schema_name() {
+            Some(name) => name,
+            None => return T::schema(self),
+        };
+        if !self.definitions.contains_key(&name) {
+            // Reserve the name first so a recursive reference finds it.
+            self.definitions
+                .insert(name.clone(), Value::Literal(Literal::Null));
+            let schema = T::schema(self);
+            self.definitions.insert(name.clone(), schema);
+        }
+        object(vec![(
+            "$ref",
+            Value::from(format!("#/definitions/{}", name)),
+        )])
+    }
+
+    pub fn definitions(&self) -> &HashMap<String, Value> {
+        &self.definitions
+    }
+
+    /// Wraps `schema` into a root document carrying `$schema` and the
+    /// collected `definitions`.
+    pub fn into_root_schema(self, schema: Value) -> Value {
+        let mut root = match schema {
+            Value::Object(map) => map.0,
+            // `true`, `false` and friends can't carry keywords.
+            other => return other,
+        };
+        root.insert("$schema".to_string(), Value::from(SCHEMA_DIALECT));
+        if !self.definitions.is_empty() {
+            root.insert("definitions".to_string(), Value::from(self.definitions));
+        }
+        Value::Object(RsonMap(root))
+    }
+}
+
+/// Returns the root JSON Schema document for `T`.
+pub fn schema_for<T: RsonSchema + ?Sized>() -> Value {
+    let mut gen = SchemaGenerator::new();
+    let schema = T::schema(&mut gen);
+    gen.into_root_schema(schema)
+}
+
+pub(crate) fn object(members: Vec<(&str, Value)>) -> Value {
+    Value::Object(RsonMap(
+        members
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+    ))
+}
+
+fn of_type(ty: &str) -> Value {
+    object(vec![("type", Value::from(ty))])
+}
+
+impl RsonSchema for Value {
+    /// Any JSON document.
+    fn schema(_gen: &mut SchemaGenerator) -> Value {
+        object(vec![])
+    }
+}
+
+impl RsonSchema for Number {
+    fn schema(_gen: &mut SchemaGenerator) -> Value {
+        of_type("number")
+    }
+}
+
+impl RsonSchema for () {
+    fn schema(_gen: &mut SchemaGenerator) -> Value {
+        of_type("null")
+    }
+}
+
+impl RsonSchema for bool {
+    fn schema(_gen: &mut SchemaGenerator) -> Value {
+        of_type("boolean")
+    }
+}
+
+impl RsonSchema for String {
+    fn schema(_gen: &mut SchemaGenerator) -> Value {
+        of_type("string")
+    }
+}
+
+impl RsonSchema for str {
+    fn schema(_gen: &mut SchemaGenerator) -> Value {
+        of_type("string")
+    }
+}
+
+impl RsonSchema for char {
+    fn schema(_gen: &mut SchemaGenerator) -> Value {
+        object(vec![
+            ("type", Value::from("string")),
+            ("minLength", Value::from(1)),
+            ("maxLength", Value::from(1)),
+        ])
+    }
+}
+
+macro_rules! schema_signed {
+    ($($ty:ty)*) => {
+        $(
+            impl RsonSchema for $ty {
+                fn schema(_gen: &mut SchemaGenerator) -> Value {
+                    of_type("integer")
+                }
+            }
+        )*
+    };
+}
+
+schema_signed!(i8 i16 i32 i64 i128 isize);
+
+macro_rules! schema_unsigned {
+    ($($ty:ty)*) => {
+        $(
+            impl RsonSchema for $ty {
+                fn schema(_gen: &mut SchemaGenerator) -> Value {
+                    object(vec![
+                        ("type", Value::from("integer")),
+                        ("minimum", Value::from(0)),
+                    ])
+                }
+            }
+        )*
+    };
+}
+
+schema_unsigned!(u8 u16 u32 u64 u128 usize);
+
+impl RsonSchema for f32 {
+    fn schema(_gen: &mut SchemaGenerator) -> Value {
+        of_type("number")
+    }
+}
+
+impl RsonSchema for f64 {
+    fn schema(_gen: &mut SchemaGenerator) -> Value {
+        of_type("number")
+    }
+}
+
+impl<T: RsonSchema> RsonSchema for Option<T> {
+    /// `null` is accepted as well as a value of `T`.
+    fn schema(gen: &mut SchemaGenerator) -> Value {
+        object(vec![(
+            "anyOf",
+            Value::Array(vec![gen.subschema_for::<T>(), of_type("null")]),
+        )])
+    }
+
+    fn is_optional() -> bool {
+        true
+    }
+}
+
+impl<T: RsonSchema + ?Sized> RsonSchema for Box<T> {
+    fn schema(gen: &mut SchemaGenerator) -> Value {
+        gen.subschema_for::<T>()
+    }
+
+    fn is_optional() -> bool {
+        T::is_optional()
+    }
+}
+
+impl<T: RsonSchema> RsonSchema for Vec<T> {
+    fn schema(gen: &mut SchemaGenerator) -> Value {
+        object(vec![
+            ("type", Value::from("array")),
+            ("items", gen.subschema_for::<T>()),
+        ])
+    }
+}
+
+impl<T: RsonSchema> RsonSchema for [T] {
+    fn schema(gen: &mut SchemaGenerator) -> Value {
+        Vec::<T>::schema(gen)
+    }
+}
+
+fn map_schema<V: RsonSchema>(gen: &mut SchemaGenerator) -> Value {
+    object(vec![
+        ("type", Value::from("object")),
+        ("additionalProperties", gen.subschema_for::<V>()),
+    ])
+}
+
+impl<V: RsonSchema> RsonSchema for HashMap<String, V> {
+    fn schema(gen: &mut SchemaGenerator) -> Value {
+        map_schema::<V>(gen)
+    }
+}
+
+impl<V: RsonSchema> RsonSchema for BTreeMap<String, V> {
+    fn schema(gen: &mut SchemaGenerator) -> Value {
+        map_schema::<V>(gen)
+    }
+}
+
+impl<V: RsonSchema> RsonSchema for RsonMap<String, V> {
+    fn schema(gen: &mut SchemaGenerator) -> Value {
+        map_schema::<V>(gen)
+    }
+}
@@ -1 +1,529 @@
+use rson_core::{to_string, Error, ErrorKind, Literal, Map, Number, Result, RsonMap, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Debug, Display, LowerExp};
+use std::hash::Hash;
 
+// *************** Serialize *****************
+
+/// A Rust type that can describe itself to a `Serializer`, the reverse of
+/// `Deserialize`:
+///
+///     #[derive(Serialize)]
+///     struct Person {
+///         name: String,
+///         scores: HashMap<u32, f64>,
+///     }
+pub trait Serialize {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer;
+}
+
+/// Converts `value` into a `Value` tree.
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> Result<Value> {
+    value.serialize(ValueSerializer::new())
+}
+
+/// Options for converting Rust types into JSON, for consumers that expect
+/// numbers in a particular form. `to_value` uses the defaults.
+///
+///     let options = SerializeOptions {
+///         float_format: FloatFormat {
+///             max_significant_digits: Some(3),
+///             ..FloatFormat::default()
+///         },
+///     };
+///     assert_eq!(options.to_string(&[1.23456])?, "[1.23]");
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct SerializeOptions {
+    /// How `f32` and `f64` values are written. Numbers already in a
+    /// `Value` and those written by `#[rson(with = "...")]` modules are
+    /// kept as they are.
+    pub float_format: FloatFormat,
+}
+
+impl SerializeOptions {
+    pub fn to_value<T: Serialize + ?Sized>(&self, value: &T) -> Result<Value> {
+        value.serialize(ValueSerializer::with_options(*self))
+    }
+
+    pub fn to_string<T: Serialize + ?Sized>(&self, value: &T) -> Result<String> {
+        Ok(to_string(&self.to_value(value)?))
+    }
+}
+
+/// How floats are turned into text. The default writes the shortest text
+/// that reads back as the same float, like `{:?}`: `0.1`, `1.0`, `1e21`.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub struct FloatFormat {
+    /// Round to at most this many significant digits (at least 1), e.g.
+    /// `Some(3)` writes `2.0 / 3.0` as `0.667` and `1.5` as `1.50`.
+    pub max_significant_digits: Option<usize>,
+    pub notation: Notation,
+    /// Drop zeros at the end of the fraction, and the `.` when none of it
+    /// is left: `1.50` becomes `1.5` and `2.0` becomes `2`.
+    pub trim_trailing_zeros: bool,
+}
+
+/// Whether floats are written with an exponent.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum Notation {
+    /// Scientific below `1e-4` and from `1e16` on, like `{:?}`.
+    #[default]
+    Auto,
+    /// Never an exponent: `1e21` is written as `1000000000000000000000.0`.
+    Fixed,
+    /// Always an exponent: `1234.5` is written as `1.2345e3`.
+    Scientific,
+}
+
+impl FloatFormat {
+    /// Formats a finite float given as `{:e}` text, so `f32`s keep their
+    /// own shortest digits.
+    fn format(&self, scientific: String) -> String {
+        let (mantissa, exponent) = scientific.split_once('e').expect("`{:e}` has an exponent");
+        let exponent: i64 = exponent.parse().expect("`{:e}` has an integer exponent");
+        let (sign, mantissa) = match mantissa.strip_prefix('-') {
+            Some(mantissa) => ("-", mantissa),
+            None => ("", mantissa),
+        };
+        let digits: String = mantissa.chars().filter(|c| *c != '.').collect();
+
+        let scientific = match self.notation {
+            Notation::Auto => digits != "0" && !(-4..16).contains(&exponent),
+            Notation::Fixed => false,
+            Notation::Scientific => true,
+        };
+        let (int, frac) = if scientific {
+            (digits[..1].to_string(), digits[1..].to_string())
+        } else if exponent < 0 {
+            let zeros = "0".repeat((-exponent - 1) as usize);
+            ("0".to_string(), zeros + &digits)
+        } else {
+            let split = exponent as usize + 1;
+            if split >= digits.len() {
+                (
+                    digits.clone() + &"0".repeat(split - digits.len()),
+                    String::new(),
+                )
+            } else {
+                (digits[..split].to_string(), digits[split..].to_string())
+            }
+        };
+
+        let frac = if self.trim_trailing_zeros {
+            frac.trim_end_matches('0')
+        } else {
+            &frac
+        };
+        let mut text = format!("{}{}", sign, int);
+        if !frac.is_empty() {
+            text.push('.');
+            text.push_str(frac);
+        } else if !scientific && !self.trim_trailing_zeros {
+            text.push_str(".0");
+        }
+        if scientific {
+            text.push_str(&format!("e{}", exponent));
+        }
+        text
+    }
+}
+
+/// A sink for the JSON data model. Arrays and objects are written through
+/// the `SerializeSeq` and `SerializeMap` handles returned by
+/// `serialize_seq` and `serialize_map`.
+pub trait Serializer: Sized {
+    type Ok;
+    type SerializeSeq: SerializeSeq<Ok = Self::Ok>;
+    type SerializeMap: SerializeMap<Ok = Self::Ok>;
+
+    fn serialize_null(self) -> Result<Self::Ok>;
+
+    fn serialize_bool(self, v: bool) -> Result<Self::Ok>;
+
+    fn serialize_number(self, v: Number) -> Result<Self::Ok>;
+
+    fn serialize_str(self, v: &str) -> Result<Self::Ok>;
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq>;
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap>;
+
+    /// Writes a float. Non-finite floats have no JSON form and become
+    /// `null`, as with `Value::from`; the rest are written like `{:?}`.
+    fn serialize_f64(self, v: f64) -> Result<Self::Ok> {
+        if v.is_finite() {
+            self.serialize_number(Number::new(format!("{:?}", v)))
+        } else {
+            self.serialize_null()
+        }
+    }
+
+    /// Like `serialize_f64`, with the shortest digits of an `f32`.
+    fn serialize_f32(self, v: f32) -> Result<Self::Ok> {
+        if v.is_finite() {
+            self.serialize_number(Number::new(format!("{:?}", v)))
+        } else {
+            self.serialize_null()
+        }
+    }
+}
+
+pub trait SerializeSeq {
+    type Ok;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()>;
+
+    fn end(self) -> Result<Self::Ok>;
+}
+
+pub trait SerializeMap {
+    type Ok;
+
+    /// Keys are always strings in JSON; maps with other key types convert
+    /// them with `Display` first.
+    fn serialize_entry<V: Serialize + ?Sized>(&mut self, key: &str, value: &V) -> Result<()>;
+
+    fn end(self) -> Result<Self::Ok>;
+}
+
+/// Builds a `Value` tree.
+pub struct ValueSerializer {
+    options: SerializeOptions,
+}
+
+impl ValueSerializer {
+    pub fn new() -> Self {
+        Self::with_options(SerializeOptions::default())
+    }
+
+    /// A serializer applying `options` to the value and everything in it.
+    pub fn with_options(options: SerializeOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl Default for ValueSerializer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Formats a float, or `null` if it isn't finite.
+fn float_value<F: Debug + LowerExp>(v: F, finite: bool, format: FloatFormat) -> Value {
+    if !finite {
+        return Value::Literal(Literal::Null);
+    }
+    let scientific = match format.max_significant_digits {
+        Some(digits) => format!("{:.*e}", digits.max(1) - 1, v),
+        None if format == FloatFormat::default() => {
+            return Value::Number(Number::new(format!("{:?}", v)));
+        }
+        None => format!("{:e}", v),
+    };
+    Value::Number(Number::new(format.format(scientific)))
+}
+
+impl Serializer for ValueSerializer {
+    type Ok = Value;
+    type SerializeSeq = SerializeArray;
+    type SerializeMap = SerializeObject;
+
+    fn serialize_null(self) -> Result<Value> {
+        Ok(Value::Literal(Literal::Null))
+    }
+
+    fn serialize_bool(self, v: bool) -> Result<Value> {
+        Ok(Value::Literal(Literal::Bool(v)))
+    }
+
+    fn serialize_number(self, v: Number) -> Result<Value> {
+        Ok(Value::Number(v))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Value> {
+        Ok(Value::String(v.to_string()))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SerializeArray> {
+        Ok(SerializeArray {
+            array: Vec::with_capacity(len.unwrap_or(0)),
+            options: self.options,
+        })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<SerializeObject> {
+        Ok(SerializeObject {
+            map: Map::with_capacity(len.unwrap_or(0)),
+            options: self.options,
+        })
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Value> {
+        Ok(float_value(v, v.is_finite(), self.options.float_format))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Value> {
+        Ok(float_value(v, v.is_finite(), self.options.float_format))
+    }
+}
+
+pub struct SerializeArray {
+    array: Vec<Value>,
+    options: SerializeOptions,
+}
+
+impl SerializeSeq for SerializeArray {
+    type Ok = Value;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<()> {
+        self.array
+            .push(value.serialize(ValueSerializer::with_options(self.options))?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(Value::Array(self.array))
+    }
+}
+
+pub struct SerializeObject {
+    map: Map<String, Value>,
+    options: SerializeOptions,
+}
+
+impl SerializeMap for SerializeObject {
+    type Ok = Value;
+
+    /// Fails if the key was already written, which happens when distinct
+    /// map keys have the same `Display` text.
+    fn serialize_entry<V: Serialize + ?Sized>(&mut self, key: &str, value: &V) -> Result<()> {
+        if self.map.contains_key(key) {
+            return Err(Error::new(
+                ErrorKind::DuplicateKey,
+                format!("duplicate key `{}` in serialized object", key),
+            ));
+        }
+        let value = value.serialize(ValueSerializer::with_options(self.options))?;
+        self.map.insert(key.to_string(), value);
+        Ok(())
+    }
+
+    fn end(self) -> Result<Value> {
+        Ok(Value::Object(RsonMap(self.map)))
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        match self {
+            Value::Literal(Literal::Null) => serializer.serialize_null(),
+            Value::Literal(Literal::Bool(b)) => serializer.serialize_bool(*b),
+            Value::Number(n) => serializer.serialize_number(n.clone()),
+            Value::String(s) => serializer.serialize_str(s),
+            Value::Array(array) => array.serialize(serializer),
+            Value::Object(map) => map.serialize(serializer),
+        }
+    }
+}
+
+impl Serialize for Number {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_number(self.clone())
+    }
+}
+
+impl Serialize for () {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_null()
+    }
+}
+
+impl Serialize for bool {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bool(*self)
+    }
+}
+
+impl Serialize for str {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self)
+    }
+}
+
+impl Serialize for String {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self)
+    }
+}
+
+impl Serialize for char {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.encode_utf8(&mut [0; 4]))
+    }
+}
+
+macro_rules! serialize_integer {
+    ($($ty:ty)*) => {
+        $(
+            impl Serialize for $ty {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+                where
+                    S: Serializer,
+                {
+                    serializer.serialize_number(Number::new(self.to_string()))
+                }
+            }
+        )*
+    };
+}
+
+serialize_integer!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+
+impl Serialize for f32 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_f32(*self)
+    }
+}
+
+impl Serialize for f64 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_f64(*self)
+    }
+}
+
+impl<T: Serialize + ?Sized> Serialize for &T {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<T: Serialize + ?Sized> Serialize for Box<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        (**self).serialize(serializer)
+    }
+}
+
+impl<T: Serialize> Serialize for Option<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        match self {
+            Some(value) => value.serialize(serializer),
+            None => serializer.serialize_null(),
+        }
+    }
+}
+
+impl<T: Serialize> Serialize for [T] {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for value in self {
+            seq.serialize_element(value)?;
+        }
+        seq.end()
+    }
+}
+
+impl<T: Serialize, const N: usize> Serialize for [T; N] {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        self[..].serialize(serializer)
+    }
+}
+
+impl<T: Serialize> Serialize for Vec<T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        self[..].serialize(serializer)
+    }
+}
+
+fn serialize_entries<'a, S, K, V, I>(serializer: S, len: usize, entries: I) -> Result<S::Ok>
+where
+    S: Serializer,
+    K: Display + 'a,
+    V: Serialize + 'a,
+    I: Iterator<Item = (&'a K, &'a V)>,
+{
+    let mut map = serializer.serialize_map(Some(len))?;
+    for (key, value) in entries {
+        map.serialize_entry(&key.to_string(), value)?;
+    }
+    map.end()
+}
+
+/// Keys are written with `Display` and read back with `FromStr`, so
+/// `HashMap<u32, T>` becomes `{"1": ...}`.
+impl<K: Display, V: Serialize> Serialize for HashMap<K, V> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        serialize_entries(serializer, self.len(), self.iter())
+    }
+}
+
+impl<K: Display, V: Serialize> Serialize for BTreeMap<K, V> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        serialize_entries(serializer, self.len(), self.iter())
+    }
+}
+
+impl<K, V> Serialize for RsonMap<K, V>
+where
+    K: Display + Hash + Ord,
+    V: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        serialize_entries(serializer, self.0.len(), self.0.iter())
+    }
+}
@@ -0,0 +1,233 @@
+//! `Serialize`, `Deserialize` and `RsonSchema` for standard library types
+//! that show up in configuration files: network addresses, non-zero
+//! integers and time.
+
+use crate::deserialize::{Deserialize, Deserializer, Visitor};
+use crate::schema::{object, RsonSchema, SchemaGenerator};
+use crate::serialize::{Serialize, SerializeMap, Serializer};
+use rson_core::Value;
+use rson_core::{Error, Result};
+use std::collections::BTreeMap;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::num::{
+    NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize, NonZeroU128,
+    NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize,
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Reads a string and parses it with `FromStr`.
+struct ParseVisitor<T>(std::marker::PhantomData<T>);
+
+macro_rules! string_types {
+    ($($ty:ty => $expecting:expr $(, $format:expr)?;)*) => {
+        $(
+            impl RsonSchema for $ty {
+                fn schema(_gen: &mut SchemaGenerator) -> Value {
+                    #[allow(unused_mut)]
+                    let mut members = vec![("type", Value::from("string"))];
+                    $(members.push(("format", Value::from($format)));)?
+                    object(members)
+                }
+            }
+
+            /// Written as its `Display` text, e.g. `"127.0.0.1:8080"`.
+            impl Serialize for $ty {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+                where
+                    S: Serializer,
+                {
+                    serializer.serialize_str(&self.to_string())
+                }
+            }
+
+            impl Visitor for ParseVisitor<$ty> {
+                type Value = $ty;
+
+                fn expecting(&self) -> &'static str {
+                    $expecting
+                }
+
+                fn visit_string(self, v: String) -> Result<$ty> {
+                    v.parse().map_err(|_| {
+                        Error::invalid_value(&format!("string {:?}", v), $expecting)
+                    })
+                }
+            }
+
+            impl Deserialize for $ty {
+                fn deserialize<D>(deserializer: D) -> Result<Self>
+                where
+                    D: Deserializer,
+                {
+                    deserializer.deserialize_string(ParseVisitor::<$ty>(std::marker::PhantomData))
+                }
+            }
+        )*
+    };
+}
+
+string_types! {
+    IpAddr => "an IP address";
+    Ipv4Addr => "an IPv4 address", "ipv4";
+    Ipv6Addr => "an IPv6 address", "ipv6";
+    SocketAddr => "a socket address";
+    SocketAddrV4 => "an IPv4 socket address";
+    SocketAddrV6 => "an IPv6 socket address";
+}
+
+macro_rules! nonzero_integers {
+    ($($ty:ty => $primitive:ty),* $(,)?) => {
+        $(
+            impl RsonSchema for $ty {
+                fn schema(gen: &mut SchemaGenerator) -> Value {
+                    let mut schema = <$primitive>::schema(gen);
+                    if let Value::Object(map) = &mut schema {
+                        map.0.insert("not".to_string(), object(vec![("const", Value::from(0))]));
+                    }
+                    schema
+                }
+            }
+
+            impl Serialize for $ty {
+                fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+                where
+                    S: Serializer,
+                {
+                    self.get().serialize(serializer)
+                }
+            }
+
+            impl Deserialize for $ty {
+                fn deserialize<D>(deserializer: D) -> Result<Self>
+                where
+                    D: Deserializer,
+                {
+                    let n = <$primitive>::deserialize(deserializer)?;
+                    <$ty>::new(n).ok_or_else(|| Error::invalid_value("number `0`", "a nonzero integer"))
+                }
+            }
+        )*
+    };
+}
+
+nonzero_integers! {
+    NonZeroI8 => i8,
+    NonZeroI16 => i16,
+    NonZeroI32 => i32,
+    NonZeroI64 => i64,
+    NonZeroI128 => i128,
+    NonZeroIsize => isize,
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroU128 => u128,
+    NonZeroUsize => usize,
+}
+
+/// Written as `{"secs": 1, "nanos": 500000000}`. Use `rson::with::duration_secs`
+/// or `rson::with::duration_millis` on a field for a plain number instead.
+impl Serialize for Duration {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("secs", &self.as_secs())?;
+        map.serialize_entry("nanos", &self.subsec_nanos())?;
+        map.end()
+    }
+}
+
+impl Deserialize for Duration {
+    fn deserialize<D>(deserializer: D) -> Result<Self>
+    where
+        D: Deserializer,
+    {
+        let (secs, nanos) = secs_and_nanos(deserializer, "secs", "nanos")?;
+        Ok(Duration::new(secs, nanos))
+    }
+}
+
+/// Written as the time since the Unix epoch,
+/// `{"secs_since_epoch": 1, "nanos_since_epoch": 0}`; times before the
+/// epoch can't be serialized. `rson::with::system_time_secs` and
+/// `rson::with::system_time_millis` write a plain number instead.
+impl Serialize for SystemTime {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok>
+    where
+        S: Serializer,
+    {
+        let since_epoch = self
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| Error::custom("SystemTime must be later than UNIX_EPOCH"))?;
+        let mut map = serializer.serialize_map(Some(2))?;
+        map.serialize_entry("secs_since_epoch", &since_epoch.as_secs())?;
+        map.serialize_entry("nanos_since_epoch", &since_epoch.subsec_nanos())?;
+        map.end()
+    }
+}
+
+impl Deserialize for SystemTime {
+    fn deserialize<D>(deserializer: D) -> Result<Self>
+    where
+        D: Deserializer,
+    {
+        let (secs, nanos) = secs_and_nanos(deserializer, "secs_since_epoch", "nanos_since_epoch")?;
+        UNIX_EPOCH
+            .checked_add(Duration::new(secs, nanos))
+            .ok_or_else(|| Error::custom("overflow deserializing SystemTime"))
+    }
+}
+
+impl RsonSchema for Duration {
+    fn schema(gen: &mut SchemaGenerator) -> Value {
+        secs_and_nanos_schema(gen, "secs", "nanos")
+    }
+}
+
+impl RsonSchema for SystemTime {
+    fn schema(gen: &mut SchemaGenerator) -> Value {
+        secs_and_nanos_schema(gen, "secs_since_epoch", "nanos_since_epoch")
+    }
+}
+
+fn secs_and_nanos_schema(gen: &mut SchemaGenerator, secs_key: &str, nanos_key: &str) -> Value {
+    let mut properties = std::collections::HashMap::new();
+    properties.insert(secs_key.to_string(), gen.subschema_for::<u64>());
+    properties.insert(nanos_key.to_string(), gen.subschema_for::<u32>());
+    object(vec![
+        ("type", Value::from("object")),
+        ("properties", Value::from(properties)),
+        (
+            "required",
+            Value::Array(vec![Value::from(secs_key), Value::from(nanos_key)]),
+        ),
+        ("additionalProperties", Value::from(false)),
+    ])
+}
+
+/// Reads an object holding exactly the two given members.
+fn secs_and_nanos<D: Deserializer>(
+    deserializer: D,
+    secs_key: &'static str,
+    nanos_key: &'static str,
+) -> Result<(u64, u32)> {
+    let mut map = BTreeMap::<String, u64>::deserialize(deserializer)?;
+    let secs = map
+        .remove(secs_key)
+        .ok_or_else(|| Error::missing_field(secs_key))?;
+    let nanos = map
+        .remove(nanos_key)
+        .ok_or_else(|| Error::missing_field(nanos_key))?;
+    if let Some(key) = map.keys().next() {
+        return Err(Error::custom(format!("unknown field `{}`", key)));
+    }
+    if nanos >= 1_000_000_000 {
+        return Err(Error::invalid_value(
+            &format!("number `{}`", nanos),
+            "less than a second of nanoseconds",
+        ));
+    }
+    Ok((secs, nanos as u32))
+}
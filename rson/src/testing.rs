@@ -0,0 +1,229 @@
+//! Helpers for tests that work with JSON documents.
+
+use rson_core::to_string;
+use rson_core::Value;
+use std::fmt;
+
+mod generate;
+pub mod golden;
+
+pub use generate::{generate, GeneratorConfig, TypeWeights};
+
+/// How `diff` treats values that only appear in `actual`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum CompareMode {
+    /// Both values must be identical.
+    Strict,
+    /// `expected` only needs to be contained in `actual`: objects may have
+    /// extra members and arrays extra trailing elements.
+    Include,
+}
+
+/// One place where two documents disagree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Difference {
+    /// Where the difference is, e.g. `$.users[2].name`.
+    pub path: String,
+    /// The value in `actual`, or `None` if it is missing there.
+    pub actual: Option<Value>,
+    /// The value in `expected`, or `None` if it is missing there.
+    pub expected: Option<Value>,
+}
+
+impl fmt::Display for Difference {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match (&self.actual, &self.expected) {
+            (Some(actual), Some(expected)) => write!(
+                f,
+                "at {}: expected {}, found {}",
+                self.path,
+                to_string(expected),
+                to_string(actual)
+            ),
+            (None, Some(expected)) => write!(
+                f,
+                "at {}: missing, expected {}",
+                self.path,
+                to_string(expected)
+            ),
+            (Some(actual), None) => write!(f, "at {}: unexpected {}", self.path, to_string(actual)),
+            (None, None) => write!(f, "at {}: no difference", self.path),
+        }
+    }
+}
+
+/// Lists where `actual` departs from `expected`, in document order with
+/// object members sorted by key. Numbers are equal when they are the same
+/// decimal (`1.0` and `1e0`), see `Number::same_value`.
+pub fn diff(actual: &Value, expected: &Value, mode: CompareMode) -> Vec<Difference> {
+    diff_within(actual, expected, mode, 0.0)
+}
+
+/// Like `diff`, but numbers are also equal when they are at most
+/// `float_tolerance` apart.
+fn diff_within(
+    actual: &Value,
+    expected: &Value,
+    mode: CompareMode,
+    float_tolerance: f64,
+) -> Vec<Difference> {
+    let mut differences = vec![];
+    let compare = Compare {
+        mode,
+        float_tolerance,
+    };
+    compare.diff_at(&mut String::from("$"), actual, expected, &mut differences);
+    differences
+}
+
+struct Compare {
+    mode: CompareMode,
+    float_tolerance: f64,
+}
+
+impl Compare {
+    fn diff_at(
+        &self,
+        path: &mut String,
+        actual: &Value,
+        expected: &Value,
+        differences: &mut Vec<Difference>,
+    ) {
+        let mode = self.mode;
+        let len = path.len();
+        match (actual, expected) {
+            (Value::Object(actual), Value::Object(expected)) => {
+                let mut keys: Vec<&String> = expected.0.keys().collect();
+                if mode == CompareMode::Strict {
+                    keys.extend(actual.0.keys().filter(|k| !expected.0.contains_key(*k)));
+                }
+                keys.sort();
+                for key in keys {
+                    push_key(path, key);
+                    match (actual.0.get(key), expected.0.get(key)) {
+                        (Some(a), Some(e)) => self.diff_at(path, a, e, differences),
+                        (a, e) => differences.push(Difference {
+                            path: path.clone(),
+                            actual: a.cloned(),
+                            expected: e.cloned(),
+                        }),
+                    }
+                    path.truncate(len);
+                }
+            }
+            (Value::Array(actual), Value::Array(expected)) => {
+                let count = match mode {
+                    CompareMode::Strict => actual.len().max(expected.len()),
+                    CompareMode::Include => expected.len(),
+                };
+                for i in 0..count {
+                    path.push_str(&format!("[{}]", i));
+                    match (actual.get(i), expected.get(i)) {
+                        (Some(a), Some(e)) => self.diff_at(path, a, e, differences),
+                        (a, e) => differences.push(Difference {
+                            path: path.clone(),
+                            actual: a.cloned(),
+                            expected: e.cloned(),
+                        }),
+                    }
+                    path.truncate(len);
+                }
+            }
+            (Value::Number(a), Value::Number(e)) => {
+                let same = a.same_value(e)
+                    || self.float_tolerance > 0.0
+                        && matches!(
+                            (a.as_str().parse::<f64>(), e.as_str().parse::<f64>()),
+                            (Ok(a), Ok(e)) if (a - e).abs() <= self.float_tolerance
+                        );
+                if !same {
+                    differences.push(Difference {
+                        path: path.clone(),
+                        actual: Some(Value::Number(a.clone())),
+                        expected: Some(Value::Number(e.clone())),
+                    });
+                }
+            }
+            (a, e) if a == e => {}
+            (a, e) => differences.push(Difference {
+                path: path.clone(),
+                actual: Some(a.clone()),
+                expected: Some(e.clone()),
+            }),
+        }
+    }
+}
+
+/// Appends `.key`, or `["key"]` when the key isn't a plain identifier.
+fn push_key(path: &mut String, key: &str) {
+    let plain = key.chars().next().is_some_and(|c| !c.is_ascii_digit())
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if plain {
+        path.push('.');
+        path.push_str(key);
+    } else {
+        path.push('[');
+        path.push_str(&to_string(&Value::from(key)));
+        path.push(']');
+    }
+}
+
+/// Backs `assert_json_eq!` and `assert_json_include!`.
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_json_matches(actual: &Value, expected: &Value, mode: CompareMode) {
+    let differences = diff(actual, expected, mode);
+    if differences.is_empty() {
+        return;
+    }
+    let header = match mode {
+        CompareMode::Strict => "JSON values are not equal:",
+        CompareMode::Include => "actual JSON does not include expected JSON:",
+    };
+    panic!("{}", report(header, &differences));
+}
+
+/// Formats `differences` as an indented list under `header`.
+fn report(header: &str, differences: &[Difference]) -> String {
+    let mut message = String::from(header);
+    for difference in differences {
+        message.push_str("\n    ");
+        message.push_str(&difference.to_string());
+    }
+    message
+}
+
+/// Asserts that two `Value`s are equal, panicking with a list of the
+/// paths where they differ:
+///
+///     assert_json_eq!(response, rson!({"id": 7, "tags": []}));
+#[macro_export]
+macro_rules! assert_json_eq {
+    ($actual:expr, $expected:expr $(,)?) => {
+        $crate::testing::assert_json_matches(
+            &$actual,
+            &$expected,
+            $crate::testing::CompareMode::Strict,
+        )
+    };
+}
+
+/// Asserts that `actual` contains `expected`: every member of an expected
+/// object and every element of an expected array must be present, but
+/// `actual` may have more. Handy for API responses carrying ids and
+/// timestamps a test doesn't care about:
+///
+///     assert_json_include!(actual: response, expected: rson!({"status": "ok"}));
+#[macro_export]
+macro_rules! assert_json_include {
+    (actual: $actual:expr, expected: $expected:expr $(,)?) => {
+        $crate::testing::assert_json_matches(
+            &$actual,
+            &$expected,
+            $crate::testing::CompareMode::Include,
+        )
+    };
+    (expected: $expected:expr, actual: $actual:expr $(,)?) => {
+        $crate::assert_json_include!(actual: $actual, expected: $expected)
+    };
+}
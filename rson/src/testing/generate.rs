@@ -0,0 +1,193 @@
+//! Reproducible random documents for benchmarks, fuzzing and load tests.
+//!
+//!     let config = GeneratorConfig { max_depth: 6, ..GeneratorConfig::default() };
+//!     let documents: Vec<Value> = (0..1000).map(|seed| generate(seed, &config)).collect();
+
+use rson_core::{Literal, Map, Number, RsonMap, Value};
+use std::ops::RangeInclusive;
+
+/// The shape of the documents `generate` makes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeneratorConfig {
+    /// How deeply arrays and objects nest. At this depth only scalars are
+    /// generated; 0 makes every document a scalar.
+    pub max_depth: usize,
+    /// How many elements an array, or members an object, has.
+    pub fan_out: RangeInclusive<usize>,
+    /// Length of strings and object keys, in characters.
+    pub string_len: RangeInclusive<usize>,
+    pub weights: TypeWeights,
+}
+
+impl Default for GeneratorConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: 4,
+            fan_out: 0..=8,
+            string_len: 0..=16,
+            weights: TypeWeights::default(),
+        }
+    }
+}
+
+/// How often each kind of value is picked, relative to the others. A
+/// weight of 0 leaves that kind out; if every kind allowed at some depth
+/// is 0, `null` is generated there.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct TypeWeights {
+    pub null: u32,
+    pub bool: u32,
+    pub number: u32,
+    pub string: u32,
+    pub array: u32,
+    pub object: u32,
+}
+
+impl Default for TypeWeights {
+    fn default() -> Self {
+        Self {
+            null: 1,
+            bool: 2,
+            number: 4,
+            string: 4,
+            array: 2,
+            object: 2,
+        }
+    }
+}
+
+/// Generates a pseudo-random document. The same `seed` and `config` give
+/// the same document on every platform and in every version of `rson`
+/// that doesn't change this generator.
+pub fn generate(seed: u64, config: &GeneratorConfig) -> Value {
+    Generator {
+        rng: SplitMix64(seed),
+        config,
+    }
+    .value(0)
+}
+
+/// Characters strings are built from: ASCII text, characters that must
+/// be escaped and some that take 2, 3 and 4 bytes in UTF-8.
+const CHARS: &[char] = &[
+    'a', 'b', 'c', 'd', 'e', 'f', 'g', 'h', 'i', 'j', 'k', 'l', 'm', 'n', 'o', 'p', 'q', 'r', 's',
+    't', 'u', 'v', 'w', 'x', 'y', 'z', 'A', 'B', 'C', 'D', 'E', 'F', '0', '1', '2', '3', '4', '5',
+    '6', '7', '8', '9', ' ', ' ', '-', '_', '.', '/', '"', '\\', '\n', '\t', 'é', 'ß', '€', '中',
+    '😀',
+];
+
+struct Generator<'a> {
+    rng: SplitMix64,
+    config: &'a GeneratorConfig,
+}
+
+impl Generator<'_> {
+    fn value(&mut self, depth: usize) -> Value {
+        let config = self.config;
+        let w = config.weights;
+        let nested = depth < config.max_depth;
+        let weights = [
+            w.null,
+            w.bool,
+            w.number,
+            w.string,
+            if nested { w.array } else { 0 },
+            if nested { w.object } else { 0 },
+        ];
+
+        let total: u64 = weights.iter().map(|&w| w as u64).sum();
+        if total == 0 {
+            return Value::Literal(Literal::Null);
+        }
+        let mut pick = self.rng.below(total);
+        let kind = weights
+            .iter()
+            .position(|&w| {
+                if pick < w as u64 {
+                    return true;
+                }
+                pick -= w as u64;
+                false
+            })
+            .unwrap_or(0);
+
+        match kind {
+            0 => Value::Literal(Literal::Null),
+            1 => Value::Literal(Literal::Bool(self.rng.below(2) == 1)),
+            2 => Value::Number(self.number()),
+            3 => Value::String(self.string(CHARS)),
+            4 => {
+                let len = self.len(&config.fan_out);
+                Value::Array((0..len).map(|_| self.value(depth + 1)).collect())
+            }
+            _ => {
+                let len = self.len(&config.fan_out);
+                let mut map = Map::new();
+                for _ in 0..len {
+                    let key = self.string(&CHARS[..26]);
+                    let value = self.value(depth + 1);
+                    map.insert(key, value);
+                }
+                Value::Object(RsonMap(map))
+            }
+        }
+    }
+
+    /// Integers, decimals and exponents, all valid JSON number text.
+    fn number(&mut self) -> Number {
+        let sign = if self.rng.below(4) == 0 { "-" } else { "" };
+        let text = match self.rng.below(8) {
+            0..=3 => format!("{}{}", sign, self.rng.below(1_000_000)),
+            4 => format!("{}{}", sign, self.rng.next() >> 1),
+            5 | 6 => format!(
+                "{}{}.{:03}",
+                sign,
+                self.rng.below(100_000),
+                self.rng.below(1000)
+            ),
+            _ => format!(
+                "{}{}.{}e{}",
+                sign,
+                1 + self.rng.below(9),
+                self.rng.below(1000),
+                self.rng.below(600) as i64 - 300
+            ),
+        };
+        Number::new(text)
+    }
+
+    fn string(&mut self, chars: &[char]) -> String {
+        let config = self.config;
+        let len = self.len(&config.string_len);
+        (0..len)
+            .map(|_| chars[self.rng.below(chars.len() as u64) as usize])
+            .collect()
+    }
+
+    fn len(&mut self, range: &RangeInclusive<usize>) -> usize {
+        let (min, max) = (*range.start(), *range.end());
+        if max <= min {
+            return min;
+        }
+        min + self.rng.below((max - min) as u64 + 1) as usize
+    }
+}
+
+/// SplitMix64: small, fast and the same everywhere, which is all
+/// reproducible test data needs. Not for anything secret.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number below `n`, which must not be 0.
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
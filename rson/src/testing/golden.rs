@@ -0,0 +1,128 @@
+//! Snapshot tests against JSON fixture files.
+//!
+//!     let response = handle(request);
+//!     Golden::new("tests/fixtures/list_users.json")
+//!         .float_tolerance(1e-9)
+//!         .assert_matches(&response);
+//!
+//! Running the tests with `RSON_UPDATE_GOLDEN=1` writes the actual values
+//! to their fixtures instead of comparing, creating missing files.
+
+use super::{diff_within, report, CompareMode};
+use rson_core::Rson;
+use rson_core::Value;
+use rson_core::{to_canonical_string, to_string};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Set to anything but `0` or an empty string to regenerate fixtures.
+pub const UPDATE_ENV: &str = "RSON_UPDATE_GOLDEN";
+
+/// A fixture file holding the expected value of a test.
+#[derive(Debug, Clone)]
+pub struct Golden {
+    path: PathBuf,
+    float_tolerance: f64,
+    sort_keys: bool,
+    mode: CompareMode,
+}
+
+impl Golden {
+    /// A fixture at `path`, relative to the working directory, which is
+    /// the package root under `cargo test`.
+    pub fn new<P: AsRef<Path>>(path: P) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            float_tolerance: 0.0,
+            sort_keys: true,
+            mode: CompareMode::Strict,
+        }
+    }
+
+    /// Treats numbers at most `tolerance` apart as equal. Defaults to `0`.
+    pub fn float_tolerance(mut self, tolerance: f64) -> Self {
+        self.float_tolerance = tolerance;
+        self
+    }
+
+    /// Whether regenerated fixtures have their object keys sorted, which
+    /// keeps their diffs stable in review. Defaults to `true`.
+    pub fn sort_keys(mut self, sort_keys: bool) -> Self {
+        self.sort_keys = sort_keys;
+        self
+    }
+
+    /// Only requires the fixture to be included in the actual value, see
+    /// `CompareMode::Include`. Fixtures are still regenerated in full.
+    pub fn include(mut self) -> Self {
+        self.mode = CompareMode::Include;
+        self
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Reads and parses the fixture.
+    pub fn load(&self) -> io::Result<Value> {
+        let file = fs::File::open(&self.path)?;
+        Rson::try_from_reader(file).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Writes `value` as the new fixture, creating parent directories.
+    pub fn save(&self, value: &Value) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut text = if self.sort_keys {
+            to_canonical_string(value)
+        } else {
+            to_string(value)
+        };
+        text.push('\n');
+        fs::write(&self.path, text)
+    }
+
+    /// Panics unless `actual` matches the fixture, listing the
+    /// differences. Saves `actual` instead when `RSON_UPDATE_GOLDEN` is
+    /// set.
+    #[track_caller]
+    pub fn assert_matches(&self, actual: &Value) {
+        if update_requested() {
+            if let Err(e) = self.save(actual) {
+                panic!("failed to write fixture {}: {}", self.path.display(), e);
+            }
+            return;
+        }
+
+        let expected = match self.load() {
+            Ok(expected) => expected,
+            Err(e) => panic!(
+                "failed to read fixture {}: {}\nrun with {}=1 to create it",
+                self.path.display(),
+                e,
+                UPDATE_ENV
+            ),
+        };
+        let differences = diff_within(actual, &expected, self.mode, self.float_tolerance);
+        if !differences.is_empty() {
+            let header = format!(
+                "value does not match fixture {} (run with {}=1 to update it):",
+                self.path.display(),
+                UPDATE_ENV
+            );
+            panic!("{}", report(&header, &differences));
+        }
+    }
+}
+
+/// Shorthand for `Golden::new(path).assert_matches(actual)`.
+#[track_caller]
+pub fn assert_golden<P: AsRef<Path>>(path: P, actual: &Value) {
+    Golden::new(path).assert_matches(actual);
+}
+
+fn update_requested() -> bool {
+    std::env::var_os(UPDATE_ENV).is_some_and(|v| !v.is_empty() && v != "0")
+}
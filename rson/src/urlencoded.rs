@@ -0,0 +1,209 @@
+use rson_core::{Literal, Map, RsonMap, Value};
+
+/// Indices past this bound, e.g. `a[99999]=x`, are stored as object keys
+/// unless the array already has that element, so a short query can't
+/// pad an array with millions of `null`s.
+const ARRAY_INDEX_LIMIT: usize = 1000;
+
+/// Bridges `application/x-www-form-urlencoded` bodies and query strings
+/// into the `Value` model.
+///
+/// Keys use bracket notation for nesting:
+///     a=1            => {"a": "1"}
+///     a[b]=1         => {"a": {"b": "1"}}
+///     a[0]=x&a[1]=y  => {"a": ["x", "y"]}
+///     a[]=x&a[]=y    => {"a": ["x", "y"]}
+///
+/// Every leaf is a `Value::String`; form encoding carries no type
+/// information. Gaps left by sparse indices are filled with `null`.
+/// When the same key is seen twice the later pair wins.
+pub fn from_urlencoded(input: &str) -> Value {
+    let mut root = Value::Object(RsonMap(Map::new()));
+
+    for pair in input.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = match pair.find('=') {
+            Some(at) => (&pair[..at], &pair[at + 1..]),
+            None => (pair, ""),
+        };
+        let path = key_path(key);
+        if path[0].is_empty() {
+            continue;
+        }
+        insert(&mut root, &path, Value::String(decode(value)));
+    }
+
+    root
+}
+
+/// Flattens a `Value` into form encoding using the same bracket notation
+/// understood by `from_urlencoded`.
+///
+/// Object keys are emitted in sorted order so the output is stable.
+/// Empty arrays and objects have no form representation and are dropped;
+/// `null` becomes an empty value. Scalars at the top level have no name
+/// and produce an empty string.
+pub fn to_urlencoded(value: &Value) -> String {
+    let mut pairs = vec![];
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_sorted() {
+                flatten(&encode(key), value, &mut pairs);
+            }
+        }
+        Value::Array(array) => {
+            for (i, value) in array.iter().enumerate() {
+                flatten(&i.to_string(), value, &mut pairs);
+            }
+        }
+        _ => {}
+    }
+    pairs.join("&")
+}
+
+fn flatten(prefix: &str, value: &Value, pairs: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_sorted() {
+                flatten(&format!("{}[{}]", prefix, encode(key)), value, pairs);
+            }
+        }
+        Value::Array(array) => {
+            for (i, value) in array.iter().enumerate() {
+                flatten(&format!("{}[{}]", prefix, i), value, pairs);
+            }
+        }
+        Value::Literal(Literal::Null) => pairs.push(format!("{}=", prefix)),
+        Value::Literal(Literal::Bool(b)) => pairs.push(format!("{}={}", prefix, b)),
+        Value::Number(n) => pairs.push(format!("{}={}", prefix, n.as_str())),
+        Value::String(s) => pairs.push(format!("{}={}", prefix, encode(s))),
+    }
+}
+
+/// Splits a raw key such as `a[b][0][]` into its decoded segments
+/// `["a", "b", "0", ""]`. Brackets are recognized before percent-decoding
+/// so an encoded `%5B` stays part of a name. A key with unbalanced
+/// brackets is treated as a single plain name.
+fn key_path(key: &str) -> Vec<String> {
+    let open = match key.find('[') {
+        Some(open) if open > 0 => open,
+        _ => return vec![decode(key)],
+    };
+
+    let mut path = vec![decode(&key[..open])];
+    let mut rest = &key[open..];
+    while !rest.is_empty() {
+        if !rest.starts_with('[') {
+            return vec![decode(key)];
+        }
+        match rest.find(']') {
+            Some(close) => {
+                path.push(decode(&rest[1..close]));
+                rest = &rest[close + 1..];
+            }
+            None => return vec![decode(key)],
+        }
+    }
+    path
+}
+
+fn insert(node: &mut Value, path: &[String], value: Value) {
+    let segment = &path[0];
+    let rest = &path[1..];
+
+    // Arrays only accept append (`[]`), existing indices and indices up to
+    // the limit; anything else turns the array into an object keyed by
+    // index.
+    if let Value::Array(array) = node {
+        let fits = segment.is_empty()
+            || matches!(segment.parse::<usize>(), Ok(i) if i < array.len() || i <= ARRAY_INDEX_LIMIT);
+        if !fits {
+            let map = array
+                .drain(..)
+                .enumerate()
+                .map(|(i, v)| (i.to_string(), v))
+                .collect();
+            *node = Value::Object(RsonMap(map));
+        }
+    }
+
+    match node {
+        Value::Array(array) => {
+            let index = segment.parse::<usize>().unwrap_or(array.len());
+            while array.len() <= index {
+                array.push(Value::Literal(Literal::Null));
+            }
+            descend(&mut array[index], rest, value);
+        }
+        Value::Object(map) => {
+            let child = map
+                .0
+                .entry(segment.clone())
+                .or_insert(Value::Literal(Literal::Null));
+            descend(child, rest, value);
+        }
+        _ => unreachable!("insert is only called on containers"),
+    }
+}
+
+fn descend(child: &mut Value, rest: &[String], value: Value) {
+    if rest.is_empty() {
+        *child = value;
+        return;
+    }
+
+    // Scalars in the way of a deeper key are replaced by a container
+    // shaped after the next segment.
+    match child {
+        Value::Array(_) | Value::Object(_) => {}
+        _ if rest[0].is_empty() || rest[0].parse::<usize>().is_ok() => {
+            *child = Value::Array(vec![]);
+        }
+        _ => *child = Value::Object(RsonMap(Map::new())),
+    }
+    insert(child, rest, value);
+}
+
+/// Percent-decodes `input`, treating `+` as a space. Malformed escapes are
+/// kept verbatim and invalid UTF-8 is replaced rather than rejected.
+fn decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => out.push(b' '),
+            b'%' if i + 2 < bytes.len() => match (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                (Some(hi), Some(lo)) => {
+                    out.push(hi << 4 | lo);
+                    i += 2;
+                }
+                _ => out.push(b'%'),
+            },
+            byte => out.push(byte),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn hex(byte: u8) -> Option<u8> {
+    (byte as char).to_digit(16).map(|d| d as u8)
+}
+
+/// Percent-encodes everything except the RFC 3986 unreserved characters,
+/// writing spaces as `+`.
+fn encode(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    for byte in input.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                out.push(byte as char)
+            }
+            b' ' => out.push('+'),
+            _ => out.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    out
+}
@@ -0,0 +1,301 @@
+//! Alternative representations for field types, used with the derives'
+//! `#[rson(with = "...")]` attribute:
+//!
+//!     #[derive(Serialize, Deserialize)]
+//!     struct Config {
+//!         #[rson(with = "rson::with::duration_secs")]
+//!         timeout: Duration,
+//!     }
+//!
+//! A `with` module provides `serialize(&T, S) -> Result<S::Ok>` and
+//! `deserialize(D) -> Result<T>`; any module with those two functions
+//! can be used.
+
+use crate::deserialize::{Deserialize, Deserializer};
+use rson_core::{Error, Result};
+use rson_core::{Number, Value};
+
+/// Reads the text of a non-negative number.
+fn unsigned_number<D: Deserializer>(deserializer: D, expected: &str) -> Result<Number> {
+    match Value::deserialize(deserializer)? {
+        Value::Number(n) if !n.as_str().starts_with('-') => Ok(n),
+        Value::Number(n) => Err(Error::invalid_value(
+            &format!("number `{}`", n.as_str()),
+            expected,
+        )),
+        other => Err(Error::invalid_type(&other.unexpected(), expected)),
+    }
+}
+
+/// `Duration` as a number of seconds, with a fraction when needed: `1.5`.
+pub mod duration_secs {
+    use super::*;
+    use crate::serialize::Serializer;
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok> {
+        let text = match duration.subsec_nanos() {
+            0 => duration.as_secs().to_string(),
+            nanos => {
+                let fraction = format!("{:09}", nanos);
+                format!("{}.{}", duration.as_secs(), fraction.trim_end_matches('0'))
+            }
+        };
+        serializer.serialize_number(Number::new(text))
+    }
+
+    /// Reads whole and fractional seconds exactly; digits below a
+    /// nanosecond are dropped.
+    pub fn deserialize<D: Deserializer>(deserializer: D) -> Result<Duration> {
+        const EXPECTED: &str = "a non-negative number of seconds";
+        let n = unsigned_number(deserializer, EXPECTED)?;
+        let invalid = || Error::invalid_value(&format!("number `{}`", n.as_str()), EXPECTED);
+
+        let text = n.as_str();
+        if text.contains(['e', 'E']) {
+            let secs: f64 = text.parse().map_err(|_| invalid())?;
+            return Duration::try_from_secs_f64(secs).map_err(|_| invalid());
+        }
+        let (whole, fraction) = text.split_once('.').unwrap_or((text, ""));
+        let secs = whole.parse().map_err(|_| invalid())?;
+        let nanos = format!("{:0<9}", fraction.get(..9).unwrap_or(fraction));
+        let nanos = nanos.parse().map_err(|_| invalid())?;
+        Ok(Duration::new(secs, nanos))
+    }
+}
+
+/// `Duration` as a whole number of milliseconds. Anything below a
+/// millisecond is dropped when serializing.
+pub mod duration_millis {
+    use super::*;
+    use crate::serialize::{Serialize, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok> {
+        duration.as_millis().serialize(serializer)
+    }
+
+    pub fn deserialize<D: Deserializer>(deserializer: D) -> Result<Duration> {
+        u64::deserialize(deserializer).map(Duration::from_millis)
+    }
+}
+
+/// `SystemTime` as seconds since the Unix epoch, see `duration_secs`.
+pub mod system_time_secs {
+    use super::*;
+    use crate::serialize::Serializer;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    pub fn serialize<S: Serializer>(time: &SystemTime, serializer: S) -> Result<S::Ok> {
+        let since_epoch = time
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| Error::custom("SystemTime must be later than UNIX_EPOCH"))?;
+        super::duration_secs::serialize(&since_epoch, serializer)
+    }
+
+    pub fn deserialize<D: Deserializer>(deserializer: D) -> Result<SystemTime> {
+        let since_epoch = super::duration_secs::deserialize(deserializer)?;
+        UNIX_EPOCH
+            .checked_add(since_epoch)
+            .ok_or_else(|| Error::custom("overflow deserializing SystemTime"))
+    }
+}
+
+/// `SystemTime` as milliseconds since the Unix epoch, the form JavaScript's
+/// `Date.now()` uses.
+pub mod system_time_millis {
+    use super::*;
+    use crate::serialize::Serializer;
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    pub fn serialize<S: Serializer>(time: &SystemTime, serializer: S) -> Result<S::Ok> {
+        let since_epoch = time
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| Error::custom("SystemTime must be later than UNIX_EPOCH"))?;
+        super::duration_millis::serialize(&since_epoch, serializer)
+    }
+
+    pub fn deserialize<D: Deserializer>(deserializer: D) -> Result<SystemTime> {
+        let since_epoch = super::duration_millis::deserialize(deserializer)?;
+        UNIX_EPOCH
+            .checked_add(since_epoch)
+            .ok_or_else(|| Error::custom("overflow deserializing SystemTime"))
+    }
+}
+
+/// `bitflags` flag sets as arrays of flag names, `["READ", "WRITE"]`.
+/// Unknown names are an error when deserializing; see
+/// `flag_names_lenient` to skip them. Bits without a name can't be
+/// serialized.
+#[cfg(feature = "bitflags")]
+pub mod flag_names {
+    use super::*;
+    use crate::serialize::Serializer;
+    use bitflags::Flags;
+
+    pub fn serialize<F: Flags, S: Serializer>(flags: &F, serializer: S) -> Result<S::Ok> {
+        super::serialize_flag_names(flags, serializer)
+    }
+
+    pub fn deserialize<F: Flags, D: Deserializer>(deserializer: D) -> Result<F> {
+        super::deserialize_flag_names(deserializer, true)
+    }
+}
+
+/// Like `flag_names`, but unknown names are skipped when deserializing,
+/// so data written by a newer version with more flags still loads.
+#[cfg(feature = "bitflags")]
+pub mod flag_names_lenient {
+    use super::*;
+    use crate::serialize::Serializer;
+    use bitflags::Flags;
+
+    pub fn serialize<F: Flags, S: Serializer>(flags: &F, serializer: S) -> Result<S::Ok> {
+        super::serialize_flag_names(flags, serializer)
+    }
+
+    pub fn deserialize<F: Flags, D: Deserializer>(deserializer: D) -> Result<F> {
+        super::deserialize_flag_names(deserializer, false)
+    }
+}
+
+/// `bitflags` flag sets as arrays of flag values, `[1, 4]`. Bits without
+/// a name are written as one more element, and are an error when
+/// deserializing; see `flag_bits_lenient` to drop them.
+#[cfg(feature = "bitflags")]
+pub mod flag_bits {
+    use super::*;
+    use crate::serialize::{Serialize, Serializer};
+    use bitflags::Flags;
+    use std::fmt::Display;
+
+    pub fn serialize<F, S>(flags: &F, serializer: S) -> Result<S::Ok>
+    where
+        F: Flags,
+        F::Bits: Serialize,
+        S: Serializer,
+    {
+        super::serialize_flag_bits(flags, serializer)
+    }
+
+    pub fn deserialize<F, D>(deserializer: D) -> Result<F>
+    where
+        F: Flags,
+        F::Bits: Deserialize + Display,
+        D: Deserializer,
+    {
+        super::deserialize_flag_bits(deserializer, true)
+    }
+}
+
+/// Like `flag_bits`, but bits without a name are dropped when
+/// deserializing.
+#[cfg(feature = "bitflags")]
+pub mod flag_bits_lenient {
+    use super::*;
+    use crate::serialize::{Serialize, Serializer};
+    use bitflags::Flags;
+    use std::fmt::Display;
+
+    pub fn serialize<F, S>(flags: &F, serializer: S) -> Result<S::Ok>
+    where
+        F: Flags,
+        F::Bits: Serialize,
+        S: Serializer,
+    {
+        super::serialize_flag_bits(flags, serializer)
+    }
+
+    pub fn deserialize<F, D>(deserializer: D) -> Result<F>
+    where
+        F: Flags,
+        F::Bits: Deserialize + Display,
+        D: Deserializer,
+    {
+        super::deserialize_flag_bits(deserializer, false)
+    }
+}
+
+#[cfg(feature = "bitflags")]
+fn serialize_flag_names<F, S>(flags: &F, serializer: S) -> Result<S::Ok>
+where
+    F: bitflags::Flags,
+    S: crate::serialize::Serializer,
+{
+    use crate::serialize::SerializeSeq;
+
+    let mut iter = flags.iter_names();
+    let names: Vec<&str> = iter.by_ref().map(|(name, _)| name).collect();
+    if !iter.remaining().is_empty() {
+        return Err(Error::custom("flags contain bits without a name"));
+    }
+    let mut seq = serializer.serialize_seq(Some(names.len()))?;
+    for name in names {
+        seq.serialize_element(name)?;
+    }
+    seq.end()
+}
+
+#[cfg(feature = "bitflags")]
+fn deserialize_flag_names<F, D>(deserializer: D, strict: bool) -> Result<F>
+where
+    F: bitflags::Flags,
+    D: Deserializer,
+{
+    let mut flags = F::empty();
+    for name in Vec::<String>::deserialize(deserializer)? {
+        match F::from_name(&name) {
+            Some(flag) => flags.insert(flag),
+            None if strict => {
+                let known: Vec<&str> = F::FLAGS
+                    .iter()
+                    .map(|flag| flag.name())
+                    .filter(|name| !name.is_empty())
+                    .collect();
+                return Err(Error::unknown_variant(&name, &known));
+            }
+            None => {}
+        }
+    }
+    Ok(flags)
+}
+
+#[cfg(feature = "bitflags")]
+fn serialize_flag_bits<F, S>(flags: &F, serializer: S) -> Result<S::Ok>
+where
+    F: bitflags::Flags,
+    F::Bits: crate::serialize::Serialize,
+    S: crate::serialize::Serializer,
+{
+    use crate::serialize::SerializeSeq;
+
+    let bits: Vec<F::Bits> = flags.iter().map(|flag| flag.bits()).collect();
+    let mut seq = serializer.serialize_seq(Some(bits.len()))?;
+    for bits in &bits {
+        seq.serialize_element(bits)?;
+    }
+    seq.end()
+}
+
+#[cfg(feature = "bitflags")]
+fn deserialize_flag_bits<F, D>(deserializer: D, strict: bool) -> Result<F>
+where
+    F: bitflags::Flags,
+    F::Bits: Deserialize + std::fmt::Display,
+    D: Deserializer,
+{
+    let mut flags = F::empty();
+    for bits in Vec::<F::Bits>::deserialize(deserializer)? {
+        match F::from_bits(bits) {
+            Some(flag) => flags.insert(flag),
+            None if strict => {
+                return Err(Error::invalid_value(
+                    &format!("number `{}`", bits),
+                    "bits of known flags",
+                ))
+            }
+            None => flags.insert(F::from_bits_truncate(bits)),
+        }
+    }
+    Ok(flags)
+}
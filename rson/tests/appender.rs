@@ -0,0 +1,128 @@
+use rson::*;
+use std::fs;
+use std::path::PathBuf;
+
+fn temp_file(name: &str, contents: Option<&str>) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("rson-appender-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join(name);
+    match contents {
+        Some(contents) => fs::write(&path, contents).unwrap(),
+        None => {
+            let _ = fs::remove_file(&path);
+        }
+    }
+    path
+}
+
+fn read(path: &PathBuf) -> String {
+    fs::read_to_string(path).unwrap()
+}
+
+#[test]
+fn test_append_to_new_file() {
+    let path = temp_file("new.json", None);
+    let mut log = ArrayFileAppender::open(&path).unwrap();
+    assert_eq!(read(&path), "[\n]\n");
+
+    log.append(&rson!({"id": 1})).unwrap();
+    assert_eq!(read(&path), "[\n{\"id\":1}\n]\n");
+    log.append(&rson!({"id": 2})).unwrap();
+    log.sync().unwrap();
+
+    assert_eq!(read(&path), "[\n{\"id\":1}\n,{\"id\":2}\n]\n");
+    assert_eq!(
+        Rson::from_reader(read(&path).as_bytes()),
+        rson!([{"id": 1}, {"id": 2}])
+    );
+}
+
+#[test]
+fn test_reopen() {
+    let path = temp_file("reopen.json", None);
+    ArrayFileAppender::open(&path)
+        .unwrap()
+        .append(&rson!("a"))
+        .unwrap();
+    ArrayFileAppender::open(&path)
+        .unwrap()
+        .append(&rson!("b"))
+        .unwrap();
+
+    assert_eq!(read(&path), "[\n\"a\"\n,\"b\"\n]\n");
+}
+
+#[test]
+fn test_empty_file() {
+    let path = temp_file("empty.json", Some(""));
+    ArrayFileAppender::open(&path)
+        .unwrap()
+        .append(&rson!(1))
+        .unwrap();
+
+    assert_eq!(read(&path), "[\n1\n]\n");
+}
+
+#[test]
+fn test_foreign_arrays() {
+    let cases = [
+        ("[]", "[1\n]\n"),
+        ("[true, false]", "[true, false,1\n]\n"),
+        (
+            "[\n  true,\n  [false]\n]\n",
+            "[\n  true,\n  [false]\n,1\n]\n",
+        ),
+    ];
+    for (contents, expected) in cases {
+        let path = temp_file("foreign.json", Some(contents));
+        ArrayFileAppender::open(&path)
+            .unwrap()
+            .append(&rson!(1))
+            .unwrap();
+        assert_eq!(read(&path), expected);
+        Rson::try_from_reader(read(&path).as_bytes()).unwrap();
+    }
+}
+
+#[test]
+fn test_recover_torn_append() {
+    let complete = "[\n{\"id\":1}\n,{\"id\":2}\n]\n";
+    let cases = [
+        // Truncated, before the record was written.
+        ("[\n{\"id\":1}\n", "[\n{\"id\":1}\n]\n"),
+        // Part of the record.
+        ("[\n{\"id\":1}\n,{\"i", "[\n{\"id\":1}\n]\n"),
+        ("[\n{\"id\":1}\n,[1,2]", "[\n{\"id\":1}\n]\n"),
+        // The whole record, but not the `]`.
+        ("[\n{\"id\":1}\n,{\"id\":2}\n", complete),
+        ("[\n{\"id\":1}\n,{\"id\":2}\n]", complete),
+        // A new file.
+        ("[", "[\n]\n"),
+        ("[\n", "[\n]\n"),
+        ("[\n{\"id", "[\n]\n"),
+    ];
+    for (contents, expected) in cases {
+        let path = temp_file("torn.json", Some(contents));
+        ArrayFileAppender::open(&path).unwrap();
+        assert_eq!(read(&path), expected, "{:?}", contents);
+    }
+}
+
+#[test]
+fn test_not_an_array() {
+    let cases = [
+        "{}",
+        "{\n  \"a\": [1]\n}\n",
+        "\"text\"",
+        "[1, 2",
+        // Would be cut to a closed array, or to a broken one.
+        "[1,2]\n garbage",
+        "[1, {\n\"a\": 2\n}, 3",
+    ];
+    for contents in cases {
+        let path = temp_file("object.json", Some(contents));
+        let err = ArrayFileAppender::open(&path).err().unwrap();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(read(&path), contents);
+    }
+}
@@ -0,0 +1,60 @@
+use rson::*;
+
+#[test]
+fn test_build_nested() {
+    let value = Value::object()
+        .field("name", "x")
+        .field("tags", Value::array().push(1).push(2))
+        .field(
+            "owner",
+            Value::object().field("id", 7u64).field("admin", false),
+        )
+        .field("none", None::<i32>)
+        .build();
+
+    assert_eq!(
+        value,
+        rson!({
+            "name": "x",
+            "tags": [1, 2],
+            "owner": {"id": 7, "admin": false},
+            "none": null
+        })
+    );
+}
+
+#[test]
+fn test_dynamic_keys() {
+    let mut builder = Value::object();
+    for i in 0..3 {
+        builder = builder.field(format!("key{}", i), i * 10);
+    }
+    builder.extend(vec![("extra", "e")]);
+
+    assert_eq!(
+        builder.build(),
+        rson!({"key0": 0, "key1": 10, "key2": 20, "extra": "e"})
+    );
+}
+
+#[test]
+fn test_field_opt_and_replace() {
+    let value = Value::object()
+        .field("a", 1)
+        .field("a", 2)
+        .field_opt("b", Some("yes"))
+        .field_opt("c", None::<&str>)
+        .build();
+
+    assert_eq!(value, rson!({"a": 2, "b": "yes"}));
+}
+
+#[test]
+fn test_empty_and_extended_arrays() {
+    assert_eq!(Value::array().build(), rson!([]));
+    assert_eq!(Value::object().build(), rson!({}));
+
+    let mut array = Value::array().push("first");
+    array.extend(1..=2);
+    assert_eq!(Value::from(array), rson!(["first", 1, 2]));
+}
@@ -0,0 +1,78 @@
+use rson::*;
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Headers {
+    accept: String,
+    host: Option<String>,
+    #[rson(with = "rson::with::duration_secs")]
+    timeout: std::time::Duration,
+    retry: Vec<Retry>,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Retry {
+    after: u32,
+}
+
+const INPUT: &str = r#"{
+    "Accept": "text/html",
+    "HOST": "example.com",
+    "TimeOut": 5,
+    "retry": [{"After": 1}],
+    "X-Other": true
+}"#;
+
+fn options() -> DeserializeOptions {
+    DeserializeOptions {
+        case_insensitive_fields: true,
+        ..DeserializeOptions::default()
+    }
+}
+
+#[test]
+fn test_get_ignore_case() {
+    let value = rson!({"Content-Type": "a", "X-ID": 1, "x-id": 2, "X-Id": 3});
+
+    assert_eq!(value.get_ignore_case("content-type"), Some(&rson!("a")));
+    assert_eq!(value.get_ignore_case("x-id"), Some(&rson!(2)));
+    assert_eq!(value.get_ignore_case("X-iD"), Some(&rson!(1)));
+    assert_eq!(value.get_ignore_case("accept"), None);
+    assert_eq!(rson!([1]).get_ignore_case("0"), None);
+}
+
+#[test]
+fn test_case_insensitive_fields() {
+    let headers: Headers = options().from_str(INPUT).unwrap();
+
+    assert_eq!(
+        headers,
+        Headers {
+            accept: "text/html".to_string(),
+            host: Some("example.com".to_string()),
+            timeout: std::time::Duration::from_secs(5),
+            retry: vec![Retry { after: 1 }],
+        }
+    );
+}
+
+#[test]
+fn test_case_sensitive_by_default() {
+    let input = r#"{"After": 1}"#;
+
+    let err = from_str::<Retry>(input).unwrap_err();
+    assert_eq!(err.to_string(), "missing field `after`");
+    let err = DeserializeOptions::default()
+        .from_str::<Retry>(input)
+        .unwrap_err();
+    assert_eq!(err.to_string(), "missing field `after`");
+}
+
+#[test]
+fn test_case_insensitive_duplicates() {
+    let err = options()
+        .from_str::<Retry>(r#"{"after": 1, "AFTER": 2}"#)
+        .unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::DuplicateKey);
+    assert_eq!(err.to_string(), "duplicate field `after`");
+}
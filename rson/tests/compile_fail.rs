@@ -0,0 +1,7 @@
+/// Macro errors point at the offending tokens; the expected messages are
+/// in `tests/ui/*.stderr` (`TRYBUILD=overwrite` regenerates them).
+#[test]
+fn test_compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
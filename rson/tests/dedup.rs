@@ -0,0 +1,129 @@
+use rson::{Literal, ParseOptions, Rson, SharedValue, StringPool, Value};
+use std::sync::Arc;
+
+#[test]
+fn test_dedup_shares_identical_subtrees() {
+    let text = r#"[
+        {"id": 1, "schema": {"type": "object", "fields": ["a", "b"]}},
+        {"id": 2, "schema": {"type": "object", "fields": ["a", "b"]}},
+        {"id": 3, "schema": {"type": "object", "fields": ["a", "b"]}}
+    ]"#;
+    let value = Rson::from_reader(text.as_bytes());
+    let (shared, stats) = value.dedup();
+
+    let schemas: Vec<Arc<SharedValue>> = match &*shared {
+        SharedValue::Array(records) => records
+            .iter()
+            .map(|record| match &**record {
+                SharedValue::Object(map) => map.0["schema"].clone(),
+                _ => panic!("expected an object"),
+            })
+            .collect(),
+        _ => panic!("expected an array"),
+    };
+    assert!(Arc::ptr_eq(&schemas[0], &schemas[1]));
+    assert!(Arc::ptr_eq(&schemas[1], &schemas[2]));
+
+    // root + 3 records * (record, id, schema, type, fields, "a", "b")
+    assert_eq!(stats.nodes, 22);
+    // root, 3 records, 3 ids, one schema with its 4 children
+    assert_eq!(stats.unique_nodes, 12);
+    assert!(stats.bytes_saved() > 0);
+    assert!(stats.bytes_after < stats.bytes_before);
+}
+
+#[test]
+fn test_dedup_round_trip() {
+    let text = r#"{"a": [1, 1, "x", "x"], "b": {"c": null}, "d": {"c": null}, "e": true}"#;
+    let value = Rson::from_reader(text.as_bytes());
+    let (shared, _) = value.dedup();
+    assert_eq!(shared.to_value(), value);
+}
+
+#[test]
+fn test_dedup_shares_strings() {
+    let text = r#"[{"status": "ok"}, {"status": "ok", "code": "ok"}, {"status": "failed"}]"#;
+    let value = Rson::from_reader(text.as_bytes());
+    let mut pool = StringPool::new();
+    let (shared, _) = value.dedup_with(&mut pool);
+
+    assert_eq!(pool.len(), 2);
+    let stats = pool.stats();
+    assert_eq!((stats.lookups, stats.hits, stats.bytes_saved), (4, 2, 4));
+    assert_eq!(stats.hit_rate(), 0.5);
+    assert_eq!(shared.to_value(), value);
+}
+
+#[test]
+fn test_pool_shared_across_documents() {
+    let mut pool = StringPool::new();
+    let first = Rson::from_reader(r#"{"status": "ok"}"#.as_bytes());
+    let second = Rson::from_reader(r#"["ok"]"#.as_bytes());
+
+    let (first, _) = first.dedup_with(&mut pool);
+    let (second, _) = second.dedup_with(&mut pool);
+
+    let a = match &*first {
+        SharedValue::Object(map) => match &*map.0["status"] {
+            SharedValue::String(s) => s.clone(),
+            _ => panic!("expected a string"),
+        },
+        _ => panic!("expected an object"),
+    };
+    let b = match &*second {
+        SharedValue::Array(array) => match &*array[0] {
+            SharedValue::String(s) => s.clone(),
+            _ => panic!("expected a string"),
+        },
+        _ => panic!("expected an array"),
+    };
+    assert!(Arc::ptr_eq(&a, &b));
+    assert_eq!(pool.stats().hits, 1);
+
+    pool.clear();
+    assert!(pool.is_empty());
+    assert_eq!(pool.stats().hits, 1);
+}
+
+#[test]
+fn test_parse_pooled() {
+    let text =
+        r#"[{"status": "ok", "id": 1}, {"status": "ok", "id": 2}, {"status": "fail\u0065d"}]"#;
+    let mut pool = StringPool::new();
+    let shared =
+        Rson::try_from_reader_pooled(text.as_bytes(), ParseOptions::default(), &mut pool).unwrap();
+    assert_eq!(shared.to_value(), Rson::from_reader(text.as_bytes()));
+
+    let status = |i: usize| match &*shared {
+        SharedValue::Array(records) => match &*records[i] {
+            SharedValue::Object(map) => match &*map.0["status"] {
+                SharedValue::String(s) => s.clone(),
+                _ => panic!("expected a string"),
+            },
+            _ => panic!("expected an object"),
+        },
+        _ => panic!("expected an array"),
+    };
+    assert!(Arc::ptr_eq(&status(0), &status(1)));
+    assert_eq!(&*status(2), "failed");
+    assert_eq!(pool.len(), 2);
+    assert_eq!((pool.stats().lookups, pool.stats().hits), (3, 1));
+
+    // The pool carries over to the next document.
+    let next =
+        Rson::try_from_reader_pooled(r#""ok""#.as_bytes(), ParseOptions::default(), &mut pool)
+            .unwrap();
+    assert_eq!(*next, SharedValue::String(status(0)));
+    assert_eq!(pool.stats().hits, 2);
+
+    let err =
+        Rson::try_from_reader_pooled("[\"ok\",".as_bytes(), ParseOptions::default(), &mut pool)
+            .unwrap_err();
+    assert!(err.is_eof());
+    let options = ParseOptions {
+        empty_as_null: true,
+        ..ParseOptions::default()
+    };
+    let null = Rson::try_from_reader_pooled(" ".as_bytes(), options, &mut pool).unwrap();
+    assert_eq!(null.to_value(), Value::Literal(Literal::Null));
+}
@@ -0,0 +1,133 @@
+use rson::{from_str, from_value, Deserialize, Literal, Rson, Value};
+use std::collections::HashMap;
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct Person {
+    first_name: String,
+    last_name: String,
+    own_business: bool,
+    address: Option<String>,
+}
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct GpuDetail {
+    r#type: String,
+    serial: u64,
+}
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct Machine {
+    id: u32,
+    gpus: Vec<GpuDetail>,
+    tags: HashMap<String, i8>,
+    point: Point,
+    meters: Meters,
+}
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct Point(i32, i32);
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct Meters(f64);
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct Wrapper<T> {
+    inner: T,
+}
+
+#[test]
+fn test_derive_struct() {
+    let person: Person = from_str(
+        r#"{"first_name": "Devajit", "last_name": "Asem", "own_business": false, "unknown": [1, 2]}"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        person,
+        Person {
+            first_name: "Devajit".to_string(),
+            last_name: "Asem".to_string(),
+            own_business: false,
+            address: None,
+        }
+    );
+}
+
+#[test]
+fn test_derive_nested() {
+    let machine: Machine = from_str(
+        r#"{
+        "id": 7,
+        "gpus": [{"type": "DDR6", "serial": 12837982}],
+        "tags": {"a": 1, "b": 2},
+        "point": [3, 4],
+        "meters": 12
+    }"#,
+    )
+    .unwrap();
+
+    let mut tags = HashMap::new();
+    tags.insert("a".to_string(), 1);
+    tags.insert("b".to_string(), 2);
+    assert_eq!(
+        machine,
+        Machine {
+            id: 7,
+            gpus: vec![GpuDetail {
+                r#type: "DDR6".to_string(),
+                serial: 12837982,
+            }],
+            tags,
+            point: Point(3, 4),
+            meters: Meters(12.0),
+        }
+    );
+}
+
+#[test]
+fn test_derive_generic() {
+    let wrapper: Wrapper<Vec<bool>> = from_str(r#"{"inner": [true, false]}"#).unwrap();
+    assert_eq!(
+        wrapper,
+        Wrapper {
+            inner: vec![true, false]
+        }
+    );
+}
+
+#[test]
+fn test_derive_errors() {
+    let err = from_str::<Person>(r#"{"first_name": "Devajit"}"#).unwrap_err();
+    assert_eq!(err.to_string(), "missing field `last_name`");
+
+    let err = from_str::<GpuDetail>(r#"{"type": 1, "serial": 2}"#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid type: number `1`, expected a string"
+    );
+
+    let err = from_str::<Point>("[1, 2, 3]").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid length 3, expected tuple struct Point with 2 elements"
+    );
+
+    let err = from_str::<u8>("256").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid value: number `256`, expected a u8"
+    );
+}
+
+#[test]
+fn test_deserialize_value() {
+    let number = Value::Number(rson::Number::new("-128".to_string()));
+    assert_eq!(from_value::<i8>(number).unwrap(), -128);
+
+    let value = Rson::from_reader(r#"{"a": [1, "x", null]}"#.as_bytes());
+    assert_eq!(from_value::<Value>(value.clone()).unwrap(), value);
+    assert_eq!(
+        from_value::<Option<bool>>(Value::Literal(Literal::Null)).unwrap(),
+        None
+    );
+}
@@ -0,0 +1,190 @@
+//! Parses every document in `data/differential` with both rson and
+//! serde_json and checks they agree on whether it is valid JSON and, if
+//! so, on the value it holds. Files follow the JSONTestSuite naming:
+//! `y_` must be accepted, `n_` rejected, and `i_` is left to the
+//! implementation.
+//!
+//! Where rson differs on purpose, the file is listed in `DEVIATIONS` with
+//! the parser mode it applies to and why. A listed deviation that stops
+//! happening fails the test too, so the list stays accurate.
+
+use rson::{ParseOptions, Rson, Value};
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    /// `Rson::try_from_reader`.
+    Default,
+    /// `ParseOptions::strict()`.
+    Strict,
+}
+
+/// `(file, mode, reason)`: documents where rson intentionally gives a
+/// different answer than serde_json.
+const DEVIATIONS: &[(&str, Mode, &str)] = &[
+    (
+        "n_structure_bom.json",
+        Mode::Default,
+        "a byte order mark is skipped like whitespace unless strict",
+    ),
+    (
+        "n_structure_form_feed.json",
+        Mode::Default,
+        "any Unicode whitespace separates tokens unless strict",
+    ),
+    (
+        "n_array_trailing_comma.json",
+        Mode::Default,
+        "trailing commas are allowed unless strict",
+    ),
+    (
+        "n_object_trailing_comma.json",
+        Mode::Default,
+        "trailing commas are allowed unless strict",
+    ),
+    (
+        "n_array_missing_comma.json",
+        Mode::Default,
+        "commas are optional unless strict",
+    ),
+    (
+        "n_object_missing_comma.json",
+        Mode::Default,
+        "commas are optional unless strict",
+    ),
+    (
+        "n_string_raw_tab.json",
+        Mode::Default,
+        "control characters in strings are kept unless strict",
+    ),
+    (
+        "n_string_raw_newline.json",
+        Mode::Default,
+        "control characters in strings are kept unless strict",
+    ),
+    (
+        "y_object_duplicate_key.json",
+        Mode::Strict,
+        "strict rejects repeated keys, which RFC 8259 says should be unique",
+    ),
+    (
+        "i_number_huge_exponent.json",
+        Mode::Default,
+        "numbers keep their source text, so there is no range to overflow",
+    ),
+    (
+        "i_number_huge_exponent.json",
+        Mode::Strict,
+        "numbers keep their source text, so there is no range to overflow",
+    ),
+];
+
+fn corpus() -> Vec<(String, Vec<u8>)> {
+    let mut dir = PathBuf::from("data/differential");
+    if !dir.exists() {
+        dir = PathBuf::from("rson").join(dir);
+    }
+    let mut files: Vec<_> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| {
+            let path = entry.unwrap().path();
+            let name = path.file_name().unwrap().to_string_lossy().into_owned();
+            (name, fs::read(&path).unwrap())
+        })
+        .collect();
+    files.sort();
+    files
+}
+
+fn parse(bytes: &[u8], mode: Mode) -> Option<Value> {
+    let options = match mode {
+        Mode::Default => ParseOptions::default(),
+        Mode::Strict => ParseOptions::strict(),
+    };
+    Rson::try_from_reader_with(bytes, options).ok()
+}
+
+/// Whether the two trees hold the same JSON value. Numbers are compared
+/// by value, exactly for integers serde_json could represent.
+fn same(rson: &Value, serde: &serde_json::Value) -> bool {
+    use serde_json::Value as S;
+    match (rson, serde) {
+        (Value::Literal(l), S::Null) => *l == rson::Literal::Null,
+        (Value::Literal(l), S::Bool(b)) => *l == rson::Literal::Bool(*b),
+        (Value::String(a), S::String(b)) => a == b,
+        (Value::Number(a), S::Number(b)) => {
+            let exact = b.as_i64().map(i128::from).or(b.as_u64().map(i128::from));
+            match exact {
+                Some(b) => a.as_str().parse::<i128>().ok() == Some(b),
+                None => a.as_str().parse::<f64>().ok() == b.as_f64(),
+            }
+        }
+        (Value::Array(a), S::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same(a, b))
+        }
+        (Value::Object(a), S::Object(b)) => {
+            a.0.len() == b.len()
+                && b.iter()
+                    .all(|(k, b)| a.0.get(k).is_some_and(|a| same(a, b)))
+        }
+        _ => false,
+    }
+}
+
+/// Describes how the parsers disagree on `bytes`, if they do.
+fn divergence(name: &str, bytes: &[u8], mode: Mode) -> Option<String> {
+    let rson = parse(bytes, mode);
+    let serde = serde_json::from_slice::<serde_json::Value>(bytes);
+    match (&rson, &serde) {
+        (Some(a), Ok(b)) if !same(a, b) => Some(format!("values differ: {:?} vs {}", a, b)),
+        (Some(_), Err(e)) => Some(format!("only rson accepts it; serde_json: {}", e)),
+        (None, Ok(_)) => Some("only serde_json accepts it".to_string()),
+        _ => {
+            let expected = match &name[..2] {
+                "y_" => true,
+                "n_" => false,
+                _ => return None,
+            };
+            if rson.is_some() != expected {
+                Some(format!(
+                    "both {} it",
+                    if expected { "reject" } else { "accept" }
+                ))
+            } else {
+                None
+            }
+        }
+    }
+}
+
+#[test]
+fn test_differential() {
+    let mut report = vec![];
+    for (name, bytes) in corpus() {
+        for mode in [Mode::Default, Mode::Strict] {
+            let documented = DEVIATIONS.iter().any(|(n, m, _)| *n == name && *m == mode);
+            match (divergence(&name, &bytes, mode), documented) {
+                (Some(why), false) => report.push(format!("{} ({:?}): {}", name, mode, why)),
+                (None, true) => report.push(format!(
+                    "{} ({:?}): listed as a deviation but both parsers agree",
+                    name, mode
+                )),
+                _ => {}
+            }
+        }
+    }
+    assert!(report.is_empty(), "\n{}", report.join("\n"));
+}
+
+#[test]
+fn test_deviations_exist() {
+    let files: Vec<String> = corpus().into_iter().map(|(name, _)| name).collect();
+    for (name, _, _) in DEVIATIONS {
+        assert!(
+            files.iter().any(|f| f == name),
+            "{} is not in the corpus",
+            name
+        );
+    }
+}
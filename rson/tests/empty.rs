@@ -0,0 +1,108 @@
+use rson::{rson, ErrorKind, ParseOptions, Rson, Value};
+use std::io::{self, Read};
+
+fn empty_as_null() -> ParseOptions {
+    ParseOptions {
+        empty_as_null: true,
+        ..ParseOptions::default()
+    }
+}
+
+/// Yields nothing, after being interrupted once.
+struct Interrupted(bool);
+
+impl Read for Interrupted {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        if self.0 {
+            return Ok(0);
+        }
+        self.0 = true;
+        Err(io::ErrorKind::Interrupted.into())
+    }
+}
+
+/// Yields its bytes one at a time.
+struct Trickle<'a>(&'a [u8]);
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        match self.0.split_first() {
+            Some((&byte, rest)) if !buf.is_empty() => {
+                buf[0] = byte;
+                self.0 = rest;
+                Ok(1)
+            }
+            _ => Ok(0),
+        }
+    }
+}
+
+#[test]
+fn test_empty_input() {
+    for (text, column) in [("", 1), ("   ", 4), ("\t\r\n ", 2)] {
+        let err = Rson::try_from_reader(text.as_bytes()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::EmptyInput, "{:?}", text);
+        assert!(err.is_eof());
+        assert_eq!(err.column(), column, "{:?}", text);
+    }
+    assert_eq!(
+        Rson::try_from_reader("".as_bytes())
+            .unwrap_err()
+            .to_string(),
+        "empty input at line 1 column 1"
+    );
+    let err = Rson::try_from_reader_with(" ".as_bytes(), ParseOptions::strict()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::EmptyInput);
+}
+
+#[test]
+fn test_empty_streams() {
+    let kind = |result: rson::Result<Value>| result.unwrap_err().kind();
+    assert_eq!(
+        kind(Rson::try_from_reader(io::empty())),
+        ErrorKind::EmptyInput
+    );
+    assert_eq!(
+        kind(Rson::try_from_reader(Interrupted(false))),
+        ErrorKind::EmptyInput
+    );
+    assert_eq!(
+        kind(Rson::try_from_reader(Trickle(b"  \n  "))),
+        ErrorKind::EmptyInput
+    );
+    assert_eq!(
+        Rson::try_from_reader(Trickle(b" [1] ")).unwrap(),
+        rson!([1])
+    );
+}
+
+#[test]
+fn test_truncated_is_not_empty() {
+    for text in ["[", " {\"a\":", "\""] {
+        let err = Rson::try_from_reader(text.as_bytes()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof, "{:?}", text);
+    }
+}
+
+#[test]
+fn test_empty_as_null() {
+    for text in ["", "   ", "\n"] {
+        let value = Rson::try_from_reader_with(text.as_bytes(), empty_as_null()).unwrap();
+        assert_eq!(value, rson!(null));
+    }
+    let value = Rson::try_from_reader_with(io::empty(), empty_as_null()).unwrap();
+    assert_eq!(value, rson!(null));
+    let keyed: Value<Box<str>> =
+        Rson::try_from_reader_keyed("".as_bytes(), empty_as_null()).unwrap();
+    assert_eq!(keyed.to_string(), "null");
+
+    // Only a missing document is `null`; missing values inside one aren't.
+    for text in ["[", "{\"a\": }", " x"] {
+        assert!(Rson::try_from_reader_with(text.as_bytes(), empty_as_null()).is_err());
+    }
+
+    let mut parser = Rson::with_options(empty_as_null());
+    assert_eq!(parser.parse_str("").unwrap(), rson!(null));
+    assert_eq!(parser.parse_str(" 1 ").unwrap(), rson!(1));
+    assert_eq!(parser.parse_slice(b"").unwrap(), rson!(null));
+}
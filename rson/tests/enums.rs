@@ -0,0 +1,141 @@
+use rson::{from_value, rson, schema_for, to_value, Deserialize, RsonSchema, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum Color {
+    Red,
+    Blue,
+}
+
+#[derive(Serialize, Deserialize, RsonSchema, Debug, PartialEq)]
+#[rson(rename_all = "snake_case")]
+enum Status {
+    InProgress,
+    Done,
+    #[rson(other)]
+    Unknown,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Task {
+    color: Color,
+    status: Status,
+}
+
+#[test]
+fn test_unit_enum_round_trip() {
+    let task = Task {
+        color: Color::Red,
+        status: Status::InProgress,
+    };
+
+    let value = to_value(&task).unwrap();
+    assert_eq!(value, rson!({"color": "Red", "status": "in_progress"}));
+    assert_eq!(from_value::<Task>(value), Ok(task));
+}
+
+#[test]
+fn test_unit_enum_other() {
+    assert_eq!(from_value::<Status>(rson!("archived")), Ok(Status::Unknown));
+    assert_eq!(to_value(&Status::Unknown).unwrap(), rson!("unknown"));
+}
+
+#[test]
+fn test_unit_enum_errors() {
+    assert_eq!(
+        from_value::<Color>(rson!("red")).unwrap_err().to_string(),
+        "unknown variant `red`, expected one of `Red`, `Blue`"
+    );
+    assert_eq!(
+        from_value::<Color>(rson!(1)).unwrap_err().to_string(),
+        "invalid type: number `1`, expected enum Color"
+    );
+}
+
+macro_rules! renamed {
+    ($rule:literal, $expected:expr) => {{
+        #[derive(Serialize)]
+        #[rson(rename_all = $rule)]
+        enum Renamed {
+            HttpServer,
+        }
+        assert_eq!(to_value(&Renamed::HttpServer).unwrap(), rson!($expected));
+    }};
+}
+
+#[test]
+fn test_rename_all_rules() {
+    renamed!("lowercase", "httpserver");
+    renamed!("UPPERCASE", "HTTPSERVER");
+    renamed!("PascalCase", "HttpServer");
+    renamed!("camelCase", "httpServer");
+    renamed!("snake_case", "http_server");
+    renamed!("SCREAMING_SNAKE_CASE", "HTTP_SERVER");
+    renamed!("kebab-case", "http-server");
+    renamed!("SCREAMING-KEBAB-CASE", "HTTP-SERVER");
+}
+
+#[test]
+fn test_enum_schema() {
+    assert_eq!(
+        schema_for::<Status>(),
+        rson!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": "Status",
+            "type": "string",
+            "examples": ["in_progress", "done"],
+        })
+    );
+}
+
+#[derive(Serialize, Deserialize, RsonSchema, Debug, PartialEq)]
+#[rson(repr_int)]
+#[repr(u8)]
+enum Opcode {
+    Nop,
+    Load = 4,
+    Store,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[rson(repr_int)]
+enum Level {
+    Low = -1,
+    High = 1,
+    #[rson(other)]
+    Unknown = 0,
+}
+
+#[test]
+fn test_repr_int_round_trip() {
+    let ops = vec![Opcode::Nop, Opcode::Load, Opcode::Store];
+    let value = to_value(&ops).unwrap();
+    assert_eq!(value, rson!([0, 4, 5]));
+    assert_eq!(from_value::<Vec<Opcode>>(value), Ok(ops));
+
+    assert_eq!(to_value(&Level::Low).unwrap(), rson!(-1));
+    assert_eq!(from_value::<Level>(rson!(-1)), Ok(Level::Low));
+    assert_eq!(from_value::<Level>(rson!(7)), Ok(Level::Unknown));
+}
+
+#[test]
+fn test_repr_int_errors() {
+    assert_eq!(
+        from_value::<Opcode>(rson!(3)).unwrap_err().to_string(),
+        "invalid value: number `3`, expected a discriminant of enum Opcode"
+    );
+    assert!(from_value::<Opcode>(rson!(256)).is_err());
+    assert!(from_value::<Opcode>(rson!("Nop")).is_err());
+}
+
+#[test]
+fn test_repr_int_schema() {
+    assert_eq!(
+        schema_for::<Opcode>(),
+        rson!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": "Opcode",
+            "type": "integer",
+            "enum": [0, 4, 5],
+        })
+    );
+}
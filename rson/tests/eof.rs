@@ -0,0 +1,53 @@
+use rson::{ErrorKind, Rson, Value};
+
+const DOCUMENT: &str = r#"{"a": [1, true, "xé é"], "b": {"c": null}, "d": false}"#;
+
+#[test]
+fn test_every_prefix_is_eof() {
+    let bytes = DOCUMENT.as_bytes();
+    for len in 0..bytes.len() {
+        let err = Rson::try_from_reader(&bytes[..len]).unwrap_err();
+        assert!(
+            err.is_eof(),
+            "{:?} gave {:?}",
+            String::from_utf8_lossy(&bytes[..len]),
+            err
+        );
+    }
+    assert!(Rson::try_from_reader(bytes).is_ok());
+}
+
+#[test]
+fn test_invalid_is_not_eof() {
+    for text in [r#"{"a" 1}"#, "[tx]", "[nul]", "[nul ", r#""\q"#] {
+        let err = Rson::try_from_reader(text.as_bytes()).unwrap_err();
+        assert!(!err.is_eof(), "{} gave {:?}", text, err);
+    }
+}
+
+#[test]
+fn test_eof_position() {
+    let err = Rson::try_from_reader(r#"{"a":"#.as_bytes()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    assert_eq!((err.line(), err.column()), (1, 6));
+    assert_eq!(
+        err.to_string(),
+        "EOF while parsing a value at line 1 column 6"
+    );
+}
+
+/// A caller buffering a socket reads until the document is complete.
+#[test]
+fn test_retry_on_eof() {
+    let mut chunks = DOCUMENT.as_bytes().chunks(7);
+    let mut buffer = vec![];
+    let value: Value = loop {
+        buffer.extend_from_slice(chunks.next().expect("document ended early"));
+        match Rson::try_from_reader(&buffer[..]) {
+            Ok(value) => break value,
+            Err(err) if err.is_eof() => continue,
+            Err(err) => panic!("{}", err),
+        }
+    };
+    assert_eq!(value, Rson::from_reader(DOCUMENT.as_bytes()));
+}
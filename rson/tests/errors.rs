@@ -0,0 +1,170 @@
+use rson::{
+    from_str, from_value, rson, to_string, Error, ErrorKind, ParseOptions, Rson, Suggestion, Value,
+};
+
+fn parse(text: &str) -> Result<Value, Error> {
+    Rson::try_from_reader(text.as_bytes())
+}
+
+fn parse_err(text: &str) -> (ErrorKind, usize, usize) {
+    let err = parse(text).unwrap_err();
+    (err.kind(), err.line(), err.column())
+}
+
+#[test]
+fn test_error_positions() {
+    assert_eq!(parse_err("{true}"), (ErrorKind::UnexpectedToken, 1, 2));
+    assert_eq!(
+        parse_err("[\n  1,\n  tru\n]"),
+        (ErrorKind::UnexpectedToken, 3, 3)
+    );
+    assert_eq!(
+        parse("[\n  1,\n  tru\n]").unwrap_err().to_string(),
+        "Expected a literal. Found: `tru` at line 3 column 3"
+    );
+    assert_eq!(
+        parse_err("true false"),
+        (ErrorKind::TrailingCharacters, 1, 6)
+    );
+    assert_eq!(parse_err(&"[".repeat(200)), (ErrorKind::DepthLimit, 1, 129));
+}
+
+#[test]
+fn test_escapes() {
+    let value = parse(r#"["a\"b\\c\/d", "\b\f\n\r\t", "café 😀", " padded "]"#).unwrap();
+    assert_eq!(
+        value,
+        rson!(["a\"b\\c/d", "\u{8}\u{c}\n\r\t", "café 😀", " padded "])
+    );
+    assert_eq!(parse(&to_string(&value)).unwrap(), value);
+
+    assert_eq!(parse_err(r#""a\qb""#), (ErrorKind::InvalidEscape, 1, 4));
+    assert_eq!(parse_err(r#""\u12x4""#), (ErrorKind::InvalidEscape, 1, 6));
+    assert_eq!(parse_err(r#""\ud83d""#).0, ErrorKind::InvalidEscape);
+    assert_eq!(parse_err(r#""\ude00""#).0, ErrorKind::InvalidEscape);
+}
+
+#[test]
+fn test_utf8() {
+    assert_eq!(parse("\"héllo wörld\"").unwrap(), rson!("héllo wörld"));
+    assert_eq!(
+        parse_err("\"\u{e9}\"\n\"x"),
+        (ErrorKind::TrailingCharacters, 2, 1)
+    );
+
+    let err = Rson::try_from_reader(&b"\"a\xff\""[..]).unwrap_err();
+    assert_eq!((err.kind(), err.column()), (ErrorKind::InvalidUtf8, 3));
+
+    let err = Rson::try_from_reader(&b"\"\xc3"[..]).unwrap_err();
+    assert!(err.is_eof());
+}
+
+#[test]
+fn test_is_eof() {
+    assert!(parse(r#""ab\"#).unwrap_err().is_eof());
+    assert!(!parse("{true}").unwrap_err().is_eof());
+}
+
+#[test]
+fn test_conversion_errors() {
+    let err = from_value::<u8>(rson!("x")).unwrap_err();
+    assert_eq!(
+        (err.kind(), err.line(), err.column()),
+        (ErrorKind::Message, 0, 0)
+    );
+
+    let err = from_str::<Vec<u8>>("[1, nope]").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedToken);
+    assert_eq!(err.column(), 5);
+
+    let err = Error::duplicate_field("id").at("/user");
+    assert_eq!(err.kind(), ErrorKind::DuplicateKey);
+    assert_eq!(err.to_string(), "at `/user`: duplicate field `id`");
+}
+
+#[test]
+fn test_strict_errors() {
+    let strict = |text: &str| {
+        Rson::try_from_reader_with(text.as_bytes(), ParseOptions::strict())
+            .unwrap_err()
+            .to_string()
+    };
+
+    assert_eq!(strict("[1, 2,]"), "trailing comma at line 1 column 7");
+    assert_eq!(strict("{\"a\": 1,\n}"), "trailing comma at line 2 column 1");
+    assert_eq!(strict("[1 2]"), "expected `,` or `]` at line 1 column 4");
+    assert_eq!(
+        strict("{\"a\": 1 \"b\": 2}"),
+        "expected `,` or `}` at line 1 column 9"
+    );
+    assert_eq!(
+        strict("[\"a\tb\"]"),
+        "control character '\\t' in a string at line 1 column 4"
+    );
+    assert!(parse("[1 2,]").is_ok());
+}
+
+#[test]
+fn test_suggestions() {
+    let suggestion = |text: &str, options: ParseOptions| {
+        Rson::try_from_reader_with(text.as_bytes(), options)
+            .unwrap_err()
+            .suggestion()
+    };
+    let lenient = ParseOptions::default;
+
+    assert_eq!(
+        suggestion("{'a': 1}", lenient()),
+        Some(Suggestion::UseDoubleQuotes)
+    );
+    assert_eq!(
+        suggestion(r#"{"a": 'b'}"#, lenient()),
+        Some(Suggestion::UseDoubleQuotes)
+    );
+    assert_eq!(suggestion("{a: 1}", lenient()), Some(Suggestion::QuoteKey));
+    assert_eq!(
+        suggestion("[1, True]", lenient()),
+        Some(Suggestion::UseLiteral("true"))
+    );
+    assert_eq!(
+        suggestion(r#"{"a": None}"#, lenient()),
+        Some(Suggestion::UseLiteral("null"))
+    );
+    assert_eq!(
+        suggestion("[1, 2,]", ParseOptions::strict()),
+        Some(Suggestion::RemoveTrailingComma)
+    );
+    assert_eq!(
+        suggestion(r#"{"a": 1 "b": 2}"#, ParseOptions::strict()),
+        Some(Suggestion::InsertComma)
+    );
+    assert_eq!(suggestion("[nul]", lenient()), None);
+    assert_eq!(suggestion("[1, 2", lenient()), None);
+}
+
+#[test]
+fn test_suggestion_hints() {
+    let err = parse("[False]").unwrap_err();
+    assert_eq!((err.line(), err.column()), (1, 2));
+    assert_eq!(
+        err.suggestion().unwrap().to_string(),
+        "did you mean `false`?"
+    );
+    assert_eq!(
+        Suggestion::RemoveTrailingComma.to_string(),
+        "remove the comma before the closing bracket"
+    );
+}
+
+#[test]
+fn test_duplicate_keys() {
+    let text = r#"{"a": 1, "b": {"c": 2, "c": 3}}"#;
+
+    let lenient = parse(text).unwrap();
+    assert_eq!(lenient, rson!({"a": 1, "b": {"c": 3}}));
+
+    let err = Rson::try_from_reader_with(text.as_bytes(), ParseOptions::strict()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::DuplicateKey);
+    assert_eq!((err.line(), err.column()), (1, 24));
+    assert_eq!(err.to_string(), "duplicate key `c` at line 1 column 24");
+}
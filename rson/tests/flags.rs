@@ -0,0 +1,91 @@
+#![cfg(feature = "bitflags")]
+
+use bitflags::bitflags;
+use rson::{from_value, rson, to_value, Deserialize, Serialize};
+
+bitflags! {
+    #[derive(Debug, PartialEq, Clone, Copy)]
+    struct Permissions: u8 {
+        const READ = 1;
+        const WRITE = 2;
+        const EXECUTE = 4;
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Strict {
+    #[rson(with = "rson::with::flag_names")]
+    names: Permissions,
+    #[rson(with = "rson::with::flag_bits")]
+    bits: Permissions,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Lenient {
+    #[rson(with = "rson::with::flag_names_lenient")]
+    names: Permissions,
+    #[rson(with = "rson::with::flag_bits_lenient")]
+    bits: Permissions,
+}
+
+#[test]
+fn test_flags_round_trip() {
+    let strict = Strict {
+        names: Permissions::READ | Permissions::EXECUTE,
+        bits: Permissions::WRITE | Permissions::EXECUTE,
+    };
+
+    let value = to_value(&strict).unwrap();
+    assert_eq!(value, rson!({"names": ["READ", "EXECUTE"], "bits": [2, 4]}));
+    assert_eq!(from_value::<Strict>(value), Ok(strict));
+
+    let empty = Strict {
+        names: Permissions::empty(),
+        bits: Permissions::empty(),
+    };
+    assert_eq!(to_value(&empty).unwrap(), rson!({"names": [], "bits": []}));
+}
+
+#[test]
+fn test_flags_unknown() {
+    let value = rson!({"names": ["READ", "DELETE"], "bits": [1, 8]});
+
+    assert_eq!(
+        from_value::<Strict>(value.clone()).unwrap_err().to_string(),
+        "unknown variant `DELETE`, expected one of `READ`, `WRITE`, `EXECUTE`"
+    );
+    assert_eq!(
+        from_value::<Strict>(rson!({"names": [], "bits": [1, 8]}))
+            .unwrap_err()
+            .to_string(),
+        "invalid value: number `8`, expected bits of known flags"
+    );
+    assert_eq!(
+        from_value::<Lenient>(value),
+        Ok(Lenient {
+            names: Permissions::READ,
+            bits: Permissions::READ,
+        })
+    );
+}
+
+#[test]
+fn test_flags_unnamed_bits() {
+    let strict = Strict {
+        names: Permissions::from_bits_retain(1 | 16),
+        bits: Permissions::from_bits_retain(1 | 16),
+    };
+    assert_eq!(
+        to_value(&strict).unwrap_err().to_string(),
+        "flags contain bits without a name"
+    );
+
+    let bits = Strict {
+        names: Permissions::READ,
+        bits: Permissions::from_bits_retain(1 | 16),
+    };
+    assert_eq!(
+        to_value(&bits).unwrap(),
+        rson!({"names": ["READ"], "bits": [1, 16]})
+    );
+}
@@ -0,0 +1,110 @@
+use rson::*;
+
+fn format(format: FloatFormat, v: f64) -> String {
+    let options = SerializeOptions {
+        float_format: format,
+    };
+    options.to_string(&v).unwrap()
+}
+
+fn digits(n: usize) -> FloatFormat {
+    FloatFormat {
+        max_significant_digits: Some(n),
+        ..FloatFormat::default()
+    }
+}
+
+#[test]
+fn test_default_is_shortest() {
+    for v in [0.1, 1.0, -2.5, 1e21, 1.5e-7, 123456.789] {
+        assert_eq!(format(FloatFormat::default(), v), format!("{:?}", v));
+        assert_eq!(
+            format(FloatFormat::default(), v),
+            to_string(&to_value(&v).unwrap())
+        );
+    }
+    assert_eq!(format(FloatFormat::default(), f64::NAN), "null");
+}
+
+#[test]
+fn test_max_significant_digits() {
+    assert_eq!(format(digits(3), 2.0 / 3.0), "0.667");
+    assert_eq!(format(digits(3), 1.5), "1.50");
+    assert_eq!(format(digits(2), 9.99), "10.0");
+    assert_eq!(format(digits(3), 123456.0), "123000.0");
+    assert_eq!(format(digits(1), -0.046), "-0.05");
+    assert_eq!(format(digits(0), 7.7), "8.0");
+    assert_eq!(format(digits(3), 1.0e20), "1.00e20");
+}
+
+#[test]
+fn test_trim_trailing_zeros() {
+    let trim = |n| FloatFormat {
+        trim_trailing_zeros: true,
+        ..digits(n)
+    };
+    assert_eq!(format(trim(3), 1.5), "1.5");
+    assert_eq!(format(trim(3), 2.0), "2");
+    assert_eq!(format(trim(17), 0.1), "0.10000000000000001");
+    assert_eq!(
+        format(
+            FloatFormat {
+                trim_trailing_zeros: true,
+                ..FloatFormat::default()
+            },
+            2.0
+        ),
+        "2"
+    );
+}
+
+#[test]
+fn test_notation() {
+    let fixed = FloatFormat {
+        notation: Notation::Fixed,
+        ..FloatFormat::default()
+    };
+    assert_eq!(format(fixed, 1e21), "1000000000000000000000.0");
+    assert_eq!(format(fixed, 1.5e-7), "0.00000015");
+    assert_eq!(format(fixed, -0.0), "-0.0");
+
+    let scientific = FloatFormat {
+        notation: Notation::Scientific,
+        ..FloatFormat::default()
+    };
+    assert_eq!(format(scientific, 1234.5), "1.2345e3");
+    assert_eq!(format(scientific, 0.001), "1e-3");
+    assert_eq!(format(scientific, 0.0), "0e0");
+    assert_eq!(
+        format(
+            FloatFormat {
+                notation: Notation::Scientific,
+                ..digits(3)
+            },
+            1234.5
+        ),
+        "1.23e3"
+    );
+}
+
+#[test]
+fn test_f32_keeps_its_digits() {
+    let options = SerializeOptions {
+        float_format: FloatFormat {
+            notation: Notation::Fixed,
+            ..FloatFormat::default()
+        },
+    };
+    assert_eq!(options.to_string(&0.1f32).unwrap(), "0.1");
+}
+
+#[test]
+fn test_options_reach_nested_values() {
+    let options = SerializeOptions {
+        float_format: digits(2),
+    };
+    let mut map = std::collections::BTreeMap::new();
+    map.insert("ratio", vec![Some(1.23456), None]);
+
+    assert_eq!(options.to_string(&map).unwrap(), r#"{"ratio":[1.2,null]}"#);
+}
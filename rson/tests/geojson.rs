@@ -0,0 +1,189 @@
+#![cfg(feature = "geojson")]
+
+use rson::geojson::{Feature, FeatureCollection, FeatureId, GeoJson, Geometry};
+use rson::*;
+
+const COLLECTION: &str = r#"{
+    "type": "FeatureCollection",
+    "features": [
+        {
+            "type": "Feature",
+            "id": "p1",
+            "geometry": {"type": "Point", "coordinates": [102, 0.5]},
+            "properties": {"name": "first"}
+        },
+        {
+            "type": "Feature",
+            "id": 7,
+            "geometry": {
+                "type": "Polygon",
+                "coordinates": [[[100, 0], [101, 0], [101, 1], [100, 1], [100, 0]]]
+            },
+            "properties": null,
+            "source": "survey"
+        },
+        {
+            "type": "Feature",
+            "geometry": null,
+            "properties": {}
+        }
+    ],
+    "bbox": [100, 0, 102, 1]
+}"#;
+
+#[test]
+fn test_read_collection() {
+    let collection: FeatureCollection = from_str(COLLECTION).unwrap();
+
+    assert_eq!(collection.features.len(), 3);
+    assert_eq!(collection.bbox, Some(vec![100.0, 0.0, 102.0, 1.0]));
+
+    let first = &collection.features[0];
+    assert_eq!(first.id, Some(FeatureId::String("p1".to_string())));
+    assert_eq!(first.geometry, Some(Geometry::Point(vec![102.0, 0.5])));
+    assert_eq!(first.property("name"), Some(&Value::from("first")));
+
+    let second = &collection.features[1];
+    assert_eq!(
+        second.id,
+        Some(FeatureId::Number(Number::new("7".to_string())))
+    );
+    assert!(matches!(&second.geometry, Some(Geometry::Polygon(rings)) if rings[0].len() == 5));
+    assert_eq!(second.properties, None);
+    assert_eq!(second.foreign_members["source"], Value::from("survey"));
+
+    assert_eq!(collection.features[2].geometry, None);
+}
+
+#[test]
+fn test_round_trip() {
+    let collection: FeatureCollection = from_str(COLLECTION).unwrap();
+    let value = to_value(&collection).unwrap();
+
+    assert_eq!(
+        from_value::<FeatureCollection>(value.clone()).unwrap(),
+        collection
+    );
+    assert_eq!(
+        value.pointer("/features/0/geometry/coordinates"),
+        Some(&rson!([102.0, 0.5]))
+    );
+    assert_eq!(value.pointer("/features/1/properties"), Some(&rson!(null)));
+}
+
+#[test]
+fn test_geojson_any() {
+    let point: GeoJson = from_str(r#"{"type": "Point", "coordinates": [1, 2, 3]}"#).unwrap();
+    assert_eq!(
+        point,
+        GeoJson::Geometry(Geometry::Point(vec![1.0, 2.0, 3.0]))
+    );
+
+    let collection: GeoJson = from_str(COLLECTION).unwrap();
+    assert!(matches!(collection, GeoJson::FeatureCollection(_)));
+
+    let nested: GeoJson = from_str(
+        r#"{"type": "GeometryCollection", "geometries": [
+            {"type": "LineString", "coordinates": [[0, 0], [1, 1]]},
+            {"type": "MultiPoint", "coordinates": []}
+        ]}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        nested,
+        GeoJson::Geometry(Geometry::GeometryCollection(vec![
+            Geometry::LineString(vec![vec![0.0, 0.0], vec![1.0, 1.0]]),
+            Geometry::MultiPoint(vec![]),
+        ]))
+    );
+}
+
+#[test]
+fn test_invalid_geometries() {
+    for (text, message) in [
+        (
+            r#"{"type": "Point", "coordinates": [1]}"#,
+            "at `/coordinates`: invalid length 1, expected a position of at least 2 numbers",
+        ),
+        (
+            r#"{"type": "Point", "coordinates": [1, "2"]}"#,
+            "at `/coordinates/1`: invalid type: string \"2\", expected a number",
+        ),
+        (
+            r#"{"type": "Point", "coordinates": [1, 1e999]}"#,
+            "at `/coordinates/1`: invalid value: number `1e999`, expected a finite number",
+        ),
+        (
+            r#"{"type": "LineString", "coordinates": [[0, 0]]}"#,
+            "at `/coordinates`: invalid length 1, expected a line string of at least 2 positions",
+        ),
+        (
+            r#"{"type": "Polygon", "coordinates": [[[0, 0], [1, 0], [1, 1], [0, 1]]]}"#,
+            "at `/coordinates/0`: a linear ring must end where it starts",
+        ),
+        (
+            r#"{"type": "Polygon", "coordinates": [[[0, 0], [1, 0], [0, 0]]]}"#,
+            "at `/coordinates/0`: a linear ring needs at least 4 positions",
+        ),
+        (
+            r#"{"type": "Circle", "coordinates": [0, 0]}"#,
+            "at `/type`: unknown variant `Circle`, expected one of `Point`, `MultiPoint`, `LineString`, `MultiLineString`, `Polygon`, `MultiPolygon`, `GeometryCollection`",
+        ),
+        (
+            r#"{"coordinates": [0, 0]}"#,
+            "at the root: missing field `type`",
+        ),
+    ] {
+        let err = from_str::<Geometry>(text).unwrap_err();
+        assert_eq!(err.to_string(), message, "{}", text);
+    }
+}
+
+#[test]
+fn test_invalid_features() {
+    let err = from_str::<FeatureCollection>(
+        r#"{"type": "FeatureCollection", "features": [{"type": "Feature", "geometry": null}]}"#,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "at `/features/0`: missing field `properties`"
+    );
+
+    let err = from_str::<Feature>(r#"{"type": "Point", "coordinates": [0, 0]}"#).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "at `/type`: invalid value: string \"Point\", expected \"Feature\""
+    );
+
+    let err = from_str::<FeatureCollection>(
+        r#"{"type": "FeatureCollection", "features": [], "bbox": [0, 0, 1]}"#,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "at `/bbox`: invalid length 3, expected a bbox of 4 or 6 numbers"
+    );
+}
+
+#[test]
+fn test_write_feature() {
+    let mut feature = Feature::new(Geometry::Point(vec![1.5, -2.0]));
+    feature.set_property("name", "here");
+
+    assert_eq!(
+        to_value(&feature).unwrap(),
+        rson!({
+            "type": "Feature",
+            "geometry": {"type": "Point", "coordinates": [1.5, -2.0]},
+            "properties": {"name": "here"}
+        })
+    );
+
+    let feature = Feature::new(Geometry::Point(vec![f64::NAN, 0.0]));
+    let err = to_value(&feature).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid value: `NaN`, expected a finite coordinate"
+    );
+}
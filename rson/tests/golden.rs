@@ -0,0 +1,59 @@
+use rson::rson;
+use rson::testing::golden::{assert_golden, Golden};
+use std::fs;
+
+#[test]
+fn test_golden_matches() {
+    let actual = rson!({"team": {"lead": true}, "scores": [3, 10], "name": "asem", "id": 7});
+    assert_golden("data/golden/user.json", &actual);
+}
+
+#[test]
+fn test_golden_float_tolerance() {
+    let actual =
+        rson!({"id": 7.0000001, "name": "asem", "scores": [3, 10], "team": {"lead": true}});
+    Golden::new("data/golden/user.json")
+        .float_tolerance(1e-6)
+        .assert_matches(&actual);
+}
+
+#[test]
+fn test_golden_include() {
+    Golden::new("data/golden/user.json")
+        .include()
+        .assert_matches(&rson!({
+            "id": 7,
+            "name": "asem",
+            "scores": [3, 10, 12],
+            "team": {"lead": true, "size": 4},
+            "created_at": "2021-03-01",
+        }));
+}
+
+#[test]
+#[should_panic(expected = "value does not match fixture data/golden/user.json")]
+fn test_golden_mismatch() {
+    assert_golden("data/golden/user.json", &rson!({"id": 8}));
+}
+
+#[test]
+#[should_panic(expected = "run with RSON_UPDATE_GOLDEN=1 to create it")]
+fn test_golden_missing() {
+    assert_golden("data/golden/missing.json", &rson!(null));
+}
+
+#[test]
+fn test_golden_save() {
+    let dir = std::env::temp_dir().join(format!("rson-golden-{}", std::process::id()));
+    let golden = Golden::new(dir.join("nested/saved.json"));
+    let value = rson!({"b": [1, 2], "a": "x"});
+
+    golden.save(&value).unwrap();
+    assert_eq!(
+        fs::read_to_string(golden.path()).unwrap(),
+        "{\"a\":\"x\",\"b\":[1,2]}\n"
+    );
+    golden.assert_matches(&value);
+
+    fs::remove_dir_all(dir).unwrap();
+}
@@ -0,0 +1,55 @@
+use rson::{include_rson, Literal, Map, Number, Rson, RsonMap, Value};
+
+#[test]
+fn test_include_rson() {
+    let defaults = include_rson!("data/defaults.json");
+    let hosts = include_rson!("data/defaults.json")["hosts"].clone();
+    assert!(hosts.to_string().starts_with("[\"a.example\""));
+
+    let mut nested = Map::new();
+    nested.insert("ok".to_string(), Value::Literal(Literal::Bool(true)));
+    let mut limits = Map::new();
+    limits.insert(
+        "depth".to_string(),
+        Value::Number(Number::new("128".to_string())),
+    );
+    limits.insert("nested".to_string(), Value::Object(RsonMap(nested)));
+
+    let mut map = Map::new();
+    map.insert(
+        "name".to_string(),
+        Value::String("rson \"defaults\"".to_string()),
+    );
+    map.insert(
+        "retries".to_string(),
+        Value::Number(Number::new("3".to_string())),
+    );
+    map.insert(
+        "ratio".to_string(),
+        Value::Number(Number::new("-0.5e-3".to_string())),
+    );
+    map.insert("verbose".to_string(), Value::Literal(Literal::Bool(false)));
+    map.insert("proxy".to_string(), Value::Literal(Literal::Null));
+    map.insert(
+        "hosts".to_string(),
+        Value::Array(vec![
+            Value::String("a.example".to_string()),
+            Value::String("b.example\u{e9}".to_string()),
+            Value::String("\u{1f600}".to_string()),
+        ]),
+    );
+    map.insert("limits".to_string(), Value::Object(RsonMap(limits)));
+
+    assert_eq!(defaults, Value::Object(RsonMap(map)));
+}
+
+/// The macro parses with the runtime parser, so it takes the same lenient
+/// input: here a missing and two trailing commas.
+#[test]
+fn test_include_rson_matches_runtime_parser() {
+    let text = include_str!("../data/lenient.json");
+    assert_eq!(
+        include_rson!("data/lenient.json"),
+        Rson::from_reader(text.as_bytes())
+    );
+}
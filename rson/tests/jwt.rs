@@ -0,0 +1,144 @@
+use rson::jwt::{self, Claims, Token};
+use rson::*;
+use std::collections::BTreeMap;
+use std::time::{Duration, UNIX_EPOCH};
+
+// The example JWS of RFC 7519, section 3.1.
+const RFC_TOKEN: &str = "eyJ0eXAiOiJKV1QiLA0KICJhbGciOiJIUzI1NiJ9\
+    .eyJpc3MiOiJqb2UiLA0KICJleHAiOjEzMDA4MTkzODAsDQogImh0dHA6Ly9leGFtcGxlLmNvbS9pc19yb290Ijp0cnVlfQ\
+    .dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+
+#[test]
+fn test_base64url() {
+    for (bytes, text) in [
+        (&b""[..], ""),
+        (b"f", "Zg"),
+        (b"fo", "Zm8"),
+        (b"foo", "Zm9v"),
+        (b"foob", "Zm9vYg"),
+        (b"fooba", "Zm9vYmE"),
+        (b"foobar", "Zm9vYmFy"),
+        (&[0xfb, 0xff], "-_8"),
+    ] {
+        assert_eq!(jwt::base64url_encode(bytes), text);
+        assert_eq!(jwt::base64url_decode(text).unwrap(), bytes);
+    }
+}
+
+#[test]
+fn test_base64url_errors() {
+    for (text, message) in [
+        ("Zm9v=", "invalid base64url length 5"),
+        ("Zm9v+w", "invalid base64url character '+' at 4"),
+        ("Zh", "invalid base64url: trailing bits are not zero"),
+        ("Zé", "invalid base64url character 'é' at 1"),
+    ] {
+        assert_eq!(
+            jwt::base64url_decode(text).unwrap_err().to_string(),
+            message
+        );
+    }
+}
+
+#[test]
+fn test_decode_rfc_token() {
+    let token = Token::decode_unverified(RFC_TOKEN).unwrap();
+
+    assert_eq!(token.algorithm(), Some("HS256"));
+    assert_eq!(token.header, rson!({"typ": "JWT", "alg": "HS256"}));
+    assert_eq!(token.signature.len(), 32);
+
+    let claims: Claims = token.claims_as().unwrap();
+    assert_eq!(claims.iss.as_deref(), Some("joe"));
+    assert_eq!(
+        claims.exp,
+        Some(UNIX_EPOCH + Duration::from_secs(1300819380))
+    );
+    assert_eq!(
+        claims.extra["http://example.com/is_root"],
+        Value::from(true)
+    );
+}
+
+#[test]
+fn test_token_errors() {
+    let err = Token::decode_unverified("a.b").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "a JWS has 3 parts separated by `.`, found 2"
+    );
+
+    let not_json = jwt::base64url_encode(b"{nope");
+    assert!(Token::decode_unverified(&format!("{0}.{0}.", not_json)).is_err());
+
+    let err =
+        jwt::decode_segment_as::<Claims>(&jwt::encode_segment(&rson!({"exp": "soon"})).unwrap())
+            .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "at `/exp`: invalid type: string \"soon\", expected a non-negative number of seconds"
+    );
+}
+
+#[test]
+fn test_claims_round_trip() {
+    let claims = Claims {
+        iss: Some("issuer".to_string()),
+        aud: vec!["api".to_string()],
+        iat: Some(UNIX_EPOCH + Duration::from_millis(1_700_000_000_500)),
+        exp: Some(UNIX_EPOCH + Duration::from_secs(1_700_003_600)),
+        extra: BTreeMap::from([("scope".to_string(), Value::from("read"))]),
+        ..Claims::default()
+    };
+
+    let segment = jwt::encode_segment(&claims).unwrap();
+    let value = jwt::decode_segment(&segment).unwrap();
+    assert_eq!(
+        value,
+        rson!({
+            "iss": "issuer",
+            "aud": "api",
+            "iat": 1700000000.5,
+            "exp": 1700003600,
+            "scope": "read"
+        })
+    );
+    assert_eq!(jwt::decode_segment_as::<Claims>(&segment).unwrap(), claims);
+
+    let many: Claims = from_value(rson!({"aud": ["a", "b"]})).unwrap();
+    assert_eq!(many.aud, ["a", "b"]);
+    assert_eq!(to_value(&many).unwrap(), rson!({"aud": ["a", "b"]}));
+}
+
+#[test]
+fn test_validity() {
+    let at = |secs| UNIX_EPOCH + Duration::from_secs(secs);
+    let claims = Claims {
+        nbf: Some(at(100)),
+        exp: Some(at(200)),
+        ..Claims::default()
+    };
+    let leeway = Duration::from_secs(10);
+
+    assert!(!claims.is_valid_at(at(80), leeway));
+    assert!(claims.is_valid_at(at(95), leeway));
+    assert!(claims.is_valid_at(at(205), leeway));
+    assert!(claims.is_expired_at(at(210), leeway));
+    assert!(!claims.is_valid_at(at(210), leeway));
+    assert!(Claims::default().is_valid_at(at(0), Duration::ZERO));
+}
+
+#[test]
+fn test_validity_far_future() {
+    let claims: Claims = from_value(rson!({"exp": 9223372036854775800})).unwrap();
+    let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+    assert!(!claims.is_expired_at(now, Duration::from_secs(30)));
+    assert!(claims.is_valid_at(now, Duration::from_secs(30)));
+
+    let claims = Claims {
+        nbf: claims.exp,
+        ..Claims::default()
+    };
+    assert!(!claims.is_valid_at(now, Duration::from_secs(30)));
+    assert!(claims.is_valid_at(claims.nbf.unwrap(), Duration::MAX));
+}
@@ -0,0 +1,42 @@
+use rson::{rson, to_string, ParseOptions, Rson, StringPool, Value};
+use std::sync::Arc;
+
+const TEXT: &str = r#"[{"id": 1, "tags": {"b": true, "a": null}}, {"id": 2, "tags": {}}]"#;
+
+#[test]
+fn test_parse_with_boxed_keys() {
+    let value: Value<Box<str>> =
+        Rson::try_from_reader_keyed(TEXT.as_bytes(), ParseOptions::default()).unwrap();
+    match &value {
+        Value::Array(records) => match &records[0] {
+            Value::Object(map) => {
+                assert_eq!(map.0.get("id").map(ToString::to_string), Some("1".into()))
+            }
+            _ => panic!("expected an object"),
+        },
+        _ => panic!("expected an array"),
+    }
+
+    let expected = Rson::from_reader(TEXT.as_bytes());
+    assert_eq!(value.clone().map_keys(String::from), expected);
+    let written = Rson::from_reader(value.to_string().as_bytes());
+    assert_eq!(written, expected);
+}
+
+#[test]
+fn test_keyed_parse_errors() {
+    let err =
+        Rson::try_from_reader_keyed::<Arc<str>>("{\"a\" 1}".as_bytes(), ParseOptions::default())
+            .unwrap_err();
+    assert_eq!((err.line(), err.column()), (1, 6));
+}
+
+#[test]
+fn test_map_keys_with_pool() {
+    let value = rson!([{"status": "ok"}, {"status": "failed"}]);
+    let mut pool = StringPool::new();
+    let shared: Value<Arc<str>> = value.clone().map_keys(|key| pool.intern(&key));
+    assert_eq!(pool.len(), 1);
+    assert_eq!(shared.to_string(), to_string(&value));
+    assert_eq!(shared.map_keys(|key| key.to_string()), value);
+}
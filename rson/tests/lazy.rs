@@ -0,0 +1,79 @@
+use rson::{LazyValue, Literal, Number, Rson, Value};
+
+const DOCUMENT: &str = r#"{
+    "Id": 93638382,
+    "Records": [{"a": "]}"}, {"b": [1, 2, {"c": "\"}"}]}],
+    "Name": "Devajit Asem",
+    "GPUDetail": {
+        "RamType": "DDR6",
+        "SerialNum": 12837982,
+    },
+    "HasGPU": true
+}"#;
+
+#[test]
+fn test_lazy_get() {
+    let doc = LazyValue::new(DOCUMENT.as_bytes());
+
+    assert_eq!(
+        doc.get("Name").unwrap().parse().unwrap(),
+        Value::String("Devajit Asem".to_string())
+    );
+    assert_eq!(
+        doc.get("HasGPU").unwrap().parse().unwrap(),
+        Value::Literal(Literal::Bool(true))
+    );
+    assert_eq!(
+        doc.get("GPUDetail")
+            .and_then(|d| d.get("SerialNum"))
+            .unwrap()
+            .parse()
+            .unwrap(),
+        Value::Number(Number::new("12837982".to_string()))
+    );
+    assert_eq!(doc.get("Missing"), None);
+    assert_eq!(doc.get("Id").unwrap().get("x"), None);
+}
+
+#[test]
+fn test_lazy_skips_brackets_inside_strings() {
+    let doc = LazyValue::new(DOCUMENT.as_bytes());
+    let records = doc.get("Records").unwrap();
+
+    assert_eq!(records.elements().unwrap().count(), 2);
+    assert_eq!(
+        records.get_index(1).unwrap().raw(),
+        br#"{"b": [1, 2, {"c": "\"}"}]}"#
+    );
+    assert_eq!(records.get_index(2), None);
+    assert_eq!(
+        doc.members().unwrap().map(|(k, _)| k).collect::<Vec<_>>(),
+        vec![
+            &b"Id"[..],
+            &b"Records"[..],
+            &b"Name"[..],
+            &b"GPUDetail"[..],
+            &b"HasGPU"[..]
+        ]
+    );
+}
+
+#[test]
+fn test_lazy_parse_reports_errors() {
+    let doc = LazyValue::new(br#"{"a": [1, tru], "b": {"c": }}"#);
+    assert!(doc.get("a").unwrap().parse().is_err());
+    assert!(doc.get("b").unwrap().parse().is_err());
+}
+
+#[test]
+fn test_lazy_get_matches_parser() {
+    let text = r#"{"a\u0062": 1, "c": 2, "c": 3, "\"q\"": 4}"#;
+    let doc = LazyValue::new(text.as_bytes());
+    let parsed = Rson::from_reader(text.as_bytes());
+
+    for key in ["ab", "c", "\"q\""] {
+        assert_eq!(doc.get(key).unwrap().parse().unwrap(), parsed[key]);
+    }
+    assert_eq!(doc.get("c").unwrap().raw(), b"3");
+    assert_eq!(doc.get("a\\u0062"), None);
+}
@@ -0,0 +1,119 @@
+use rson::{from_value, rson, to_value, Deserialize, ErrorKind, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::net::{IpAddr, Ipv4Addr};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Inventory {
+    counts: HashMap<u32, String>,
+    hosts: BTreeMap<IpAddr, bool>,
+}
+
+#[test]
+fn test_non_string_keys_round_trip() {
+    let mut counts = HashMap::new();
+    counts.insert(1, "one".to_string());
+    counts.insert(20, "twenty".to_string());
+    let mut hosts = BTreeMap::new();
+    hosts.insert(IpAddr::V4(Ipv4Addr::LOCALHOST), true);
+    hosts.insert("::1".parse().unwrap(), false);
+    let inventory = Inventory { counts, hosts };
+
+    let value = to_value(&inventory).unwrap();
+    assert_eq!(
+        value,
+        rson!({
+            "counts": {"1": "one", "20": "twenty"},
+            "hosts": {"127.0.0.1": true, "::1": false},
+        })
+    );
+    assert_eq!(from_value::<Inventory>(value), Ok(inventory));
+}
+
+#[test]
+fn test_unparsable_key() {
+    let result = from_value::<HashMap<u8, bool>>(rson!({"256": true}));
+
+    assert_eq!(
+        result.unwrap_err().to_string(),
+        "invalid value: key \"256\", expected a key of type `u8`"
+    );
+}
+
+/// Displays every key the same way, so they collide once stringified.
+#[derive(PartialEq, Eq, Hash)]
+struct Opaque(u8);
+
+impl std::fmt::Display for Opaque {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("opaque")
+    }
+}
+
+#[test]
+fn test_colliding_keys() {
+    let mut map = HashMap::new();
+    map.insert(Opaque(1), 1);
+    map.insert(Opaque(2), 2);
+
+    let err = to_value(&map).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::DuplicateKey);
+    assert_eq!(
+        err.to_string(),
+        "duplicate key `opaque` in serialized object"
+    );
+}
+
+#[derive(Serialize)]
+struct Shapes<T> {
+    point: (),
+    pair: Pair,
+    id: Id,
+    unit: Unit,
+    items: [T; 2],
+    ratio: f64,
+    nan: f32,
+    maybe: Option<&'static str>,
+    letter: char,
+    r#type: Box<str>,
+}
+
+#[derive(Serialize)]
+struct Pair(u8, i64);
+
+#[derive(Serialize)]
+struct Id(u64);
+
+#[derive(Serialize)]
+struct Unit;
+
+#[test]
+fn test_serialize_shapes() {
+    let shapes = Shapes {
+        point: (),
+        pair: Pair(1, -2),
+        id: Id(7),
+        unit: Unit,
+        items: [true, false],
+        ratio: 0.5,
+        nan: f32::NAN,
+        maybe: None,
+        letter: 'x',
+        r#type: "t".into(),
+    };
+
+    assert_eq!(
+        to_value(&shapes).unwrap(),
+        rson!({
+            "point": null,
+            "pair": [1, -2],
+            "id": 7,
+            "unit": null,
+            "items": [true, false],
+            "ratio": 0.5,
+            "nan": null,
+            "maybe": null,
+            "letter": "x",
+            "type": "t",
+        })
+    );
+}
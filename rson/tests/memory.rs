@@ -0,0 +1,75 @@
+use rson::{Literal, Map, Number, Rson, RsonMap, Value};
+use std::mem::size_of;
+
+#[test]
+fn test_deep_size_of_scalars() {
+    assert_eq!(Value::Literal(Literal::Null).deep_size_of(), 0);
+    assert_eq!(
+        Value::String(String::with_capacity(100)).deep_size_of(),
+        100
+    );
+    assert_eq!(
+        Value::Number(Number::new("12324".to_string())).deep_size_of(),
+        5
+    );
+}
+
+#[test]
+fn test_deep_size_of_containers() {
+    let mut array = Vec::with_capacity(4);
+    array.push(Value::String("abc".to_string()));
+    array.push(Value::Literal(Literal::Bool(true)));
+    let array = Value::Array(array);
+    assert_eq!(array.deep_size_of(), 4 * size_of::<Value>() + 3);
+
+    let mut map = Map::new();
+    map.insert("key".to_string(), array);
+    let capacity = map.capacity();
+    let object = Value::Object(RsonMap(map));
+    assert_eq!(
+        object.deep_size_of(),
+        capacity * (size_of::<(String, Value)>() + 1) + 3 + 4 * size_of::<Value>() + 3
+    );
+}
+
+#[test]
+fn test_deep_size_of_parsed_document() {
+    let value = Rson::from_reader(r#"{"Name": "Devajit Asem", "Array": [1, 2, 3]}"#.as_bytes());
+    let payload = "Name".len() + "Devajit Asem".len() + "Array".len() + 3;
+    assert!(value.deep_size_of() >= payload + 3 * size_of::<Value>());
+}
+
+#[test]
+fn test_shrink_to_fit() {
+    let mut name = String::with_capacity(64);
+    name.push_str("Devajit Asem");
+    let mut key = String::with_capacity(64);
+    key.push_str("Array");
+    let mut array = Vec::with_capacity(16);
+    array.push(Value::String(name));
+
+    let mut map = Map::with_capacity(32);
+    map.insert(key, Value::Array(array));
+    let mut value = Value::Object(RsonMap(map));
+    let before = value.deep_size_of();
+
+    value.shrink_to_fit();
+
+    let mut map = Map::new();
+    map.insert(
+        "Array".to_string(),
+        Value::Array(vec![Value::String("Devajit Asem".to_string())]),
+    );
+    assert_eq!(value, Value::Object(RsonMap(map)));
+
+    let map = match &value {
+        Value::Object(map) => &map.0,
+        _ => unreachable!(),
+    };
+    let expected = map.capacity() * (size_of::<(String, Value)>() + 1)
+        + "Array".len()
+        + size_of::<Value>()
+        + "Devajit Asem".len();
+    assert_eq!(value.deep_size_of(), expected);
+    assert!(value.deep_size_of() < before);
+}
@@ -0,0 +1,121 @@
+#![cfg(feature = "metrics")]
+
+use metrics_util::debugging::{DebugValue, DebuggingRecorder};
+use metrics_util::{CompositeKey, MetricKind};
+use rson::ndjson::Filter;
+use rson::{to_string, ErrorKind, LazyValue, ParseOptions, Rson};
+
+type Snapshot = Vec<(
+    CompositeKey,
+    Option<metrics::Unit>,
+    Option<metrics::SharedString>,
+    DebugValue,
+)>;
+
+/// The value of counter `name`, with a `kind` label if given.
+fn counter(snapshot: &Snapshot, name: &str, kind: Option<&str>) -> u64 {
+    snapshot
+        .iter()
+        .find_map(|(key, _, _, value)| {
+            let key = key.key();
+            let label = key.labels().find(|label| label.key() == "kind");
+            let matches = key.name() == name && label.map(|label| label.value()) == kind;
+            match value {
+                DebugValue::Counter(count) if matches => Some(*count),
+                _ => None,
+            }
+        })
+        .unwrap_or(0)
+}
+
+/// How many durations histogram `name` has recorded.
+fn samples(snapshot: &Snapshot, name: &str) -> usize {
+    snapshot
+        .iter()
+        .find_map(|(key, _, _, value)| match value {
+            DebugValue::Histogram(samples)
+                if key.kind() == MetricKind::Histogram && key.key().name() == name =>
+            {
+                Some(samples.len())
+            }
+            _ => None,
+        })
+        .unwrap_or(0)
+}
+
+#[test]
+fn test_parse_metrics() {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    metrics::with_local_recorder(&recorder, || {
+        Rson::try_from_reader(r#"{"a": [1, 2]}"#.as_bytes()).unwrap();
+        Rson::parse_to_depth("[[1]]".as_bytes(), 1).unwrap();
+        let mut parser = Rson::with_options(ParseOptions::strict());
+        parser.parse_str("true").unwrap();
+        let e = parser.parse_str("[1,]").unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::UnexpectedToken);
+        Rson::try_from_reader("1 2".as_bytes()).unwrap_err();
+    });
+    let snapshot = snapshotter.snapshot().into_vec();
+
+    assert_eq!(counter(&snapshot, "rson_documents_parsed_total", None), 3);
+    assert_eq!(
+        counter(&snapshot, "rson_bytes_parsed_total", None),
+        13 + 5 + 4 + 4 + 3
+    );
+    assert_eq!(
+        counter(
+            &snapshot,
+            "rson_parse_errors_total",
+            Some("unexpected_token")
+        ),
+        1
+    );
+    assert_eq!(
+        counter(
+            &snapshot,
+            "rson_parse_errors_total",
+            Some("trailing_characters")
+        ),
+        1
+    );
+    assert_eq!(samples(&snapshot, "rson_parse_duration_seconds"), 5);
+}
+
+#[test]
+fn test_internal_parses_are_not_counted() {
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    metrics::with_local_recorder(&recorder, || {
+        let lazy = LazyValue::new(r#"{"\u0061": 1, "\u0062": 2, "z": 3}"#.as_bytes());
+        assert!(lazy.get("z").is_some());
+        Filter::new("$.a == 12").unwrap();
+        let mut partial = Rson::parse_to_depth("[[1, [2]]]".as_bytes(), 1).unwrap();
+        partial.expand(1).unwrap();
+        partial.into_value().unwrap();
+    });
+    let snapshot = snapshotter.snapshot().into_vec();
+
+    assert_eq!(counter(&snapshot, "rson_documents_parsed_total", None), 1);
+    assert_eq!(counter(&snapshot, "rson_bytes_parsed_total", None), 10);
+}
+
+#[test]
+fn test_serialize_metrics() {
+    let value = Rson::from_reader(r#"{"a": [1, 2]}"#.as_bytes());
+    let recorder = DebuggingRecorder::new();
+    let snapshotter = recorder.snapshotter();
+    metrics::with_local_recorder(&recorder, || {
+        assert_eq!(to_string(&value), r#"{"a":[1,2]}"#);
+        let mut out = Vec::new();
+        rson::to_writer(&mut out, &value).unwrap();
+    });
+    let snapshot = snapshotter.snapshot().into_vec();
+
+    assert_eq!(
+        counter(&snapshot, "rson_documents_serialized_total", None),
+        2
+    );
+    assert_eq!(counter(&snapshot, "rson_bytes_serialized_total", None), 22);
+    assert_eq!(samples(&snapshot, "rson_serialize_duration_seconds"), 2);
+}
@@ -0,0 +1,62 @@
+use rson::*;
+
+fn minified(text: &str) -> Result<String> {
+    let mut out = Vec::new();
+    minify(text.as_bytes(), &mut out)?;
+    Ok(String::from_utf8(out).unwrap())
+}
+
+fn minified_jsonc(text: &str) -> Result<String> {
+    let mut out = Vec::new();
+    minify_jsonc(text.as_bytes(), &mut out)?;
+    Ok(String::from_utf8(out).unwrap())
+}
+
+#[test]
+fn test_minify() {
+    let text = "{\r\n  \"name\" : \"a  b\\\" \\\\\" ,\n  \"list\": [ 1, -2.5e3 , true,\tnull ],\n  \"é\": \"中 文\"\n}\n";
+
+    assert_eq!(
+        minified(text).unwrap(),
+        r#"{"name":"a  b\" \\","list":[1,-2.5e3,true,null],"é":"中 文"}"#
+    );
+    assert_eq!(
+        Rson::from_reader(minified(text).unwrap().as_bytes()),
+        Rson::from_reader(text.as_bytes())
+    );
+}
+
+#[test]
+fn test_minify_keeps_separate_tokens_apart() {
+    assert_eq!(minified("[1 2, true  false]").unwrap(), "[1 2,true false]");
+    assert_eq!(minified(" \"a\" \"b\" ").unwrap(), "\"a\"\"b\"");
+}
+
+#[test]
+fn test_minify_leaves_comments() {
+    assert_eq!(minified("[1, /* x */ 2]").unwrap(), "[1,/*x*/2]");
+}
+
+#[test]
+fn test_minify_jsonc() {
+    let text = "// settings\n{\n  \"url\": \"http://x//y/*z*/\", // inline\n  /* block\n   * comment */ \"n\": 1/**/2,\n  \"end\": true // no newline";
+
+    assert_eq!(
+        minified_jsonc(text).unwrap(),
+        r#"{"url":"http://x//y/*z*/","n":1 2,"end":true"#
+    );
+}
+
+#[test]
+fn test_minify_errors() {
+    let err = minified("{\"a\": \"open").unwrap_err();
+    assert!(err.is_eof());
+    assert_eq!(err.to_string(), "EOF in a string at line 1 column 7");
+
+    let err = minified_jsonc("[1,\n  /* open").unwrap_err();
+    assert_eq!(err.to_string(), "EOF in a comment at line 2 column 3");
+
+    let err = minified_jsonc("[1 / 2]").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedToken);
+    assert_eq!(err.to_string(), "expected `//` or `/*` at line 1 column 4");
+}
@@ -0,0 +1,142 @@
+use rson::ndjson::{self, Filter};
+use rson::*;
+
+const LOG: &str = r#"{"level": "info", "message": "started", "took": 3}
+{"level": "error", "message": "disk full", "took": 120, "tags": ["io"]}
+
+{"level": "warn", "message": "slow", "took": 80.5}
+{"level": "error", "message": "timeout", "http": {"status": 504}}
+"#;
+
+fn messages(expression: &str) -> Vec<String> {
+    ndjson::filter(LOG.as_bytes(), expression)
+        .unwrap()
+        .map(|record| record.unwrap().expect_str("/message").unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_lines() {
+    let records: Vec<_> = ndjson::lines(LOG.as_bytes())
+        .collect::<Result<_>>()
+        .unwrap();
+
+    assert_eq!(records.len(), 4);
+    assert_eq!(records[2].expect_str("/level").unwrap(), "warn");
+}
+
+#[test]
+fn test_lines_crlf_and_no_final_newline() {
+    let records: Vec<_> = ndjson::lines("1\r\n[2]\r\n\"3\"".as_bytes())
+        .collect::<Result<_>>()
+        .unwrap();
+
+    assert_eq!(records.len(), 3);
+}
+
+#[test]
+fn test_bad_record_keeps_going() {
+    let mut records = ndjson::lines("{\"a\": 1}\n\n{\"a\": x}\n{\"a\": 3}\n".as_bytes());
+
+    assert!(records.next().unwrap().is_ok());
+    let err = records.next().unwrap().unwrap_err();
+    assert_eq!((err.line(), err.column()), (3, 7));
+    assert!(records.next().unwrap().is_ok());
+    assert!(records.next().is_none());
+}
+
+#[test]
+fn test_invalid_utf8_keeps_going() {
+    let input = b"\"a\"\n\"b\xff\"\n\"c\"\n";
+    let mut records = ndjson::lines(&input[..]);
+
+    assert_eq!(records.next().unwrap().unwrap(), rson!("a"));
+    let err = records.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidUtf8);
+    assert_eq!((err.line(), err.column()), (2, 3));
+    assert_eq!(records.next().unwrap().unwrap(), rson!("c"));
+    assert!(records.next().is_none());
+}
+
+#[test]
+fn test_filter_equality() {
+    assert_eq!(messages("$.level == 'error'"), ["disk full", "timeout"]);
+    assert_eq!(messages(r#"$.level != "error""#), ["started", "slow"]);
+    assert_eq!(messages("$['level']=='warn'"), ["slow"]);
+}
+
+#[test]
+fn test_filter_numbers() {
+    assert_eq!(messages("$.took >= 80"), ["disk full", "slow"]);
+    assert_eq!(messages("$.took < 80.5"), ["started"]);
+    assert_eq!(messages("$.took == 3.0"), ["started"]);
+    assert_eq!(messages("$.http.status >= 500"), ["timeout"]);
+}
+
+#[test]
+fn test_filter_large_integers() {
+    let log = "{\"id\": 12345678901234567890}\n{\"id\": 12345678901234567891}\n{\"id\": -5}\n";
+    let ids = |expression: &str| -> Vec<String> {
+        ndjson::filter(log.as_bytes(), expression)
+            .unwrap()
+            .map(|record| record.unwrap()["id"].to_string())
+            .collect()
+    };
+    assert_eq!(
+        ids("$.id == 12345678901234567890"),
+        ["12345678901234567890"]
+    );
+    assert_eq!(ids("$.id > 12345678901234567890"), ["12345678901234567891"]);
+    assert_eq!(ids("$.id < -0"), ["-5"]);
+    assert_eq!(ids("$.id >= -5.0").len(), 3);
+}
+
+#[test]
+fn test_filter_exists() {
+    assert_eq!(messages("$.tags[0]"), ["disk full"]);
+    assert_eq!(messages("$.http"), ["timeout"]);
+    assert!(messages("$.tags[1]").is_empty());
+}
+
+#[test]
+fn test_filter_is_lazy() {
+    let mut records = ndjson::filter(LOG.as_bytes(), "$.level == 'error'").unwrap();
+
+    assert_eq!(
+        records
+            .next()
+            .unwrap()
+            .unwrap()
+            .expect_str("/message")
+            .unwrap(),
+        "disk full"
+    );
+}
+
+#[test]
+fn test_filter_mismatched_types() {
+    let filter = Filter::new("$.a < 'b'").unwrap();
+
+    assert!(!filter.matches(&rson!({ "a": 1 })));
+    assert!(filter.matches(&rson!({ "a": "a" })));
+    assert!(Filter::new("$.a == null")
+        .unwrap()
+        .matches(&rson!({ "a": null })));
+    assert!(!Filter::new("$.a != 1").unwrap().matches(&rson!({})));
+}
+
+#[test]
+fn test_filter_errors() {
+    for (expression, column) in [
+        ("level == 'error'", 1),
+        ("$. == 1", 3),
+        ("$.a = 1", 5),
+        ("$.a == error", 8),
+        ("$[x]", 3),
+        ("$.a == 1 && $.b", 10),
+    ] {
+        let err = Filter::new(expression).unwrap_err();
+        assert_eq!(err.column(), column, "{:?}: {}", expression, err);
+    }
+    assert!(Filter::new("$.a == 'b").unwrap_err().is_eof());
+}
@@ -0,0 +1,230 @@
+use rson::*;
+
+fn relaxed() -> ParseOptions {
+    ParseOptions {
+        relaxed_numbers: true,
+        ..ParseOptions::default()
+    }
+}
+
+fn number(text: &str, options: ParseOptions) -> Result<String> {
+    match Rson::try_from_reader_with(text.as_bytes(), options)? {
+        Value::Number(n) => Ok(n.as_str().to_string()),
+        value => panic!("not a number: {:?}", value),
+    }
+}
+
+#[test]
+fn test_valid_numbers() {
+    for text in [
+        "0", "-0", "7", "-12", "1.5", "-0.25", "1e5", "1E+5", "2.5e-3", "0e0",
+    ] {
+        assert_eq!(number(text, ParseOptions::strict()).unwrap(), text);
+        assert_eq!(number(text, relaxed()).unwrap(), text);
+    }
+}
+
+#[test]
+fn test_numbers_in_structures() {
+    let value = Rson::from_reader(r#"{"a": [-1, 2.5e3,0.5], "b": -0.0}"#.as_bytes());
+
+    assert_eq!(value.expect_f64("/a/1").unwrap(), 2500.0);
+    assert_eq!(value.expect_i64("/a/0").unwrap(), -1);
+    assert_eq!(value.expect_f64("/b").unwrap(), 0.0);
+}
+
+#[test]
+fn test_malformed_numbers() {
+    let cases = [
+        (
+            "0123",
+            "leading zeros are not allowed in a number at line 1 column 2",
+        ),
+        (
+            "-00",
+            "leading zeros are not allowed in a number at line 1 column 3",
+        ),
+        ("1.", "EOF while parsing a number at line 1 column 3"),
+        (
+            "[1.]",
+            "expected a digit after the decimal point at line 1 column 4",
+        ),
+        (
+            ".5",
+            "expected a digit before the decimal point at line 1 column 1",
+        ),
+        ("1e", "EOF while parsing a number at line 1 column 3"),
+        (
+            "[1e]",
+            "expected a digit in the exponent at line 1 column 4",
+        ),
+        (
+            "[1e+]",
+            "expected a digit in the exponent at line 1 column 5",
+        ),
+        ("+1", "a number can't start with `+` at line 1 column 1"),
+        ("[-]", "expected a digit at line 1 column 3"),
+        (
+            "1.2.3",
+            "unexpected character `.` in number at line 1 column 4",
+        ),
+        (
+            "12abc",
+            "unexpected character `a` in number at line 1 column 3",
+        ),
+    ];
+    for (text, message) in cases {
+        for options in [ParseOptions::default(), ParseOptions::strict()] {
+            let err = number(text, options).unwrap_err();
+            assert_eq!(err.to_string(), message, "{}", text);
+        }
+    }
+}
+
+#[test]
+fn test_malformed_number_kinds() {
+    let err = number("[0123]", ParseOptions::strict()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidNumber);
+
+    let err = number("[1.", ParseOptions::strict()).unwrap_err();
+    assert!(err.is_eof());
+}
+
+#[test]
+fn test_relaxed_numbers() {
+    let cases = [
+        ("0123", "123"),
+        ("-007", "-7"),
+        ("00", "0"),
+        ("+1", "1"),
+        ("1.", "1.0"),
+        ("[1.]", ""),
+        (".5", "0.5"),
+        ("-.5", "-0.5"),
+        ("+.5e2", "0.5e2"),
+    ];
+    for (text, expected) in cases {
+        if expected.is_empty() {
+            let value = Rson::try_from_reader_with(text.as_bytes(), relaxed()).unwrap();
+            assert_eq!(to_string(&value), "[1.0]");
+        } else {
+            assert_eq!(number(text, relaxed()).unwrap(), expected, "{}", text);
+        }
+    }
+}
+
+#[test]
+fn test_relaxed_rejects_missing_digits() {
+    let err = number("[1e]", relaxed()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidNumber);
+
+    let err = number("[.]", relaxed()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "expected a digit after the decimal point at line 1 column 3"
+    );
+}
+
+#[test]
+fn test_try_into_f64_lossless() {
+    let lossless = |text: &str| Number::new(text.to_string()).try_into_f64_lossless();
+
+    assert_eq!(lossless("0.5"), Some(0.5));
+    assert_eq!(lossless("-0"), Some(-0.0));
+    assert_eq!(lossless("1.50e2"), Some(150.0));
+    assert_eq!(lossless("9007199254740992"), Some(9007199254740992.0));
+    assert_eq!(lossless("0.000244140625"), Some(0.000244140625));
+    assert_eq!(lossless("1e300"), None);
+    assert_eq!(lossless("1e22"), Some(1e22));
+
+    assert_eq!(lossless("0.1"), None);
+    assert_eq!(lossless("9007199254740993"), None);
+    assert_eq!(lossless("12345678901234567890"), None);
+    assert_eq!(lossless("1e400"), None);
+    assert_eq!(lossless("5e-324"), None);
+    assert_eq!(lossless("abc"), None);
+    assert_eq!(lossless("0e9223372036854775807"), None);
+    assert_eq!(lossless("0.0e-9223372036854775808"), None);
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Trade {
+    #[rson(number_fallback = "id_text")]
+    id: u64,
+    id_text: Option<String>,
+    #[rson(number_fallback = "raw_amount")]
+    amount: Option<i32>,
+    raw_amount: Option<Number>,
+}
+
+fn fallback() -> DeserializeOptions {
+    DeserializeOptions {
+        number_fallback: true,
+        ..DeserializeOptions::default()
+    }
+}
+
+#[test]
+fn test_number_fallback() {
+    let input = r#"{"id": 98765432109876543210, "amount": 3000000000}"#;
+    let trade: Trade = fallback().from_str(input).unwrap();
+    assert_eq!(
+        trade,
+        Trade {
+            id: 0,
+            id_text: Some("98765432109876543210".to_string()),
+            amount: None,
+            raw_amount: Some(Number::new("3000000000".to_string())),
+        }
+    );
+
+    let trade: Trade = fallback().from_str(r#"{"id": 7, "amount": -2}"#).unwrap();
+    assert_eq!((trade.id, trade.id_text), (7, None));
+    assert_eq!((trade.amount, trade.raw_amount), (Some(-2), None));
+}
+
+#[test]
+fn test_number_fallback_off() {
+    let input = r#"{"id": 98765432109876543210}"#;
+    let err = from_str::<Trade>(input).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid value: number `98765432109876543210`, expected a u64"
+    );
+
+    // Only numbers fall back; other values are still type errors.
+    let err = fallback().from_str::<Trade>(r#"{"id": "7"}"#).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::Message);
+    // So do fractions the type can't hold.
+    let trade: Trade = fallback().from_str(r#"{"id": 1.5}"#).unwrap();
+    assert_eq!(trade.id_text.as_deref(), Some("1.5"));
+}
+
+#[test]
+fn test_floats_out_of_range() {
+    let float = |text: &str| from_value::<f64>(Value::Number(Number::new(text.to_string())));
+    assert_eq!(float("1e308").unwrap(), 1e308);
+    assert_eq!(float("1e-400").unwrap(), 0.0);
+    for text in ["1e400", "-1e400", "NaN", "inf"] {
+        let err = float(text).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("invalid value: number `{}`, expected an f64", text)
+        );
+    }
+    let err = from_value::<f32>(Value::Number(Number::new("1e39".to_string()))).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid value: number `1e39`, expected an f32"
+    );
+
+    #[derive(Debug, Deserialize)]
+    struct Reading {
+        #[rson(number_fallback = "raw")]
+        value: f64,
+        raw: Option<Number>,
+    }
+    let reading: Reading = fallback().from_str(r#"{"value": -1e400}"#).unwrap();
+    assert_eq!(reading.value, 0.0);
+    assert_eq!(reading.raw.as_ref().map(Number::as_str), Some("-1e400"));
+}
@@ -0,0 +1,118 @@
+use rson::*;
+
+const DOC: &str = r#"{
+    "name": "regions",
+    "count": 2,
+    "regions": [
+        {"id": "eu", "stats": {"hits": [1, 2, 3], "note": "a ] in \"text\""}},
+        {"id": "us", "stats": {}}
+    ]
+}"#;
+
+#[test]
+fn test_depth_zero_is_raw() {
+    let partial = Rson::parse_to_depth(DOC.as_bytes(), 0).unwrap();
+
+    match &partial {
+        PartialValue::Raw(raw) => assert_eq!(raw.get(), DOC),
+        other => panic!("expected a raw value, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_depth_one() {
+    let partial = Rson::parse_to_depth(DOC.as_bytes(), 1).unwrap();
+
+    assert_eq!(
+        partial.pointer("/name"),
+        Some(&PartialValue::String("regions".to_string()))
+    );
+    let regions = partial.pointer("/regions").unwrap();
+    assert!(regions.is_raw());
+    assert_eq!(partial.pointer("/regions/0"), None);
+}
+
+#[test]
+fn test_raw_text_keeps_strings_intact() {
+    let partial = Rson::parse_to_depth(DOC.as_bytes(), 3).unwrap();
+
+    match partial.pointer("/regions/0/stats").unwrap() {
+        PartialValue::Raw(raw) => {
+            assert_eq!(
+                raw.get(),
+                r#"{"hits": [1, 2, 3], "note": "a ] in \"text\""}"#
+            );
+            let stats = raw.parse().unwrap();
+            assert_eq!(stats.expect_array("/hits", 3).unwrap().len(), 3);
+        }
+        other => panic!("expected a raw value, got {:?}", other),
+    }
+    match partial.pointer("/regions/1/stats").unwrap() {
+        PartialValue::Raw(raw) => assert_eq!(raw.to_string(), "{}"),
+        other => panic!("expected a raw value, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_expand() {
+    let mut partial = Rson::parse_to_depth(DOC.as_bytes(), 1).unwrap();
+
+    partial.pointer_mut("/regions").unwrap().expand(1).unwrap();
+
+    assert_eq!(
+        partial.pointer("/regions/1").map(PartialValue::is_raw),
+        Some(true)
+    );
+    partial
+        .pointer_mut("/regions/1")
+        .unwrap()
+        .expand(5)
+        .unwrap();
+    assert_eq!(
+        partial.pointer("/regions/1/id"),
+        Some(&PartialValue::String("us".to_string()))
+    );
+}
+
+#[test]
+fn test_into_value_matches_full_parse() {
+    for depth in 0..5 {
+        let partial = Rson::parse_to_depth(DOC.as_bytes(), depth).unwrap();
+        assert_eq!(
+            partial.into_value().unwrap(),
+            Rson::from_reader(DOC.as_bytes()),
+            "depth {}",
+            depth
+        );
+    }
+}
+
+#[test]
+fn test_scalar_document() {
+    let partial = Rson::parse_to_depth("  42 ".as_bytes(), 0).unwrap();
+
+    assert_eq!(
+        partial,
+        PartialValue::from(Rson::from_reader("42".as_bytes()))
+    );
+}
+
+#[test]
+fn test_errors() {
+    for input in ["[1, [2, 3]", "[1, [2, 3}]", "[[1]] x", "{\"a\": [\"]"] {
+        let err = Rson::parse_to_depth(input.as_bytes(), 1).unwrap_err();
+        assert!(
+            matches!(
+                err.kind(),
+                ErrorKind::UnexpectedEof
+                    | ErrorKind::UnexpectedToken
+                    | ErrorKind::TrailingCharacters
+            ),
+            "{:?}: {}",
+            input,
+            err
+        );
+    }
+    let err = Rson::parse_to_depth("[1, [2, 3]".as_bytes(), 1).unwrap_err();
+    assert!(err.is_eof());
+}
@@ -0,0 +1,168 @@
+use rson::{rson, Value, MAX_ARRAY_PADDING};
+
+#[test]
+fn test_pointer() {
+    let value = rson!({
+        "users": [{"name": "asem", "id": 7}],
+        "a/b": {"m~n": true},
+        "": 0,
+    });
+
+    assert_eq!(value.pointer(""), Some(&value));
+    assert_eq!(value.pointer("/users/0/name"), Some(&rson!("asem")));
+    assert_eq!(value.pointer("/a~1b/m~0n"), Some(&rson!(true)));
+    assert_eq!(value.pointer("/"), Some(&rson!(0)));
+    assert_eq!(value.pointer("/users/01"), None);
+    assert_eq!(value.pointer("/users/1"), None);
+    assert_eq!(value.pointer("users"), None);
+}
+
+#[test]
+fn test_pointer_mut() {
+    let mut value = rson!({"users": [{"name": "asem"}]});
+    *value.pointer_mut("/users/0/name").unwrap() = rson!("devajit");
+
+    assert_eq!(value, rson!({"users": [{"name": "devajit"}]}));
+}
+
+#[test]
+fn test_expect() {
+    let value = rson!({
+        "name": "asem",
+        "id": 7,
+        "ratio": 0.5,
+        "admin": false,
+        "tags": ["a", "b"],
+        "team": {},
+    });
+
+    assert_eq!(value.expect_str("/name"), Ok("asem"));
+    assert_eq!(value.expect_i64("/id"), Ok(7));
+    assert_eq!(value.expect_u64("/id"), Ok(7));
+    assert_eq!(value.expect_f64("/ratio"), Ok(0.5));
+    assert_eq!(value.expect_bool("/admin"), Ok(false));
+    assert_eq!(value.expect_array("/tags", 2).map(Vec::len), Ok(2));
+    assert!(value.expect_object("/team").is_ok());
+}
+
+#[test]
+fn test_expect_errors() {
+    let value = rson!({"user": {"id": 7, "tags": []}, "ratio": 0.5});
+
+    let errors = [
+        value.expect_str("/user/id").unwrap_err(),
+        value.expect_i64("/user/name").unwrap_err(),
+        value.expect_i64("/ratio").unwrap_err(),
+        value.expect_array("/user/tags", 1).unwrap_err(),
+        value.expect_array("", 0).unwrap_err(),
+    ];
+
+    assert_eq!(
+        errors.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        vec![
+            "at `/user/id`: invalid type: number `7`, expected a string",
+            "at `/user/name`: no such value",
+            "at `/ratio`: invalid value: number `0.5`, expected an i64",
+            "at `/user/tags`: invalid length 0, expected at least 1 element",
+            "at the root: invalid type: object, expected an array",
+        ]
+    );
+}
+
+#[test]
+fn test_set_at_creates_path() {
+    let mut value = rson!({"name": "x"});
+
+    assert_eq!(value.set_at("/a/b/0/c", 1).unwrap(), None);
+    assert_eq!(value.set_at("/name", "y").unwrap(), Some(rson!("x")));
+    assert_eq!(value, rson!({"name": "y", "a": {"b": [{"c": 1}]}}));
+
+    let mut value = rson!(null);
+    value.set_at("/m~1n/-", true).unwrap();
+    value.set_at("/m~1n/-", false).unwrap();
+    assert_eq!(value, rson!({"m/n": [true, false]}));
+
+    value.set_at("", 5).unwrap();
+    assert_eq!(value, rson!(5));
+}
+
+#[test]
+fn test_set_at_pads_arrays() {
+    let mut value = rson!({"list": [1], "gap": null});
+
+    value.set_at("/list/3", 4).unwrap();
+    value.set_at("/gap/1/x", "y").unwrap();
+
+    assert_eq!(
+        value,
+        rson!({"list": [1, null, null, 4], "gap": [null, {"x": "y"}]})
+    );
+}
+
+#[test]
+fn test_set_at_errors() {
+    let original = rson!({"name": "x", "list": [1]});
+    let mut value = original.clone();
+
+    let err = value.set_at("/name/first", 1).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "at `/name`: invalid type: string \"x\", expected an object or array"
+    );
+    let err = value.set_at("/list/first", 1).unwrap_err();
+    assert_eq!(err.to_string(), "at `/list`: `first` is not an array index");
+    let err = value.set_at("name", 1).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "at `name`: a JSON Pointer must be empty or start with `/`"
+    );
+    assert_eq!(value, original);
+}
+
+#[test]
+fn test_set_at_padding_limit() {
+    let mut value = rson!({"list": []});
+
+    for index in ["18446744073709551615", "1000000000000"] {
+        let err = value.set_at(&format!("/list/{}", index), 1).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!(
+                "at `/list`: index {} is more than {} past the end of the array",
+                index, MAX_ARRAY_PADDING
+            )
+        );
+        let err = value.set_at(&format!("/new/{}/x", index), 1).unwrap_err();
+        assert!(err.to_string().starts_with("at `/new`: index"), "{}", err);
+        assert!(value
+            .get_or_insert_with(&format!("/list/{}", index), || 1.into())
+            .is_err());
+    }
+    assert_eq!(value, rson!({"list": []}));
+
+    value
+        .set_at(&format!("/list/{}", MAX_ARRAY_PADDING), true)
+        .unwrap();
+    assert_eq!(
+        value.expect_array("/list", 0).unwrap().len(),
+        MAX_ARRAY_PADDING + 1
+    );
+}
+
+#[test]
+fn test_get_or_insert_with() {
+    let mut value = rson!({"retries": 3, "off": null});
+
+    *value
+        .get_or_insert_with("/limits/cpu", || rson!(1))
+        .unwrap() = rson!(2);
+    let retries = value.get_or_insert_with("/retries", || rson!(5)).unwrap();
+    assert_eq!(*retries, rson!(3));
+    let off = value.get_or_insert_with("/off", || rson!(true)).unwrap();
+    assert_eq!(*off, Value::from(()));
+
+    assert_eq!(
+        value,
+        rson!({"retries": 3, "off": null, "limits": {"cpu": 2}})
+    );
+}
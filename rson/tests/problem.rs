@@ -0,0 +1,80 @@
+use rson::*;
+
+#[test]
+fn test_new_uses_reason_phrase() {
+    let problem = ProblemDetails::new(404).with_detail("no order 7");
+
+    assert_eq!(
+        to_value(&problem).unwrap(),
+        rson!({
+            "type": "about:blank",
+            "title": "Not Found",
+            "status": 404,
+            "detail": "no order 7"
+        })
+    );
+    assert_eq!(ProblemDetails::new(599).title, None);
+    assert_eq!(PROBLEM_JSON, "application/problem+json");
+}
+
+#[test]
+fn test_round_trip() {
+    let problem = ProblemDetails::new(422)
+        .with_type("https://example.com/probs/out-of-credit")
+        .with_title("You do not have enough credit.")
+        .with_instance("/account/12345/msgs/abc")
+        .with_extension("balance", 30)
+        .with_extension("accounts", vec!["/account/12345", "/account/67890"])
+        .with_extension("status", "ignored");
+
+    assert!(!problem.extensions.contains_key("status"));
+    let value = to_value(&problem).unwrap();
+    assert_eq!(from_value::<ProblemDetails>(value).unwrap(), problem);
+}
+
+#[test]
+fn test_from_str() {
+    let problem: ProblemDetails = from_str(
+        r#"{
+            "type": "https://example.com/probs/out-of-credit",
+            "title": "You do not have enough credit.",
+            "status": 403,
+            "detail": "Your current balance is 30, but that costs 50.",
+            "balance": 30
+        }"#,
+    )
+    .unwrap();
+
+    assert_eq!(problem.status, Some(403));
+    assert_eq!(problem.r#type, "https://example.com/probs/out-of-credit");
+    assert_eq!(problem.extensions["balance"], Value::from(30));
+}
+
+#[test]
+fn test_wrong_member_types_are_ignored() {
+    let problem: ProblemDetails =
+        from_str(r#"{"type": 1, "title": ["x"], "status": "404", "detail": null}"#).unwrap();
+
+    assert_eq!(problem, ProblemDetails::default());
+    assert_eq!(problem.r#type, "about:blank");
+
+    let problem: ProblemDetails = from_str(r#"{"status": 70000}"#).unwrap();
+    assert_eq!(problem.status, None);
+    assert!(from_str::<ProblemDetails>("[]").is_err());
+}
+
+#[test]
+fn test_from_error() {
+    let err = Rson::try_from_reader("{\"a\": x}".as_bytes()).unwrap_err();
+    let problem = ProblemDetails::from(&err);
+
+    assert_eq!(problem.status, Some(400));
+    assert_eq!(problem.title.as_deref(), Some("Bad Request"));
+    assert_eq!(problem.detail, Some(err.to_string()));
+    assert_eq!(problem.extensions["line"], Value::from(1));
+    assert_eq!(problem.extensions["column"], Value::from(7));
+
+    let problem = ProblemDetails::from(Error::missing_field("id"));
+    assert_eq!(problem.detail.as_deref(), Some("missing field `id`"));
+    assert!(problem.extensions.is_empty());
+}
@@ -0,0 +1,108 @@
+use rson::{
+    from_str, rson, schema_for, to_value, Deserialize, ErrorKind, RsonSchema, Serialize, MAX_DEPTH,
+};
+
+#[derive(Serialize, Deserialize, RsonSchema, Debug, PartialEq)]
+struct Tree {
+    name: String,
+    children: Vec<Tree>,
+}
+
+#[derive(Serialize, Deserialize, RsonSchema, Debug, PartialEq)]
+struct Node {
+    value: u32,
+    next: Option<Box<Node>>,
+}
+
+#[derive(Serialize, Deserialize, RsonSchema, Debug, PartialEq)]
+struct List<T> {
+    head: T,
+    tail: Option<Box<Self>>,
+}
+
+#[test]
+fn test_recursive_round_trip() {
+    let tree: Tree = from_str(
+        r#"{"name": "root", "children": [
+            {"name": "a", "children": []},
+            {"name": "b", "children": [{"name": "c", "children": []}]}
+        ]}"#,
+    )
+    .unwrap();
+    assert_eq!(tree.children[1].children[0].name, "c");
+    assert_eq!(
+        to_value(&tree)
+            .unwrap()
+            .pointer("/children/1/children/0")
+            .unwrap(),
+        &rson!({"name": "c", "children": []})
+    );
+
+    let list: Node = from_str(r#"{"value": 1, "next": {"value": 2, "next": null}}"#).unwrap();
+    assert_eq!(
+        list,
+        Node {
+            value: 1,
+            next: Some(Box::new(Node {
+                value: 2,
+                next: None
+            })),
+        }
+    );
+    assert_eq!(
+        to_value(&list).unwrap(),
+        rson!({"value": 1, "next": {"value": 2, "next": null}})
+    );
+}
+
+#[test]
+fn test_recursive_depth_limit() {
+    // Each level of `Node` is one object deep.
+    let nested = |depth: usize| {
+        let mut text = String::from(r#"{"value": 0, "next": null}"#);
+        for _ in 1..depth {
+            text = format!(r#"{{"value": 0, "next": {}}}"#, text);
+        }
+        text
+    };
+
+    let node: Node = from_str(&nested(MAX_DEPTH)).unwrap();
+    let mut depth = 1;
+    let mut current = &node;
+    while let Some(next) = &current.next {
+        depth += 1;
+        current = next;
+    }
+    assert_eq!(depth, MAX_DEPTH);
+
+    let too_deep = from_str::<Node>(&nested(MAX_DEPTH + 1)).unwrap_err();
+    assert_eq!(too_deep.kind(), ErrorKind::DepthLimit);
+}
+
+#[test]
+fn test_depth_limit_arrays() {
+    let text = "[".repeat(MAX_DEPTH + 1) + &"]".repeat(MAX_DEPTH + 1);
+    assert_eq!(
+        from_str::<rson::Value>(&text).unwrap_err().to_string(),
+        "Nesting deeper than 128 levels at line 1 column 129"
+    );
+}
+
+#[test]
+fn test_recursive_schema() {
+    let schema = schema_for::<Tree>();
+    assert_eq!(
+        schema["definitions"]["Tree"]["properties"]["children"],
+        rson!({"type": "array", "items": {"$ref": "#/definitions/Tree"}})
+    );
+
+    let schema = schema_for::<List<String>>();
+    assert_eq!(
+        schema["properties"]["tail"],
+        rson!({"anyOf": [{"$ref": "#/definitions/List<String>"}, {"type": "null"}]})
+    );
+    assert_eq!(
+        schema["definitions"]["List<String>"]["properties"]["head"],
+        rson!({"type": "string"})
+    );
+}
@@ -0,0 +1,158 @@
+use rson::{rson, schema_for, Map, RsonMap, RsonSchema, Value, MAX_RESOLVED_BYTES};
+use std::mem;
+
+#[test]
+fn test_resolve_refs() {
+    let document = rson!({
+        "definitions": {
+            "id": {"type": "integer", "minimum": 1},
+            "user": {
+                "type": "object",
+                "properties": {
+                    "id": {"$ref": "#/definitions/id"},
+                    "friends": {"type": "array", "items": {"$ref": "#/definitions/id"}},
+                },
+            },
+            "a~b/c": {"type": "string"},
+        },
+        "properties": {
+            "owner": {"$ref": "#/definitions/user", "description": "ignored"},
+            "label": {"$ref": "#/definitions/a~0b~1c"},
+            "external": {"$ref": "other.json#/definitions/x"},
+        },
+    });
+
+    let resolved = document.resolve_refs().unwrap();
+    assert_eq!(
+        resolved["properties"]["owner"],
+        rson!({
+            "type": "object",
+            "properties": {
+                "id": {"type": "integer", "minimum": 1},
+                "friends": {"type": "array", "items": {"type": "integer", "minimum": 1}},
+            },
+        })
+    );
+    assert_eq!(resolved["properties"]["label"], rson!({"type": "string"}));
+    assert_eq!(
+        resolved["properties"]["external"],
+        rson!({"$ref": "other.json#/definitions/x"})
+    );
+}
+
+#[test]
+fn test_resolve_refs_root_and_escapes() {
+    let document = rson!({
+        "definitions": {"List<String>": {"type": "array"}},
+        "items": {"$ref": "#/definitions/List%3CString%3E"},
+        "self": {"$ref": "#/definitions"},
+    });
+
+    let resolved = document.resolve_refs().unwrap();
+    assert_eq!(resolved["items"], rson!({"type": "array"}));
+    assert_eq!(resolved["self"], rson!({"List<String>": {"type": "array"}}));
+}
+
+#[test]
+fn test_resolve_refs_errors() {
+    let document = rson!({
+        "definitions": {
+            "a": {"$ref": "#/definitions/b"},
+            "b": {"items": {"$ref": "#/definitions/a"}},
+        },
+        "properties": {
+            "x": {"$ref": "#/definitions/missing"},
+            "y": {"$ref": "#/definitions/missing"},
+            "z": {"$ref": "#/definitions/a"},
+        },
+    });
+
+    let message = document.resolve_refs().unwrap_err().to_string();
+    assert!(message.starts_with("unresolved $ref: "), "{}", message);
+    assert!(message.contains("`#/definitions/missing` does not exist"));
+    assert_eq!(message.matches("missing").count(), 1);
+    assert!(message.contains("cycle `#/definitions/a` -> `#/definitions/b` -> `#/definitions/a`"));
+}
+
+#[test]
+fn test_resolve_refs_limit() {
+    // Each definition uses the next twice: 2^30 values once expanded.
+    let mut definitions = Map::new();
+    for i in 0..30 {
+        let reference = format!("#/definitions/d{}", i + 1);
+        let element = rson!({"$ref": (reference.as_str())});
+        definitions.insert(
+            format!("d{}", i),
+            Value::Array(vec![element.clone(), element]),
+        );
+    }
+    definitions.insert("d30".to_string(), rson!(true));
+    let mut map = Map::new();
+    map.insert(
+        "definitions".to_string(),
+        Value::Object(RsonMap(definitions)),
+    );
+    map.insert("root".to_string(), rson!({"$ref": "#/definitions/d0"}));
+    let document = Value::Object(RsonMap(map));
+
+    let err = document.resolve_refs().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "resolving $refs produces more than {} bytes",
+            MAX_RESOLVED_BYTES
+        )
+    );
+
+    // Seven values, the keys `a` and `b` and four one-digit numbers.
+    let small = rson!({"a": [1, 2], "b": {"$ref": "#/a"}});
+    let size = 7 * mem::size_of::<Value>() + 2 + 4;
+    assert!(small.resolve_refs_with_limit(size).is_ok());
+    assert!(small.resolve_refs_with_limit(size - 1).is_err());
+}
+
+#[test]
+fn test_resolve_refs_limit_counts_strings() {
+    // Few values, but 2^19 copies of a 1 MB string once expanded.
+    let mut definitions = Map::new();
+    definitions.insert("d0".to_string(), Value::String("x".repeat(1 << 20)));
+    for i in 1..20 {
+        let reference = format!("#/definitions/d{}", i - 1);
+        let element = rson!({"$ref": (reference.as_str())});
+        definitions.insert(
+            format!("d{}", i),
+            Value::Array(vec![element.clone(), element]),
+        );
+    }
+    let mut map = Map::new();
+    map.insert(
+        "definitions".to_string(),
+        Value::Object(RsonMap(definitions)),
+    );
+    map.insert("root".to_string(), rson!({"$ref": "#/definitions/d19"}));
+    let document = Value::Object(RsonMap(map));
+
+    assert!(document.resolve_refs().is_err());
+}
+
+#[derive(RsonSchema)]
+#[allow(dead_code)]
+struct Account {
+    owner: User,
+    members: Vec<User>,
+}
+
+#[derive(RsonSchema)]
+#[allow(dead_code)]
+struct User {
+    name: String,
+}
+
+#[test]
+fn test_resolve_generated_schema() {
+    let resolved = schema_for::<Account>().resolve_refs().unwrap();
+    assert_eq!(
+        resolved["properties"]["members"]["items"]["properties"]["name"],
+        rson!({"type": "string"})
+    );
+}
@@ -0,0 +1,87 @@
+use rson::*;
+
+#[test]
+fn test_parse_many() {
+    let mut parser = Rson::with_options(ParseOptions::default());
+    let messages = [
+        r#"{"id": 1, "ok": true}"#,
+        "[1.5e3, null, \"x\"]",
+        "  -0.25 ",
+        "false",
+    ];
+
+    for message in messages {
+        assert_eq!(
+            parser.parse_str(message).unwrap(),
+            Rson::from_reader(message.as_bytes())
+        );
+    }
+}
+
+#[test]
+fn test_recovers_after_errors() {
+    let mut parser = Rson::with_options(ParseOptions::default());
+
+    let err = parser.parse_str("[[[1, x").unwrap_err();
+    assert_eq!((err.line(), err.column()), (1, 7));
+    let err = parser.parse_str("tru").unwrap_err();
+    assert!(err.is_eof());
+    let err = parser.parse_str("[1] 2").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::TrailingCharacters);
+
+    assert_eq!(
+        parser.parse_str("\n [1]").unwrap(),
+        Rson::from_reader("[1]".as_bytes())
+    );
+    let err = parser.parse_str("\n\n  nul!").unwrap_err();
+    assert_eq!((err.line(), err.column()), (3, 3));
+}
+
+#[test]
+fn test_keeps_options() {
+    let mut parser = Rson::with_options(ParseOptions::strict());
+
+    assert!(parser.parse_str("[1,]").is_err());
+    assert!(parser.parse_slice(b"[1]").is_ok());
+    assert!(parser.parse_str("[01]").is_err());
+
+    let mut relaxed = Rson::with_options(ParseOptions {
+        relaxed_numbers: true,
+        ..ParseOptions::default()
+    });
+    assert_eq!(
+        relaxed.parse_str("[007, .5]").unwrap(),
+        Rson::from_reader("[7, 0.5]".as_bytes())
+    );
+}
+
+#[test]
+fn test_parse_slice_invalid_utf8() {
+    let mut parser = Rson::with_options(ParseOptions::default());
+
+    let err = parser.parse_slice(b"\"\xff\"").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidUtf8);
+    assert!(parser.parse_slice(b"\"ok\"").is_ok());
+}
+
+#[test]
+fn test_reuses_string_buffer() {
+    let mut parser = Rson::with_options(ParseOptions::default());
+
+    let err = parser
+        .parse_str(r#"["long string cut", "ab\q"]"#)
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidEscape);
+    assert_eq!(
+        parser
+            .parse_str(r#"{"k": "v", "longer key": "é"}"#)
+            .unwrap(),
+        rson!({"k": "v", "longer key": "é"})
+    );
+
+    let mut buffer = br#""first""#.to_vec();
+    assert_eq!(parser.parse_slice(&buffer).unwrap(), rson!("first"));
+    buffer.truncate(0);
+    buffer.extend_from_slice(b"2");
+    assert_eq!(parser.parse_slice(&buffer).unwrap(), rson!(2));
+}
@@ -1,5 +1,4 @@
-use rson::{self, Literal, Number, Rson, RsonMap, Value};
-use std::collections::HashMap;
+use rson::{self, Literal, Map, Number, Rson, RsonMap, Value};
 
 #[test]
 fn test_literal() {
@@ -46,7 +45,7 @@ fn test_number() {
 fn test_object_empty() {
     let object = "{  }";
     let actual = Rson::from_reader(object.as_bytes());
-    assert!(actual == Value::Object(RsonMap(HashMap::new())));
+    assert!(actual == Value::Object(RsonMap(Map::new())));
 }
 
 #[test]
@@ -62,7 +61,7 @@ fn test_object_string_bool() {
     let object = r#"{"IsGPU": true}"#;
     let actual = Rson::from_reader(object.as_bytes());
 
-    let mut map = HashMap::new();
+    let mut map = Map::new();
     map.insert(r#"IsGPU"#.to_string(), Value::Literal(Literal::Bool(true)));
     assert_eq!(actual, Value::Object(RsonMap(map)));
 }
@@ -73,7 +72,7 @@ fn test_object_string_string() {
     let object = r#"{"name": "Devajit Asem"}"#;
     let actual = Rson::from_reader(object.as_bytes());
 
-    let mut map = HashMap::new();
+    let mut map = Map::new();
     map.insert(
         r#"name"#.to_string(),
         Value::String(r#"Devajit Asem"#.to_string()),
@@ -104,7 +103,7 @@ fn test_object_multiple_entries() {
     }"#;
     let actual = Rson::from_reader(object.as_bytes());
 
-    let mut map = HashMap::new();
+    let mut map = Map::new();
     map.insert(
         r#"Id"#.to_string(),
         Value::Number(Number::new("93638382".to_string())),
@@ -120,7 +119,7 @@ fn test_object_multiple_entries() {
         Value::Literal(Literal::Bool(false)),
     );
 
-    let mut inner_map = HashMap::new();
+    let mut inner_map = Map::new();
     inner_map.insert(
         r#"RamType"#.to_string(),
         Value::String(r#"DDR6"#.to_string()),
@@ -143,7 +142,7 @@ fn test_object_array() {
     let array = r#"{"Name": ["Devajit Asem", 12324, true, false, null]}"#;
     let actual = Rson::from_reader(array.as_bytes());
 
-    let mut map = HashMap::new();
+    let mut map = Map::new();
     map.insert(
         r#"Name"#.to_string(),
         Value::Array(vec![
@@ -172,7 +171,7 @@ fn setup_object() -> (&'static str, Value) {
     "Array": ["Devajit Asem", 12324, true, false, null]
     }"#;
 
-    let mut map = HashMap::new();
+    let mut map = Map::new();
     map.insert(
         r#"Id"#.to_string(),
         Value::Number(Number::new("93638382".to_string())),
@@ -188,7 +187,7 @@ fn setup_object() -> (&'static str, Value) {
         Value::Literal(Literal::Bool(false)),
     );
 
-    let mut inner_map = HashMap::new();
+    let mut inner_map = Map::new();
     inner_map.insert(
         r#"RamType"#.to_string(),
         Value::String(r#"DDR6"#.to_string()),
@@ -227,7 +226,7 @@ fn test_object_access_index() {
     let (object_str, _) = setup_object();
     let parsed_object = Rson::from_reader(object_str.as_bytes());
 
-    let mut gpu_detail_map = HashMap::new();
+    let mut gpu_detail_map = Map::new();
     gpu_detail_map.insert(
         r#"RamType"#.to_string(),
         Value::String(r#"DDR6"#.to_string()),
@@ -1,7 +1,7 @@
 use rson::*;
 use std::fs::File;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, Debug, PartialEq)]
 struct A {
     b: u32,
 }
@@ -17,8 +17,14 @@ fn open_file(filename: &str) -> File {
 #[test]
 fn test_open_file() {
     let json = Rson::from_reader(open_file("test.json"));
-    assert_eq!(json, Value::Literal(Literal::Null));
+
+    let mut map = Map::new();
+    map.insert("hello".to_string(), Value::String("world".to_string()));
+    assert_eq!(json, Value::Object(RsonMap(map)));
 }
 
 #[test]
-fn test_derive() {}
+fn test_derive() {
+    let a: A = from_str(r#"{"b": 42}"#).unwrap();
+    assert_eq!(a, A { b: 42 });
+}
@@ -0,0 +1,124 @@
+use rson::{rson, Literal, Map, Number, Rson, RsonMap, Value};
+
+#[test]
+fn test_rson_literal() {
+    let actual = rson!({
+        "Id": 93638382,
+        "Name": "Devajit Asem",
+        "HasGPU": true,
+        "Got3080": null,
+        "Ratio": -1.5e3,
+        "GPUDetail": {
+            "RamType": "DDR6",
+        },
+        "Array": ["Devajit Asem", 12324, true, false, null],
+    });
+
+    let expected = Rson::from_reader(
+        r#"{
+        "Id": 93638382,
+        "Name": "Devajit Asem",
+        "HasGPU": true,
+        "Got3080": null,
+        "Ratio": 0,
+        "GPUDetail": {"RamType": "DDR6"},
+        "Array": ["Devajit Asem", 12324, true, false, null]
+    }"#
+        .as_bytes(),
+    );
+    let mut expected = match expected {
+        Value::Object(map) => map,
+        _ => unreachable!(),
+    };
+    expected.0.insert(
+        "Ratio".to_string(),
+        Value::Number(Number::new("-1.5e3".to_string())),
+    );
+
+    assert_eq!(actual, Value::Object(expected));
+}
+
+#[test]
+fn test_rson_interpolation() {
+    let id = 7u32;
+    let name = String::from("rson");
+    let tags = vec!["a", "b"];
+    let missing: Option<i64> = None;
+    let key = "computed";
+
+    let actual = rson!({
+        "id": id,
+        "name": name,
+        "tags": tags,
+        "missing": missing,
+        "sum": (1 + 2),
+        "nested": [id * 2, {"deep": (0.5f64)}],
+        (key): [],
+    });
+
+    let mut nested = Map::new();
+    nested.insert(
+        "deep".to_string(),
+        Value::Number(Number::new("0.5".to_string())),
+    );
+    let mut map = Map::new();
+    map.insert(
+        "id".to_string(),
+        Value::Number(Number::new("7".to_string())),
+    );
+    map.insert("name".to_string(), Value::String("rson".to_string()));
+    map.insert(
+        "tags".to_string(),
+        Value::Array(vec![
+            Value::String("a".to_string()),
+            Value::String("b".to_string()),
+        ]),
+    );
+    map.insert("missing".to_string(), Value::Literal(Literal::Null));
+    map.insert(
+        "sum".to_string(),
+        Value::Number(Number::new("3".to_string())),
+    );
+    map.insert(
+        "nested".to_string(),
+        Value::Array(vec![
+            Value::Number(Number::new("14".to_string())),
+            Value::Object(RsonMap(nested)),
+        ]),
+    );
+    map.insert("computed".to_string(), Value::Array(vec![]));
+
+    assert_eq!(actual, Value::Object(RsonMap(map)));
+}
+
+#[test]
+fn test_rson_spread() {
+    let defaults = rson!({"retries": 3, "verbose": false});
+    let actual = rson!({..defaults, "verbose": true}).unwrap();
+
+    assert_eq!(actual, rson!({"retries": 3, "verbose": true}));
+}
+
+#[test]
+fn test_rson_spread_non_object() {
+    let err = rson!({"a": [{..Value::from(1)}]}).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid type: number, expected an object to spread"
+    );
+
+    let mut extra = std::collections::BTreeMap::new();
+    extra.insert("b".to_string(), 2);
+    let nested = rson!({"a": [{..extra, "c": 3}]}).unwrap();
+    assert_eq!(nested, rson!({"a": [{"b": 2, "c": 3}]}));
+}
+
+/// `Value`'s key parameter has a default, which inference doesn't use;
+/// the macro names `String` so unannotated bindings still compile.
+#[test]
+fn test_rson_unannotated() {
+    let array = rson!([1]);
+    let null = rson!(null);
+    let number = rson!(-2.5);
+    assert_eq!(format!("{} {} {}", array, null, number), "[1] null -2.5");
+}
@@ -0,0 +1,137 @@
+// The types below only exist to have their schemas derived.
+#![allow(dead_code)]
+
+use rson::{rson, schema_for, RsonSchema, Value};
+use std::collections::HashMap;
+
+/// A user of the API.
+#[derive(RsonSchema)]
+struct User {
+    /// Unique id.
+    id: u64,
+    name: String,
+    /// Where the user lives,
+    /// if they told us.
+    address: Option<Address>,
+    tags: Vec<String>,
+    role: Role,
+}
+
+#[derive(RsonSchema)]
+struct Address {
+    city: String,
+    r#type: char,
+}
+
+/// What a user may do.
+#[derive(RsonSchema)]
+enum Role {
+    Admin,
+    Member,
+}
+
+#[test]
+fn test_struct_schema() {
+    let expected = rson!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "User",
+        "description": "A user of the API.",
+        "type": "object",
+        "properties": {
+            "id": {"type": "integer", "minimum": 0, "description": "Unique id."},
+            "name": {"type": "string"},
+            "address": {
+                "anyOf": [{"$ref": "#/definitions/Address"}, {"type": "null"}],
+                "description": "Where the user lives,\nif they told us.",
+            },
+            "tags": {"type": "array", "items": {"type": "string"}},
+            "role": {"$ref": "#/definitions/Role"},
+        },
+        "required": ["id", "name", "tags", "role"],
+        "definitions": {
+            "Address": {
+                "title": "Address",
+                "type": "object",
+                "properties": {
+                    "city": {"type": "string"},
+                    "type": {"type": "string", "minLength": 1, "maxLength": 1},
+                },
+                "required": ["city", "type"],
+            },
+            "Role": {
+                "title": "Role",
+                "description": "What a user may do.",
+                "type": "string",
+                "enum": ["Admin", "Member"],
+            },
+        },
+    });
+
+    assert_eq!(schema_for::<User>(), expected);
+}
+
+#[derive(RsonSchema)]
+struct Meters(f64);
+
+#[derive(RsonSchema)]
+struct Point(i32, i32);
+
+#[derive(RsonSchema)]
+struct Marker;
+
+#[derive(RsonSchema)]
+struct Page<T> {
+    items: Vec<T>,
+    extra: HashMap<String, Value>,
+}
+
+#[test]
+fn test_other_shapes() {
+    assert_eq!(
+        schema_for::<Meters>(),
+        rson!({"$schema": "http://json-schema.org/draft-07/schema#", "type": "number"})
+    );
+    assert_eq!(
+        schema_for::<Point>(),
+        rson!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "array",
+            "items": [{"type": "integer"}, {"type": "integer"}],
+            "minItems": 2,
+            "maxItems": 2,
+        })
+    );
+    assert_eq!(
+        schema_for::<Marker>(),
+        rson!({"$schema": "http://json-schema.org/draft-07/schema#", "type": "null"})
+    );
+    assert_eq!(
+        schema_for::<Page<bool>>(),
+        rson!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "title": "Page",
+            "type": "object",
+            "properties": {
+                "items": {"type": "array", "items": {"type": "boolean"}},
+                "extra": {"type": "object", "additionalProperties": {}},
+            },
+            "required": ["items", "extra"],
+        })
+    );
+}
+
+#[derive(RsonSchema)]
+struct Tree {
+    value: i64,
+    children: Vec<Tree>,
+}
+
+#[test]
+fn test_recursive_schema() {
+    let schema = schema_for::<Tree>();
+    assert_eq!(
+        schema["properties"]["children"],
+        rson!({"type": "array", "items": {"$ref": "#/definitions/Tree"}})
+    );
+    assert_eq!(schema["definitions"]["Tree"]["title"], rson!("Tree"));
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "serde")]
+
+use rson::{rson, to_string, Number, Rson, Value};
+
+const TEXT: &str = r#"{"name": "rson", "tags": ["json", "parser"], "stable": false, "next": null, "stars": 42, "ratio": 0.5}"#;
+
+#[test]
+fn test_round_trip_through_serde_json() {
+    let value = Rson::from_reader(TEXT.as_bytes());
+    let json = serde_json::Value::from(value.clone());
+    assert_eq!(
+        json,
+        serde_json::from_str::<serde_json::Value>(TEXT).unwrap()
+    );
+    assert_eq!(Value::from(json), value);
+}
+
+#[test]
+fn test_from_serde_json() {
+    let json = serde_json::json!({"a": [1, -2, 2.5, true, null, "x"]});
+    let value: Value = json.into();
+    assert_eq!(value, rson!({"a": [1, -2, 2.5, true, null, "x"]}));
+}
+
+#[test]
+fn test_integers_are_exact() {
+    for text in ["18446744073709551615", "-9223372036854775808", "0"] {
+        let json = serde_json::Value::from(Value::Number(Number::new(text.into())));
+        assert_eq!(json.to_string(), text);
+    }
+}
+
+#[test]
+fn test_other_numbers_go_through_f64() {
+    let convert = |text: &str| serde_json::Value::from(Value::Number(Number::new(text.into())));
+
+    assert_eq!(
+        convert("98765432109876543210").as_f64(),
+        Some(9.876543210987654e19)
+    );
+    assert_eq!(convert("1E3").to_string(), "1000.0");
+    assert_eq!(convert("+1.5").as_f64(), Some(1.5));
+    assert_eq!(convert("1e400"), serde_json::Value::Null);
+    assert_eq!(convert("NaN"), serde_json::Value::Null);
+
+    let value = Value::from(convert("1E3"));
+    assert_eq!(to_string(&value), "1000.0");
+}
@@ -0,0 +1,63 @@
+use rson::{to_canonical_string, to_string, Literal, Map, Number, Rson, RsonMap, Value};
+use std::collections::hash_map::DefaultHasher;
+
+#[test]
+fn test_to_string() {
+    let value = Value::Array(vec![
+        Value::String("quote \" and \\ and\nnewline\u{1}".to_string()),
+        Value::Number(Number::new("12324".to_string())),
+        Value::Literal(Literal::Bool(true)),
+        Value::Literal(Literal::Null),
+        Value::Object(RsonMap(Map::new())),
+    ]);
+
+    assert_eq!(
+        to_string(&value),
+        r#"["quote \" and \\ and\nnewline\u0001",12324,true,null,{}]"#
+    );
+}
+
+#[test]
+fn test_to_string_round_trip() {
+    let text =
+        r#"{"Name": "Devajit Asem", "Array": [1, false, null], "GPUDetail": {"RamType": "DDR6"}}"#;
+    let value = Rson::from_reader(text.as_bytes());
+    assert_eq!(Rson::from_reader(to_string(&value).as_bytes()), value);
+}
+
+#[test]
+fn test_canonical_string_sorts_keys() {
+    let text = r#"{"b": 1, "a": {"z": true, "y": [2, 1]}, "c": "x"}"#;
+    let value = Rson::from_reader(text.as_bytes());
+    assert_eq!(
+        to_canonical_string(&value),
+        r#"{"a":{"y":[2,1],"z":true},"b":1,"c":"x"}"#
+    );
+}
+
+#[test]
+fn test_canonical_hash_ignores_key_order() {
+    let a = Rson::from_reader(r#"{"id": 1, "tags": ["x", "y"], "ok": true}"#.as_bytes());
+    let b = Rson::from_reader(r#"{"ok": true, "tags": ["x", "y"], "id": 1}"#.as_bytes());
+    let c = Rson::from_reader(r#"{"ok": true, "tags": ["y", "x"], "id": 1}"#.as_bytes());
+
+    assert_eq!(
+        a.canonical_hash::<DefaultHasher>(),
+        b.canonical_hash::<DefaultHasher>()
+    );
+    assert_ne!(
+        a.canonical_hash::<DefaultHasher>(),
+        c.canonical_hash::<DefaultHasher>()
+    );
+}
+
+#[cfg(feature = "sha256")]
+#[test]
+fn test_sha256_hex() {
+    let value = Rson::from_reader(r#"{"b": 1, "a": 2}"#.as_bytes());
+    // sha256 of `{"a":2,"b":1}`
+    assert_eq!(
+        value.sha256_hex(),
+        "d3626ac30a87e6f7a6428233b3c68299976865fa5508e4267c5415c76af7a772"
+    );
+}
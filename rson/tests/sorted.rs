@@ -0,0 +1,29 @@
+use rson::*;
+
+#[test]
+fn test_entries_sorted() {
+    let value = rson!({"b": 2, "c": 3, "a": 1, "B": 0});
+
+    let keys: Vec<&str> = value
+        .entries_sorted()
+        .unwrap()
+        .map(|(k, _)| k.as_str())
+        .collect();
+    assert_eq!(keys, ["B", "a", "b", "c"]);
+    assert_eq!(value.entries_sorted().unwrap().len(), 4);
+    assert!(rson!([1, 2]).entries_sorted().is_none());
+}
+
+#[test]
+fn test_iter_sorted() {
+    let mut map = Map::new();
+    for key in ["z", "y", "x", "é", "10", "9"] {
+        map.insert(key.to_string(), Value::from(key));
+    }
+    let map = RsonMap(map);
+
+    let entries: Vec<(&String, &Value)> = map.iter_sorted().collect();
+    let keys: Vec<&str> = entries.iter().map(|(k, _)| k.as_str()).collect();
+    assert_eq!(keys, ["10", "9", "x", "y", "z", "é"]);
+    assert!(entries.iter().all(|(k, v)| Value::from(k.as_str()) == **v));
+}
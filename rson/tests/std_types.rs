@@ -0,0 +1,113 @@
+use rson::{from_value, rson, to_value, Deserialize, RsonSchema, Serialize};
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+use std::num::{NonZeroI32, NonZeroU16};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Serialize, Deserialize, RsonSchema, Debug, PartialEq)]
+struct Listener {
+    bind: SocketAddr,
+    peer: IpAddr,
+    mirror: Ipv6Addr,
+    workers: NonZeroU16,
+    offset: NonZeroI32,
+    idle: Duration,
+    #[rson(with = "rson::with::duration_secs")]
+    timeout: Duration,
+    #[rson(with = "rson::with::duration_millis")]
+    retry: Duration,
+    #[rson(with = "rson::with::system_time_secs")]
+    started: SystemTime,
+    #[rson(with = "rson::with::system_time_millis")]
+    updated: SystemTime,
+    stopped: SystemTime,
+}
+
+#[test]
+fn test_std_types_round_trip() {
+    let listener = Listener {
+        bind: "127.0.0.1:8080".parse().unwrap(),
+        peer: "10.0.0.1".parse().unwrap(),
+        mirror: Ipv6Addr::LOCALHOST,
+        workers: NonZeroU16::new(4).unwrap(),
+        offset: NonZeroI32::new(9).unwrap(),
+        idle: Duration::new(1, 5),
+        timeout: Duration::from_millis(1500),
+        retry: Duration::from_millis(250),
+        started: UNIX_EPOCH + Duration::from_secs(1_600_000_000),
+        updated: UNIX_EPOCH + Duration::from_millis(1_600_000_000_123),
+        stopped: UNIX_EPOCH + Duration::new(7, 8),
+    };
+
+    let value = to_value(&listener).unwrap();
+    assert_eq!(
+        value,
+        rson!({
+            "bind": "127.0.0.1:8080",
+            "peer": "10.0.0.1",
+            "mirror": "::1",
+            "workers": 4,
+            "offset": 9,
+            "idle": {"secs": 1, "nanos": 5},
+            "timeout": 1.5,
+            "retry": 250,
+            "started": 1600000000,
+            "updated": 1600000000123,
+            "stopped": {"secs_since_epoch": 7, "nanos_since_epoch": 8},
+        })
+    );
+    assert_eq!(from_value::<Listener>(value), Ok(listener));
+}
+
+#[test]
+fn test_duration_secs_forms() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Timeout {
+        #[rson(with = "rson::with::duration_secs")]
+        after: Duration,
+    }
+
+    let parse = |value| from_value::<Timeout>(value).map(|t| t.after);
+    assert_eq!(parse(rson!({"after": 2})), Ok(Duration::from_secs(2)));
+    assert_eq!(
+        parse(rson!({"after": 0.0000000019})),
+        Ok(Duration::from_nanos(1))
+    );
+    assert_eq!(
+        parse(rson!({"after": 2.5e-1})),
+        Ok(Duration::from_millis(250))
+    );
+    assert_eq!(
+        parse(rson!({"after": -1})).unwrap_err().to_string(),
+        "invalid value: number `-1`, expected a non-negative number of seconds"
+    );
+    assert_eq!(
+        parse(rson!({})).unwrap_err().to_string(),
+        "missing field `after`"
+    );
+}
+
+#[test]
+fn test_std_type_errors() {
+    assert_eq!(
+        from_value::<IpAddr>(rson!("localhost"))
+            .unwrap_err()
+            .to_string(),
+        "invalid value: string \"localhost\", expected an IP address"
+    );
+    assert_eq!(
+        from_value::<NonZeroU16>(rson!(0)).unwrap_err().to_string(),
+        "invalid value: number `0`, expected a nonzero integer"
+    );
+    assert_eq!(
+        from_value::<Duration>(rson!({"secs": 1, "nanos": 1000000000}))
+            .unwrap_err()
+            .to_string(),
+        "invalid value: number `1000000000`, expected less than a second of nanoseconds"
+    );
+    assert_eq!(
+        from_value::<Duration>(rson!({"secs": 1}))
+            .unwrap_err()
+            .to_string(),
+        "missing field `nanos`"
+    );
+}
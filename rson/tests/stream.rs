@@ -0,0 +1,141 @@
+use rson::*;
+
+type Calls = fn(&mut JsonWriter<Vec<u8>>) -> Result<()>;
+
+fn written(f: impl FnOnce(&mut JsonWriter<Vec<u8>>) -> Result<()>) -> String {
+    let mut w = JsonWriter::new(Vec::new());
+    f(&mut w).unwrap();
+    String::from_utf8(w.finish().unwrap()).unwrap()
+}
+
+#[test]
+fn test_chained_calls() {
+    let text = written(|w| {
+        w.begin_object()?.key("items")?.begin_array()?;
+        for i in 0..3 {
+            w.value(&i.into())?;
+        }
+        w.end_array()?.key("next")?.string("a \"quoted\"\n")?;
+        w.key("empty")?.begin_object()?.end_object()?.end_object()?;
+        Ok(())
+    });
+
+    assert_eq!(
+        text,
+        r#"{"items":[0,1,2],"next":"a \"quoted\"\n","empty":{}}"#
+    );
+    assert_eq!(
+        Rson::from_reader(text.as_bytes())
+            .expect_array("/items", 3)
+            .unwrap()[2],
+        Value::from(2)
+    );
+}
+
+#[test]
+fn test_scoped_writers() {
+    let text = written(|w| {
+        let mut root = w.object()?;
+        root.entry("id", &7.into())?.string("name", "x")?;
+        let mut rows = root.array("rows")?;
+        for i in 0..2 {
+            let mut row = rows.object()?;
+            row.entry("i", &i.into())?;
+            row.array("tags")?.end()?;
+            row.end()?;
+        }
+        rows.string("last")?.element(&rson!({"a": null}))?;
+        rows.end()?;
+        root.end()
+    });
+
+    assert_eq!(
+        text,
+        r#"{"id":7,"name":"x","rows":[{"i":0,"tags":[]},{"i":1,"tags":[]},"last",{"a":null}]}"#
+    );
+}
+
+#[test]
+fn test_top_level_scalar() {
+    assert_eq!(written(|w| w.value(&true.into()).map(drop)), "true");
+}
+
+#[test]
+fn test_malformed_calls() {
+    let cases: Vec<(Calls, &str)> = vec![
+        (
+            |w| w.begin_object()?.string("v").map(drop),
+            "a value in an object needs a key first",
+        ),
+        (
+            |w| w.begin_array()?.key("k").map(drop),
+            "key `k` inside an array",
+        ),
+        (|w| w.key("k").map(drop), "key `k` outside an object"),
+        (
+            |w| w.begin_object()?.key("a")?.key("b").map(drop),
+            "key `b` after a key without a value",
+        ),
+        (
+            |w| w.begin_object()?.key("a")?.end_object().map(drop),
+            "end_object() after a key without a value",
+        ),
+        (
+            |w| w.begin_object()?.end_array().map(drop),
+            "end_array() while an object is open",
+        ),
+        (
+            |w| w.begin_array()?.end_object().map(drop),
+            "end_object() while an array is open",
+        ),
+        (
+            |w| w.end_array().map(drop),
+            "end_array() without begin_array()",
+        ),
+        (
+            |w| w.value(&1.into())?.value(&2.into()).map(drop),
+            "a document has only one top-level value",
+        ),
+    ];
+    for (f, message) in cases {
+        let mut w = JsonWriter::new(Vec::new());
+        assert_eq!(f(&mut w).unwrap_err().to_string(), message);
+    }
+}
+
+#[test]
+fn test_errors_write_nothing() {
+    let mut w = JsonWriter::new(Vec::new());
+    w.begin_object().unwrap();
+    assert!(w.string("v").is_err());
+    w.key("k")
+        .unwrap()
+        .string("v")
+        .unwrap()
+        .end_object()
+        .unwrap();
+
+    assert_eq!(w.finish().unwrap(), br#"{"k":"v"}"#);
+}
+
+#[test]
+fn test_finish_incomplete() {
+    let w = JsonWriter::new(Vec::new());
+    assert_eq!(w.finish().unwrap_err().to_string(), "no value was written");
+
+    let mut w = JsonWriter::new(Vec::new());
+    w.begin_array().unwrap().begin_object().unwrap();
+    assert_eq!(w.finish().unwrap_err().to_string(), "unclosed object");
+}
+
+#[test]
+fn test_child_dropped_without_end() {
+    let mut w = JsonWriter::new(Vec::new());
+    let mut root = w.object().unwrap();
+    let _ = root.array("rows").unwrap();
+
+    assert_eq!(
+        root.string("k", "v").err().unwrap().to_string(),
+        "a nested object or array was not ended"
+    );
+}
@@ -0,0 +1,120 @@
+use rson::{rson, ErrorKind, Number, ParseOptions, Rson, Value};
+
+fn violations(text: &str) -> Vec<(ErrorKind, usize, usize, String)> {
+    Rson::strict_violations(text.as_bytes())
+        .unwrap()
+        .into_iter()
+        .map(|e| (e.kind(), e.line(), e.column(), e.to_string()))
+        .collect()
+}
+
+#[test]
+fn test_strict_input_has_no_violations() {
+    let text = "{\"a\": [1, -2.5e3, \"x\"],\r\n\t\"b\": {\"c\": null}}";
+    assert_eq!(violations(text), vec![]);
+    assert!(Rson::try_from_reader_with(text.as_bytes(), ParseOptions::strict()).is_ok());
+}
+
+#[test]
+fn test_strict_violations() {
+    let text = "{\n  \"a\": [1 2,],\n  \"b\": +1,\n  \"c\": \"tab\there\",\n\u{a0} \"d\": 007\n}";
+    let found = violations(text);
+    let positions: Vec<_> = found.iter().map(|v| (v.0, v.1, v.2)).collect();
+    assert_eq!(
+        positions,
+        vec![
+            (ErrorKind::UnexpectedToken, 2, 11),
+            (ErrorKind::UnexpectedToken, 2, 13),
+            (ErrorKind::InvalidNumber, 3, 8),
+            (ErrorKind::UnexpectedToken, 4, 12),
+            (ErrorKind::UnexpectedToken, 5, 1),
+            (ErrorKind::InvalidNumber, 5, 9),
+        ]
+    );
+    assert!(found[0].3.starts_with("expected `,` or `]`"));
+    assert!(found[1].3.starts_with("trailing comma"));
+    assert!(found[4].3.starts_with("'\\u{a0}' is not JSON whitespace"));
+
+    // Each is where a strict parse stops.
+    let err = Rson::try_from_reader_with(text.as_bytes(), ParseOptions::strict()).unwrap_err();
+    assert_eq!((err.kind(), err.line(), err.column()), (found[0].0, 2, 11));
+}
+
+#[test]
+fn test_strict_violations_relaxed_numbers() {
+    let found = violations("[.5, 1., 0123]");
+    let positions: Vec<_> = found.iter().map(|v| (v.0, v.1, v.2)).collect();
+    assert_eq!(
+        positions,
+        vec![
+            (ErrorKind::InvalidNumber, 1, 2),
+            (ErrorKind::InvalidNumber, 1, 8),
+            (ErrorKind::InvalidNumber, 1, 11),
+        ]
+    );
+}
+
+#[test]
+fn test_strict_violations_duplicate_keys() {
+    let text = "{\"a\": 1, \"b\": {\"a\": 2}, \"a\": 3}";
+    let found = violations(text);
+    assert_eq!(
+        found,
+        vec![(
+            ErrorKind::DuplicateKey,
+            1,
+            25,
+            "duplicate key `a` at line 1 column 25".to_string()
+        )]
+    );
+    let err = Rson::try_from_reader_with(text.as_bytes(), ParseOptions::strict()).unwrap_err();
+    assert_eq!((err.kind(), err.line(), err.column()), (found[0].0, 1, 25));
+}
+
+#[test]
+fn test_strict_violations_invalid_input() {
+    let err = Rson::strict_violations("[1, {]".as_bytes()).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UnexpectedToken);
+}
+
+#[test]
+fn test_validate_strict() {
+    let relaxed = ParseOptions {
+        relaxed_numbers: true,
+        ..ParseOptions::default()
+    };
+    let value = Rson::try_from_reader_with("[+1, .5, 007]".as_bytes(), relaxed).unwrap();
+    assert!(value.validate_strict().is_ok());
+    assert!(rson!({"a": [1, 2.5, -0.0], "b": "x"})
+        .validate_strict()
+        .is_ok());
+
+    for text in ["0", "-0", "10", "1.5", "-1.5e3", "1E+2", "0.0e-1"] {
+        let value = Value::Number(Number::new(text.to_string()));
+        assert!(value.validate_strict().is_ok(), "{}", text);
+    }
+    for text in [
+        "", "-", "+1", "01", ".5", "1.", "1e", "1e+", "0x1F", "1.5.2", "--1",
+    ] {
+        let value = Value::Number(Number::new(text.to_string()));
+        assert!(value.validate_strict().is_err(), "{:?}", text);
+    }
+
+    let mut value = rson!({"a": [1, 2], "b/c": 3});
+    if let Some(n) = value.pointer_mut("/a/1") {
+        *n = Value::Number(Number::new("NaN".to_string()));
+    }
+    assert_eq!(
+        value.validate_strict().unwrap_err().to_string(),
+        "at `/a/1`: invalid value: number `NaN`, expected an RFC 8259 number"
+    );
+
+    let mut value = rson!({"a": [1, 2], "b/c": 3});
+    if let Some(n) = value.pointer_mut("/b~1c") {
+        *n = Value::Number(Number::new("1 ".to_string()));
+    }
+    assert_eq!(
+        value.validate_strict().unwrap_err().to_string(),
+        "at `/b~1c`: invalid value: number `1 `, expected an RFC 8259 number"
+    );
+}
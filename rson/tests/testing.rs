@@ -0,0 +1,165 @@
+use rson::testing::{diff, generate, CompareMode, Difference, GeneratorConfig, TypeWeights};
+use rson::{assert_json_eq, assert_json_include, rson, to_string, Rson, Value};
+
+#[test]
+fn test_assert_json_eq() {
+    let actual = Rson::from_reader(r#"{"b": [1, 2], "a": {"c": null}}"#.as_bytes());
+    assert_json_eq!(actual, rson!({"a": {"c": null}, "b": [1, 2.0]}));
+    assert_json_eq!(actual, rson!({"a": {"c": null}, "b": [1e0, 2]}));
+}
+
+#[test]
+fn test_diff_large_numbers() {
+    assert_eq!(
+        diff(
+            &rson!({"id": 12345678901234567890}),
+            &rson!({"id": 12345678901234567891}),
+            CompareMode::Strict
+        )
+        .len(),
+        1
+    );
+    assert!(diff(
+        &rson!([12345678901234567890, 0.5, -0]),
+        &rson!([1.2345678901234567890e19, 50e-2, 0]),
+        CompareMode::Strict
+    )
+    .is_empty());
+}
+
+#[test]
+fn test_assert_json_include() {
+    let actual = rson!({"id": 7, "user": {"name": "asem", "roles": ["admin", "dev"]}});
+    assert_json_include!(actual: actual, expected: rson!({"user": {"roles": ["admin"]}}));
+    assert_json_include!(expected: rson!({}), actual: actual);
+}
+
+#[test]
+#[should_panic(expected = "JSON values are not equal:
+    at $.a: expected 2, found 1
+    at $.extra: unexpected true")]
+fn test_assert_json_eq_message() {
+    assert_json_eq!(rson!({"a": 1, "extra": true}), rson!({"a": 2}));
+}
+
+#[test]
+fn test_diff_paths() {
+    let actual = rson!({"list": [1], "weird key": "x"});
+    let expected = rson!({"list": [1, {"deep": false}], "weird key": "y"});
+
+    assert_eq!(
+        diff(&actual, &expected, CompareMode::Strict),
+        vec![
+            Difference {
+                path: "$.list[1]".to_string(),
+                actual: None,
+                expected: Some(rson!({"deep": false})),
+            },
+            Difference {
+                path: r#"$["weird key"]"#.to_string(),
+                actual: Some(rson!("x")),
+                expected: Some(rson!("y")),
+            },
+        ]
+    );
+    assert_eq!(
+        diff(
+            &actual,
+            &rson!({"list": [1, 2], "other": 3}),
+            CompareMode::Include
+        )
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>(),
+        vec![
+            "at $.list[1]: missing, expected 2",
+            "at $.other: missing, expected 3"
+        ]
+    );
+}
+
+/// Depth of the deepest array or object, 0 for scalars.
+fn depth(value: &Value) -> usize {
+    match value {
+        Value::Array(array) => 1 + array.iter().map(depth).max().unwrap_or(0),
+        Value::Object(map) => 1 + map.0.values().map(depth).max().unwrap_or(0),
+        _ => 0,
+    }
+}
+
+#[test]
+fn test_generate_is_reproducible() {
+    let config = GeneratorConfig::default();
+    assert_eq!(generate(42, &config), generate(42, &config));
+    assert_ne!(
+        to_string(&generate(42, &config)),
+        to_string(&generate(43, &config))
+    );
+
+    for seed in 0..200 {
+        let value = generate(seed, &config);
+        assert_eq!(
+            Rson::try_from_reader(to_string(&value).as_bytes()).unwrap(),
+            value
+        );
+    }
+}
+
+#[test]
+fn test_generate_shape() {
+    let config = GeneratorConfig {
+        max_depth: 3,
+        fan_out: 2..=3,
+        string_len: 5..=5,
+        weights: TypeWeights {
+            null: 0,
+            bool: 0,
+            number: 0,
+            string: 1,
+            array: 1,
+            object: 0,
+        },
+    };
+    for seed in 0..50 {
+        let value = generate(seed, &config);
+        assert!(depth(&value) <= 3);
+        check_shape(&value);
+    }
+
+    fn check_shape(value: &Value) {
+        match value {
+            Value::Array(array) => {
+                assert!((2..=3).contains(&array.len()));
+                array.iter().for_each(check_shape);
+            }
+            Value::String(string) => assert_eq!(string.chars().count(), 5),
+            other => panic!("unexpected {}", to_string(other)),
+        }
+    }
+}
+
+#[test]
+fn test_generate_nesting() {
+    // Only containers until the maximum depth, then nulls.
+    let config = GeneratorConfig {
+        max_depth: 5,
+        fan_out: 1..=1,
+        weights: TypeWeights {
+            null: 0,
+            bool: 0,
+            number: 0,
+            string: 0,
+            array: 1,
+            object: 1,
+        },
+        ..GeneratorConfig::default()
+    };
+    let value = generate(7, &config);
+    assert_eq!(depth(&value), 5);
+
+    let scalars = GeneratorConfig {
+        max_depth: 0,
+        ..config
+    };
+    assert_eq!(generate(7, &scalars), rson!(null));
+}
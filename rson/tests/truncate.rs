@@ -0,0 +1,52 @@
+use rson::rson;
+
+#[test]
+fn test_truncate_strings_and_arrays() {
+    let value = rson!({
+        "name": "Devajit Asem",
+        "ids": [1, 2, 3, 4, 5],
+        "short": [true],
+    });
+
+    assert_eq!(
+        value.truncate_for_log(4, 3, 8),
+        rson!({
+            "name": "Deva… (12 chars)",
+            "ids": [1, 2, 3, "… 2 more items"],
+            "shor… (5 chars)": [true],
+        })
+    );
+}
+
+#[test]
+fn test_truncate_objects_and_depth() {
+    let value = rson!({
+        "c": 3,
+        "a": {"deep": [1, 2], "nested": {"x": 1}},
+        "b": [[1], {}],
+    });
+
+    assert_eq!(
+        value.truncate_for_log(100, 2, 2),
+        rson!({
+            "a": {"deep": "[… 2 items]", "nested": "{… 1 member}"},
+            "b": ["[… 1 item]", "{… 0 members}"],
+            "…": "1 more member",
+        })
+    );
+    assert_eq!(
+        value.truncate_for_log(100, 100, 1),
+        rson!({"a": "{… 2 members}", "b": "[… 2 items]", "c": 3})
+    );
+    assert_eq!(value.truncate_for_log(100, 100, 0), rson!("{… 3 members}"));
+}
+
+#[test]
+fn test_truncate_multibyte() {
+    let value = rson!(["añbç", "ok"]);
+
+    assert_eq!(
+        value.truncate_for_log(2, 10, 10),
+        rson!(["añ… (4 chars)", "ok"])
+    );
+}
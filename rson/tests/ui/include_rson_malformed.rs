@@ -0,0 +1,7 @@
+use rson::include_rson;
+
+fn main() {
+    // trybuild builds this in `target/tests/trybuild/rson`, which is where
+    // the path is relative to.
+    let _ = include_rson!("../../../../rson/tests/ui/malformed.json");
+}
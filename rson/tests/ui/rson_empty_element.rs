@@ -0,0 +1,7 @@
+use rson::rson;
+
+fn main() {
+    let _ = rson!([1,, 2]);
+    let _ = rson!({"a": 1, , "b": 2});
+    let _ = rson!([,]);
+}
@@ -0,0 +1,7 @@
+use rson::rson;
+
+fn main() {
+    let _ = rson!({"mask": 0x1f});
+    let _ = rson!([1, 2u8]);
+    let _ = rson!({"ok": 'y'});
+}
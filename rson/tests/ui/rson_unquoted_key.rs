@@ -0,0 +1,5 @@
+use rson::rson;
+
+fn main() {
+    let _ = rson!({"id": 1, name: "rson"});
+}
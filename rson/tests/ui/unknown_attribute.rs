@@ -0,0 +1,9 @@
+use rson::Deserialize;
+
+#[derive(Deserialize)]
+struct Config {
+    #[rson(renamed = "n")]
+    name: String,
+}
+
+fn main() {}
@@ -0,0 +1,99 @@
+use rson::{from_urlencoded, to_urlencoded, Literal, Map, Number, RsonMap, Value};
+
+fn string(s: &str) -> Value {
+    Value::String(s.to_string())
+}
+
+#[test]
+fn test_from_urlencoded_nesting() {
+    let actual = from_urlencoded("a=1&b[0]=x&b[1]=y&c[d][]=p&c[d][]=q&e[f]=hello+world%21");
+
+    let mut c = Map::new();
+    c.insert(
+        "d".to_string(),
+        Value::Array(vec![string("p"), string("q")]),
+    );
+    let mut e = Map::new();
+    e.insert("f".to_string(), string("hello world!"));
+
+    let mut map = Map::new();
+    map.insert("a".to_string(), string("1"));
+    map.insert(
+        "b".to_string(),
+        Value::Array(vec![string("x"), string("y")]),
+    );
+    map.insert("c".to_string(), Value::Object(RsonMap(c)));
+    map.insert("e".to_string(), Value::Object(RsonMap(e)));
+
+    assert_eq!(actual, Value::Object(RsonMap(map)));
+}
+
+#[test]
+fn test_from_urlencoded_edge_cases() {
+    // Sparse indices are padded, repeated keys keep the last value and
+    // unbalanced brackets are part of the name.
+    let actual = from_urlencoded("s[2]=z&k=1&k=2&flag&&x[y=3&%5Bq%5D=4");
+
+    let mut map = Map::new();
+    map.insert(
+        "s".to_string(),
+        Value::Array(vec![
+            Value::Literal(Literal::Null),
+            Value::Literal(Literal::Null),
+            string("z"),
+        ]),
+    );
+    map.insert("k".to_string(), string("2"));
+    map.insert("flag".to_string(), string(""));
+    map.insert("x[y".to_string(), string("3"));
+    map.insert("[q]".to_string(), string("4"));
+
+    assert_eq!(actual, Value::Object(RsonMap(map)));
+}
+
+#[test]
+fn test_from_urlencoded_index_limit() {
+    let value = from_urlencoded("a[1000]=x");
+    assert_eq!(value.expect_array("/a", 0).unwrap().len(), 1001);
+
+    // Each index is within 1000 of the last, but the padding is capped:
+    // past `a[1000]` the array becomes an object keyed by index.
+    let stepped: Vec<String> = (1..=200).map(|i| format!("a[{}]=1", i * 1000)).collect();
+    let value = from_urlencoded(&stepped.join("&"));
+    match &value["a"] {
+        Value::Object(map) => {
+            assert_eq!(map.0.len(), 1001 + 199);
+            assert_eq!(map.0["200000"], string("1"));
+        }
+        other => panic!("expected an object, got {}", other),
+    }
+}
+
+#[test]
+fn test_to_urlencoded() {
+    let mut inner = Map::new();
+    inner.insert("name".to_string(), string("Devajit Asem"));
+    inner.insert("gpu".to_string(), Value::Literal(Literal::Null));
+
+    let mut map = Map::new();
+    map.insert("user".to_string(), Value::Object(RsonMap(inner)));
+    map.insert(
+        "ids".to_string(),
+        Value::Array(vec![
+            Value::Number(Number::new("1".to_string())),
+            Value::Literal(Literal::Bool(true)),
+        ]),
+    );
+
+    let encoded = to_urlencoded(&Value::Object(RsonMap(map)));
+    assert_eq!(
+        encoded,
+        "ids[0]=1&ids[1]=true&user[gpu]=&user[name]=Devajit+Asem"
+    );
+}
+
+#[test]
+fn test_urlencoded_round_trip() {
+    let query = "a=1&b[0]=x&b[1]=y+z&c[d]=%26%3D";
+    assert_eq!(to_urlencoded(&from_urlencoded(query)), query);
+}
@@ -0,0 +1,96 @@
+use rson::{validate_all, Diagnostic, ErrorKind, Severity, Suggestion};
+
+fn check(text: &str) -> Vec<(Severity, ErrorKind, usize, usize)> {
+    validate_all(text.as_bytes())
+        .iter()
+        .map(|d| (d.severity, d.error.kind(), d.error.line(), d.error.column()))
+        .collect()
+}
+
+#[test]
+fn test_valid_document() {
+    assert_eq!(check(r#"{"a": [1, 2.5, "x"], "b": {"c": null}}"#), vec![]);
+}
+
+#[test]
+fn test_reports_every_error() {
+    let text = "{\n  \"name\": 'app',\n  port: 8080,\n  \"debug\": True,\n  \"tags\": [\"a\", \"b\\q\", c],\n  \"ok\": 1\n}";
+    let diagnostics = validate_all(text.as_bytes());
+    let found: Vec<_> = diagnostics
+        .iter()
+        .map(|d| (d.severity, d.error.line(), d.error.suggestion()))
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            (Severity::Error, 2, Some(Suggestion::UseDoubleQuotes)),
+            (Severity::Error, 3, Some(Suggestion::QuoteKey)),
+            (Severity::Error, 4, Some(Suggestion::UseLiteral("true"))),
+            (Severity::Error, 5, None),
+            (Severity::Error, 5, None),
+        ]
+    );
+    assert_eq!(diagnostics[3].error.kind(), ErrorKind::InvalidEscape);
+    assert_eq!(diagnostics[4].error.column(), 24);
+}
+
+#[test]
+fn test_warnings_and_errors() {
+    assert_eq!(
+        check("[1, 2,, 007 tru]"),
+        vec![
+            (Severity::Error, ErrorKind::UnexpectedToken, 1, 7),
+            (Severity::Warning, ErrorKind::InvalidNumber, 1, 10),
+            (Severity::Warning, ErrorKind::UnexpectedToken, 1, 13),
+            (Severity::Error, ErrorKind::UnexpectedToken, 1, 13),
+        ]
+    );
+}
+
+#[test]
+fn test_duplicate_keys_warn() {
+    assert_eq!(
+        check(r#"{"a": 1, "a": 2, "b": [{"c": 1, "c": x}]}"#),
+        vec![
+            (Severity::Warning, ErrorKind::DuplicateKey, 1, 10),
+            (Severity::Error, ErrorKind::UnexpectedToken, 1, 38),
+        ]
+    );
+}
+
+#[test]
+fn test_nested_errors() {
+    assert_eq!(
+        check(r#"{"a": [1, {"b": x}, 3], "c": [}, "d": 1}"#),
+        vec![
+            (Severity::Error, ErrorKind::UnexpectedToken, 1, 17),
+            (Severity::Error, ErrorKind::UnexpectedToken, 1, 31),
+            // The stray `}` closes the outer object.
+            (Severity::Error, ErrorKind::TrailingCharacters, 1, 32),
+        ]
+    );
+}
+
+#[test]
+fn test_unrecoverable_errors_end_checking() {
+    assert_eq!(
+        check(r#"[1, x, "open"#),
+        vec![
+            (Severity::Error, ErrorKind::UnexpectedToken, 1, 5),
+            (Severity::Error, ErrorKind::UnexpectedEof, 1, 13),
+        ]
+    );
+    assert_eq!(
+        check("[1] 2"),
+        vec![(Severity::Error, ErrorKind::TrailingCharacters, 1, 5)]
+    );
+}
+
+#[test]
+fn test_diagnostic_display() {
+    let diagnostics: Vec<Diagnostic> = validate_all("[1,]".as_bytes());
+    assert_eq!(
+        diagnostics[0].to_string(),
+        "warning: trailing comma at line 1 column 4"
+    );
+}
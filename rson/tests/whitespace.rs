@@ -0,0 +1,65 @@
+use rson::*;
+use std::fs::File;
+
+fn open_file(filename: &str) -> File {
+    let mut path = std::env::current_dir().unwrap();
+    path.push("data/");
+    path.push(filename);
+
+    File::open(path).unwrap()
+}
+
+#[test]
+fn test_crlf_fixture() {
+    let json = Rson::from_reader(open_file("crlf.json"));
+
+    assert_eq!(
+        json.pointer("/name").unwrap(),
+        &Value::String("windows".to_string())
+    );
+    assert_eq!(json.expect_array("/lines", 0).unwrap().len(), 2);
+    assert_eq!(
+        json.pointer("/ok").unwrap(),
+        &Value::Literal(Literal::Bool(true))
+    );
+}
+
+#[test]
+fn test_crlf_fixture_strict() {
+    let json = Rson::try_from_reader_with(open_file("crlf.json"), ParseOptions::strict()).unwrap();
+
+    assert_eq!(json, Rson::from_reader(open_file("crlf.json")));
+}
+
+#[test]
+fn test_crlf_position() {
+    let err = Rson::try_from_reader("[\r\n  1,\r\n  x\r\n]".as_bytes()).unwrap_err();
+
+    assert_eq!(err.kind(), ErrorKind::UnexpectedToken);
+    assert_eq!((err.line(), err.column()), (3, 3));
+}
+
+#[test]
+fn test_lone_cr() {
+    let json = Rson::try_from_reader("\r[1,\r2]\r".as_bytes()).unwrap();
+
+    assert_eq!(json.expect_array("", 0).unwrap().len(), 2);
+}
+
+#[test]
+fn test_strict_rejects_other_whitespace() {
+    for ws in ["\u{c}", "\u{a0}", "\u{2028}", "\u{feff}"] {
+        let input = format!("{}[1,{}2]", ws, ws);
+        let err = Rson::try_from_reader_with(input.as_bytes(), ParseOptions::strict()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedToken, "{:?}", ws);
+    }
+}
+
+#[test]
+fn test_lenient_accepts_other_whitespace() {
+    for ws in ["\u{c}", "\u{a0}", "\u{2028}", "\u{feff}"] {
+        let input = format!("{}[1,{}2]{}", ws, ws, ws);
+        let json = Rson::try_from_reader(input.as_bytes()).unwrap();
+        assert_eq!(json.expect_array("", 0).unwrap().len(), 2, "{:?}", ws);
+    }
+}
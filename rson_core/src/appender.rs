@@ -0,0 +1,179 @@
+use crate::rson::Rson;
+use crate::value::Value;
+use crate::write::to_writer;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+/// Keeps a JSON array in a file and appends records to it in place, so the
+/// file is valid JSON between any two appends without ever being
+/// rewritten:
+///
+///     let mut log = ArrayFileAppender::open("events.json")?;
+///     log.append(&event)?;
+///
+/// Each record goes on its own line, after a `,` for all but the first,
+/// and the closing `]` on the last line. An append truncates that line and
+/// writes the record and a new `]` in its place. If a process dies partway
+/// through an append, `open` drops the unfinished line and closes the
+/// array again, keeping every complete record.
+pub struct ArrayFileAppender {
+    file: File,
+    /// Offset of the line holding the closing `]`.
+    end: u64,
+    empty: bool,
+}
+
+impl ArrayFileAppender {
+    /// Opens the array file at `path`, creating it as `[]` if it doesn't
+    /// exist or is empty. A file written by something else must hold a
+    /// JSON array.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        let len = file.metadata()?.len();
+
+        let end = match find_back(&mut file, len, |b| !is_white(b))? {
+            None => None,
+            Some((last, b']')) => match find_back(&mut file, last, |b| b != b' ' && b != b'\t')? {
+                Some((newline, b'\n')) => Some(newline + 1),
+                _ if parses(&mut file)? => Some(last),
+                _ => recover(&mut file, len)?,
+            },
+            Some(_) => recover(&mut file, len)?,
+        };
+
+        let mut appender = Self {
+            file,
+            end: 0,
+            empty: true,
+        };
+        match end {
+            Some(end) => {
+                check_array(&mut appender.file)?;
+                appender.end = end;
+                appender.empty = matches!(
+                    find_back(&mut appender.file, end, |b| !is_white(b))?,
+                    Some((_, b'['))
+                );
+            }
+            None => {
+                appender.file.set_len(0)?;
+                appender.file.seek(SeekFrom::Start(0))?;
+                appender.file.write_all(b"[\n")?;
+                appender.end = 2;
+            }
+        }
+        appender.write_at_end(b"]\n")?;
+        Ok(appender)
+    }
+
+    /// Appends `record` as the last element of the array.
+    pub fn append(&mut self, record: &Value) -> io::Result<()> {
+        let mut line = Vec::new();
+        if !self.empty {
+            line.push(b',');
+        }
+        to_writer(&mut line, record)?;
+        line.push(b'\n');
+
+        let end = self.end + line.len() as u64;
+        line.extend_from_slice(b"]\n");
+        self.write_at_end(&line)?;
+        self.end = end;
+        self.empty = false;
+        Ok(())
+    }
+
+    /// Waits for the appended records to reach the disk.
+    pub fn sync(&self) -> io::Result<()> {
+        self.file.sync_data()
+    }
+
+    /// Replaces everything from `end` on with `bytes`. Truncating first
+    /// means a write cut short never leaves old bytes behind it.
+    fn write_at_end(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.file.set_len(self.end)?;
+        self.file.seek(SeekFrom::Start(self.end))?;
+        self.file.write_all(bytes)
+    }
+}
+
+/// Finds the end of the complete lines of a file cut off mid-append.
+/// Records never contain line breaks, so a line is complete once it ends
+/// with one. What is kept must be an array missing its `]`, or the file
+/// wasn't written by `ArrayFileAppender` and isn't touched.
+fn recover(file: &mut File, len: u64) -> io::Result<Option<u64>> {
+    match find_back(file, len, |b| b == b'\n')? {
+        Some((newline, _)) if open_array(file, newline + 1)? => Ok(Some(newline + 1)),
+        Some(_) => Err(invalid("the file doesn't hold a JSON array")),
+        // Only the `[` of a new file can be cut off on the first line.
+        None if matches!(find_back(file, len, |b| !is_white(b))?, Some((_, b'['))) => Ok(None),
+        None => Err(invalid("the file doesn't hold a JSON array")),
+    }
+}
+
+/// Whether the whole file is JSON, for arrays not written by
+/// `ArrayFileAppender` whose `]` isn't on a line of its own.
+fn parses(file: &mut File) -> io::Result<bool> {
+    file.seek(SeekFrom::Start(0))?;
+    Ok(Rson::try_from_reader_unrecorded(&mut *file).is_ok())
+}
+
+/// Whether the first `end` bytes of the file are a JSON array once a `]`
+/// is added.
+fn open_array(file: &mut File, end: u64) -> io::Result<bool> {
+    file.seek(SeekFrom::Start(0))?;
+    let prefix = (&mut *file).take(end).chain(&b"]"[..]);
+    Ok(matches!(
+        Rson::try_from_reader_unrecorded(prefix),
+        Ok(Value::Array(_))
+    ))
+}
+
+/// Refuses to append to a file that holds something other than an array.
+fn check_array(file: &mut File) -> io::Result<()> {
+    file.seek(SeekFrom::Start(0))?;
+    for byte in io::BufReader::new(&mut *file).bytes() {
+        match byte? {
+            b if is_white(b) => continue,
+            b'[' => return Ok(()),
+            _ => break,
+        }
+    }
+    Err(invalid("the file doesn't hold a JSON array"))
+}
+
+/// The offset and value of the last byte before `before` matching `pred`,
+/// reading the file backwards.
+fn find_back(
+    file: &mut File,
+    before: u64,
+    pred: impl Fn(u8) -> bool,
+) -> io::Result<Option<(u64, u8)>> {
+    let mut buf = [0; 4096];
+    let mut end = before;
+    while end > 0 {
+        let start = end.saturating_sub(buf.len() as u64);
+        let chunk = &mut buf[..(end - start) as usize];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(chunk)?;
+        if let Some(i) = chunk.iter().rposition(|&b| pred(b)) {
+            return Ok(Some((start + i as u64, chunk[i])));
+        }
+        end = start;
+    }
+    Ok(None)
+}
+
+fn is_white(b: u8) -> bool {
+    matches!(b, b' ' | b'\t' | b'\n' | b'\r')
+}
+
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
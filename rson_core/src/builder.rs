@@ -0,0 +1,89 @@
+use crate::value::{Map, RsonMap, Value};
+
+impl Value {
+    /// Starts building an object, for code that can't use `rson!`, e.g.
+    /// because its keys are only known at runtime:
+    ///
+    ///     let value = Value::object()
+    ///         .field("name", "x")
+    ///         .field("tags", Value::array().push(1).push(2))
+    ///         .build();
+    pub fn object() -> ObjectBuilder {
+        ObjectBuilder::default()
+    }
+
+    /// Starts building an array. See `Value::object`.
+    pub fn array() -> ArrayBuilder {
+        ArrayBuilder::default()
+    }
+}
+
+/// An object under construction, from `Value::object`. Builders can be
+/// passed wherever a value goes without calling `build`.
+#[derive(Debug, Clone, Default)]
+pub struct ObjectBuilder {
+    map: Map<String, Value>,
+}
+
+impl ObjectBuilder {
+    /// Sets `key` to `value`, replacing an earlier field with that key.
+    pub fn field<K: Into<String>, V: Into<Value>>(mut self, key: K, value: V) -> Self {
+        self.map.insert(key.into(), value.into());
+        self
+    }
+
+    /// Sets `key` only if `value` is `Some`, leaving the field out
+    /// instead of writing `null`.
+    pub fn field_opt<K: Into<String>, V: Into<Value>>(self, key: K, value: Option<V>) -> Self {
+        match value {
+            Some(value) => self.field(key, value),
+            None => self,
+        }
+    }
+
+    pub fn build(self) -> Value {
+        Value::Object(RsonMap(self.map))
+    }
+}
+
+impl<K: Into<String>, V: Into<Value>> Extend<(K, V)> for ObjectBuilder {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        self.map
+            .extend(iter.into_iter().map(|(k, v)| (k.into(), v.into())));
+    }
+}
+
+impl From<ObjectBuilder> for Value {
+    fn from(builder: ObjectBuilder) -> Self {
+        builder.build()
+    }
+}
+
+/// An array under construction, from `Value::array`.
+#[derive(Debug, Clone, Default)]
+pub struct ArrayBuilder {
+    items: Vec<Value>,
+}
+
+impl ArrayBuilder {
+    pub fn push<V: Into<Value>>(mut self, value: V) -> Self {
+        self.items.push(value.into());
+        self
+    }
+
+    pub fn build(self) -> Value {
+        Value::Array(self.items)
+    }
+}
+
+impl<V: Into<Value>> Extend<V> for ArrayBuilder {
+    fn extend<I: IntoIterator<Item = V>>(&mut self, iter: I) {
+        self.items.extend(iter.into_iter().map(Into::into));
+    }
+}
+
+impl From<ArrayBuilder> for Value {
+    fn from(builder: ArrayBuilder) -> Self {
+        builder.build()
+    }
+}
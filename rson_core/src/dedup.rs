@@ -0,0 +1,186 @@
+use crate::pool::StringPool;
+use crate::value::{Literal, Number, RsonMap, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+use std::mem::size_of;
+use std::sync::Arc;
+
+/// A `Value` whose identical subtrees are stored once and shared through
+/// an `Arc`. Produced by `Value::dedup`; documents that repeat the same
+/// objects per record (schemas, lookup tables, default settings) shrink
+/// to the size of their distinct parts. Strings come from a `StringPool`,
+/// which `Rson::try_from_reader_pooled` fills while parsing.
+#[derive(Debug, Eq, PartialEq)]
+pub enum SharedValue {
+    Literal(Literal),
+    Number(Number),
+    String(Arc<str>),
+    Array(Vec<Arc<SharedValue>>),
+    Object(RsonMap<String, Arc<SharedValue>>),
+}
+
+impl SharedValue {
+    /// Expands the shared tree back into an owned `Value`.
+    pub fn to_value(&self) -> Value {
+        match self {
+            SharedValue::Literal(Literal::Null) => Value::Literal(Literal::Null),
+            SharedValue::Literal(Literal::Bool(b)) => Value::Literal(Literal::Bool(*b)),
+            SharedValue::Number(n) => Value::Number(Number::new(n.as_str().to_string())),
+            SharedValue::String(s) => Value::String(s.to_string()),
+            SharedValue::Array(array) => Value::Array(array.iter().map(|v| v.to_value()).collect()),
+            SharedValue::Object(map) => Value::Object(RsonMap(
+                map.0
+                    .iter()
+                    .map(|(k, v)| (k.clone(), v.to_value()))
+                    .collect(),
+            )),
+        }
+    }
+}
+
+/// What a dedup pass found. Byte counts are estimates of the node and
+/// payload sizes, good for comparing before and after rather than for
+/// exact accounting.
+#[derive(Debug, Default, Eq, PartialEq, Copy, Clone)]
+pub struct DedupStats {
+    /// Nodes in the original tree.
+    pub nodes: usize,
+    /// Distinct nodes kept after sharing.
+    pub unique_nodes: usize,
+    pub bytes_before: usize,
+    pub bytes_after: usize,
+}
+
+impl DedupStats {
+    pub fn bytes_saved(&self) -> usize {
+        self.bytes_before - self.bytes_after
+    }
+}
+
+impl Value {
+    /// Builds a `SharedValue` where every group of identical subtrees is
+    /// backed by a single allocation, and reports how much was saved.
+    pub fn dedup(&self) -> (Arc<SharedValue>, DedupStats) {
+        self.dedup_with(&mut StringPool::new())
+    }
+
+    /// Like `dedup`, taking strings from `pool`, which can be shared by
+    /// many documents.
+    pub fn dedup_with(&self, pool: &mut StringPool) -> (Arc<SharedValue>, DedupStats) {
+        let mut interner = Interner {
+            nodes: HashSet::new(),
+            pool,
+            stats: DedupStats::default(),
+        };
+        let root = interner.intern(self);
+        (root, interner.stats)
+    }
+}
+
+/// Hash-conses nodes bottom-up. Because children are interned before
+/// their parent, two subtrees are identical exactly when their roots hold
+/// the same scalars and point at the same child allocations, so keys are
+/// compared shallowly and each node is hashed once.
+struct Interner<'a> {
+    nodes: HashSet<NodeKey>,
+    pool: &'a mut StringPool,
+    stats: DedupStats,
+}
+
+impl Interner<'_> {
+    fn intern(&mut self, value: &Value) -> Arc<SharedValue> {
+        let node = match value {
+            Value::Literal(Literal::Null) => SharedValue::Literal(Literal::Null),
+            Value::Literal(Literal::Bool(b)) => SharedValue::Literal(Literal::Bool(*b)),
+            Value::Number(n) => SharedValue::Number(Number::new(n.as_str().to_string())),
+            Value::String(s) => SharedValue::String(self.pool.intern(s)),
+            Value::Array(array) => {
+                SharedValue::Array(array.iter().map(|v| self.intern(v)).collect())
+            }
+            Value::Object(map) => SharedValue::Object(RsonMap(
+                map.0
+                    .iter()
+                    .map(|(k, v)| (k.clone(), self.intern(v)))
+                    .collect(),
+            )),
+        };
+
+        let bytes = node_size(&node);
+        self.stats.nodes += 1;
+        self.stats.bytes_before += bytes;
+
+        let key = NodeKey(Arc::new(node));
+        if let Some(existing) = self.nodes.get(&key) {
+            return existing.0.clone();
+        }
+
+        self.stats.unique_nodes += 1;
+        self.stats.bytes_after += bytes;
+        let shared = key.0.clone();
+        self.nodes.insert(key);
+        shared
+    }
+}
+
+fn node_size(node: &SharedValue) -> usize {
+    let payload = match node {
+        SharedValue::Literal(_) => 0,
+        SharedValue::Number(n) => n.as_str().len(),
+        SharedValue::String(s) => s.len(),
+        SharedValue::Array(array) => array.len() * size_of::<Arc<SharedValue>>(),
+        SharedValue::Object(map) => map
+            .0
+            .keys()
+            .map(|k| k.len() + size_of::<(String, Arc<SharedValue>)>())
+            .sum(),
+    };
+    size_of::<SharedValue>() + payload
+}
+
+struct NodeKey(Arc<SharedValue>);
+
+impl Hash for NodeKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match &*self.0 {
+            SharedValue::Literal(l) => (0u8, l).hash(state),
+            SharedValue::Number(n) => (1u8, n.as_str()).hash(state),
+            SharedValue::String(s) => (2u8, s).hash(state),
+            SharedValue::Array(array) => {
+                3u8.hash(state);
+                for child in array {
+                    Arc::as_ptr(child).hash(state);
+                }
+            }
+            SharedValue::Object(map) => {
+                // Combine entry hashes commutatively; map iteration order
+                // is not stable.
+                let entries = map.0.iter().fold(0u64, |acc, (k, v)| {
+                    let mut entry = DefaultHasher::new();
+                    (k, Arc::as_ptr(v)).hash(&mut entry);
+                    acc.wrapping_add(entry.finish())
+                });
+                (4u8, map.0.len(), entries).hash(state);
+            }
+        }
+    }
+}
+
+impl PartialEq for NodeKey {
+    fn eq(&self, other: &Self) -> bool {
+        match (&*self.0, &*other.0) {
+            (SharedValue::Array(a), SharedValue::Array(b)) => {
+                a.len() == b.len() && a.iter().zip(b).all(|(x, y)| Arc::ptr_eq(x, y))
+            }
+            (SharedValue::Object(a), SharedValue::Object(b)) => {
+                a.0.len() == b.0.len()
+                    && a.0
+                        .iter()
+                        .all(|(k, x)| b.0.get(k).is_some_and(|y| Arc::ptr_eq(x, y)))
+            }
+            (a, b) => a == b,
+        }
+    }
+}
+
+impl Eq for NodeKey {}
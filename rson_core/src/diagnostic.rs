@@ -0,0 +1,58 @@
+use crate::error::Error;
+use crate::rson::Rson;
+use std::fmt::{self, Display};
+use std::io::Read;
+
+/// How bad a `Diagnostic` is.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Severity {
+    /// Valid for `rson`'s default options, but not strict RFC 8259 JSON,
+    /// so other parsers may reject it.
+    Warning,
+    /// Not JSON at all.
+    Error,
+}
+
+/// One problem `validate_all` found.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// What is wrong and where, with a `Suggestion` for common mistakes.
+    pub error: Error,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.severity {
+            Severity::Warning => write!(f, "warning: {}", self.error),
+            Severity::Error => write!(f, "error: {}", self.error),
+        }
+    }
+}
+
+/// Checks a whole document in one pass, reporting every syntax error
+/// instead of stopping at the first, along with warnings for what isn't
+/// strict JSON, repeated keys included (see `Rson::strict_violations`).
+/// Diagnostics are in the order of the text; none means the document is
+/// strict JSON.
+///
+/// After an error in a member or element the rest of it is skipped, up to
+/// the next `,` or closing bracket, and checking goes on from there. An
+/// unclosed string or bracket runs to the end of the input, so it ends
+/// the checking with one last error.
+pub fn validate_all<R: Read>(reader: R) -> Vec<Diagnostic> {
+    let (errors, warnings) = Rson::check_all(reader);
+    let mut diagnostics: Vec<_> = warnings
+        .into_iter()
+        .map(|error| Diagnostic {
+            severity: Severity::Warning,
+            error,
+        })
+        .chain(errors.into_iter().map(|error| Diagnostic {
+            severity: Severity::Error,
+            error,
+        }))
+        .collect();
+    diagnostics.sort_by_key(|d| (d.error.line(), d.error.column()));
+    diagnostics
+}
@@ -0,0 +1,208 @@
+use std::fmt::{self, Display};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// What went wrong, for callers that need to branch on the cause rather
+/// than show the message. New kinds may be added, so matches need a
+/// wildcard arm.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// A character that can't appear where it was found.
+    UnexpectedToken,
+    /// The input ended in the middle of a value. More input may fix it.
+    UnexpectedEof,
+    /// The input was empty or only whitespace. Also an EOF: more input may
+    /// fix it.
+    EmptyInput,
+    /// A `\` escape in a string that JSON doesn't define, or a malformed
+    /// `\u` escape.
+    InvalidEscape,
+    InvalidNumber,
+    /// Arrays and objects nested deeper than `MAX_DEPTH`.
+    DepthLimit,
+    /// The same key twice in one object.
+    DuplicateKey,
+    /// More input after a complete value.
+    TrailingCharacters,
+    InvalidUtf8,
+    /// Anything else, including errors converting between `Value`s and
+    /// Rust types.
+    Message,
+}
+
+/// A likely fix for a common mistake in JSON text, for tools that offer
+/// to correct it. `Display` gives a hint for people.
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+#[non_exhaustive]
+pub enum Suggestion {
+    /// A string or key in single quotes.
+    UseDoubleQuotes,
+    /// A comma before the closing `]` or `}`.
+    RemoveTrailingComma,
+    /// Two members or elements without a comma between them.
+    InsertComma,
+    /// An object key without quotes.
+    QuoteKey,
+    /// A literal from another language, like Python's `True` or `None`;
+    /// holds the JSON literal to use instead.
+    UseLiteral(&'static str),
+}
+
+impl Display for Suggestion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Suggestion::UseDoubleQuotes => f.write_str("JSON strings use double quotes"),
+            Suggestion::RemoveTrailingComma => {
+                f.write_str("remove the comma before the closing bracket")
+            }
+            Suggestion::InsertComma => f.write_str("add a `,` before this"),
+            Suggestion::QuoteKey => f.write_str("put the key in double quotes"),
+            Suggestion::UseLiteral(literal) => write!(f, "did you mean `{}`?", literal),
+        }
+    }
+}
+
+/// Error produced while parsing JSON text or converting between `Value`s
+/// and Rust types. It is `Send + Sync + 'static`, so `?` converts it into
+/// a `Box<dyn std::error::Error + Send + Sync>`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Error {
+    kind: ErrorKind,
+    message: String,
+    line: usize,
+    column: usize,
+    suggestion: Option<Suggestion>,
+}
+
+impl Error {
+    pub fn new<T: Display>(kind: ErrorKind, message: T) -> Self {
+        Self {
+            kind,
+            message: message.to_string(),
+            line: 0,
+            column: 0,
+            suggestion: None,
+        }
+    }
+
+    pub fn with_suggestion(self, suggestion: Suggestion) -> Self {
+        Self {
+            suggestion: Some(suggestion),
+            ..self
+        }
+    }
+
+    pub fn custom<T: Display>(message: T) -> Self {
+        Self::new(ErrorKind::Message, message)
+    }
+
+    pub(crate) fn io(e: std::io::Error) -> Self {
+        Self::custom(format!("I/O error: {}", e))
+    }
+
+    /// An error in JSON text, at a 1-based line and column.
+    pub fn syntax<T: Display>(kind: ErrorKind, message: T, line: usize, column: usize) -> Self {
+        Self {
+            line,
+            column,
+            ..Self::new(kind, message)
+        }
+    }
+
+    /// Moves an error in JSON text that was parsed on its own, like one
+    /// record of a JSON Lines file, to `line` of the whole input.
+    pub fn on_line(self, line: usize) -> Self {
+        if self.line == 0 {
+            return self;
+        }
+        Self { line, ..self }
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// The line the error was found on, counting from 1, or 0 for errors
+    /// that aren't about a position in JSON text.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column the error was found at, in characters counting from 1,
+    /// or 0 like `line`.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// How to fix the JSON text, for errors caused by a common mistake
+    /// like a trailing comma or Python's `True`.
+    pub fn suggestion(&self) -> Option<Suggestion> {
+        self.suggestion
+    }
+
+    /// Whether the input ended too early, i.e. reading more of it and
+    /// parsing again may succeed. That includes empty input.
+    pub fn is_eof(&self) -> bool {
+        matches!(self.kind, ErrorKind::UnexpectedEof | ErrorKind::EmptyInput)
+    }
+
+    pub fn invalid_type(unexpected: &str, expected: &str) -> Self {
+        Self::custom(format!(
+            "invalid type: {}, expected {}",
+            unexpected, expected
+        ))
+    }
+
+    pub fn invalid_value(unexpected: &str, expected: &str) -> Self {
+        Self::custom(format!(
+            "invalid value: {}, expected {}",
+            unexpected, expected
+        ))
+    }
+
+    pub fn invalid_length(len: usize, expected: &str) -> Self {
+        Self::custom(format!("invalid length {}, expected {}", len, expected))
+    }
+
+    pub fn missing_field(field: &str) -> Self {
+        Self::custom(format!("missing field `{}`", field))
+    }
+
+    pub fn duplicate_field(field: &str) -> Self {
+        Self::new(
+            ErrorKind::DuplicateKey,
+            format!("duplicate field `{}`", field),
+        )
+    }
+
+    pub fn unknown_variant(variant: &str, expected: &[&str]) -> Self {
+        Self::custom(format!(
+            "unknown variant `{}`, expected one of `{}`",
+            variant,
+            expected.join("`, `")
+        ))
+    }
+
+    /// Qualifies the error with the JSON Pointer of the value it is about.
+    pub fn at(self, pointer: &str) -> Self {
+        let message = if pointer.is_empty() {
+            format!("at the root: {}", self.message)
+        } else {
+            format!("at `{}`: {}", pointer, self.message)
+        };
+        Self { message, ..self }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)?;
+        if self.line > 0 {
+            write!(f, " at line {} column {}", self.line, self.column)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {}
@@ -0,0 +1,71 @@
+//! Usage metrics through the `metrics` facade, with the `metrics` feature.
+//! Without it the functions here do nothing and compile away.
+//!
+//! Counters: `rson_documents_parsed_total`, `rson_bytes_parsed_total`,
+//! `rson_parse_errors_total` (labelled with `kind`),
+//! `rson_documents_serialized_total` and `rson_bytes_serialized_total`.
+//! Histograms, in seconds: `rson_parse_duration_seconds` and
+//! `rson_serialize_duration_seconds`.
+
+use crate::error::{ErrorKind, Result};
+
+#[cfg(feature = "metrics")]
+pub(crate) struct Timer(std::time::Instant);
+#[cfg(not(feature = "metrics"))]
+pub(crate) struct Timer;
+
+impl Timer {
+    #[inline]
+    pub(crate) fn start() -> Self {
+        #[cfg(feature = "metrics")]
+        return Timer(std::time::Instant::now());
+        #[cfg(not(feature = "metrics"))]
+        return Timer;
+    }
+}
+
+/// Records a parsed document, or the kind of error that stopped it.
+#[inline]
+pub(crate) fn parsed<T>(result: &Result<T>, bytes: usize, timer: Timer) {
+    #[cfg(feature = "metrics")]
+    {
+        ::metrics::counter!("rson_bytes_parsed_total").increment(bytes as u64);
+        ::metrics::histogram!("rson_parse_duration_seconds").record(timer.0.elapsed());
+        match result {
+            Ok(_) => ::metrics::counter!("rson_documents_parsed_total").increment(1),
+            Err(e) => ::metrics::counter!("rson_parse_errors_total", "kind" => label(e.kind()))
+                .increment(1),
+        }
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = (result, bytes, timer);
+}
+
+/// Records a document written out in `bytes` bytes.
+#[inline]
+pub(crate) fn serialized(bytes: usize, timer: Timer) {
+    #[cfg(feature = "metrics")]
+    {
+        ::metrics::counter!("rson_documents_serialized_total").increment(1);
+        ::metrics::counter!("rson_bytes_serialized_total").increment(bytes as u64);
+        ::metrics::histogram!("rson_serialize_duration_seconds").record(timer.0.elapsed());
+    }
+    #[cfg(not(feature = "metrics"))]
+    let _ = (bytes, timer);
+}
+
+/// The `kind` label of an error.
+fn label(kind: ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::UnexpectedToken => "unexpected_token",
+        ErrorKind::UnexpectedEof => "unexpected_eof",
+        ErrorKind::EmptyInput => "empty_input",
+        ErrorKind::InvalidEscape => "invalid_escape",
+        ErrorKind::InvalidNumber => "invalid_number",
+        ErrorKind::DepthLimit => "depth_limit",
+        ErrorKind::DuplicateKey => "duplicate_key",
+        ErrorKind::TrailingCharacters => "trailing_characters",
+        ErrorKind::InvalidUtf8 => "invalid_utf8",
+        ErrorKind::Message => "message",
+    }
+}
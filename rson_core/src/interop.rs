@@ -0,0 +1,64 @@
+//! Conversions to and from `serde_json::Value` (`serde` feature).
+//!
+//! Numbers: `rson` keeps number text as parsed, `serde_json` keeps an
+//! `i64`, a `u64` or an `f64` (unless its `arbitrary_precision` feature is
+//! on). Converting to `serde_json` is exact for integers in those ranges;
+//! any other number becomes the nearest `f64`, and one too large for an
+//! `f64`, or text that isn't a number at all, becomes `null`, as
+//! `serde_json` does with non-finite floats. Converting back writes the
+//! number as `serde_json` displays it, so `1.0` comes back as `1.0` but
+//! `1E3` as `1000.0`.
+//!
+//! Key order: `serde_json` sorts object keys unless its `preserve_order`
+//! feature is on, and `rson` keeps them in hash order unless its own
+//! `preserve_order` is. Enable both to carry order across.
+
+use crate::value::{Literal, Number, RsonMap, Value};
+
+impl From<serde_json::Value> for Value {
+    fn from(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => Value::Literal(Literal::Null),
+            serde_json::Value::Bool(b) => Value::Literal(Literal::Bool(b)),
+            serde_json::Value::Number(n) => Value::Number(Number::new(n.to_string())),
+            serde_json::Value::String(s) => Value::String(s),
+            serde_json::Value::Array(array) => {
+                Value::Array(array.into_iter().map(Value::from).collect())
+            }
+            serde_json::Value::Object(map) => Value::Object(RsonMap(
+                map.into_iter().map(|(k, v)| (k, Value::from(v))).collect(),
+            )),
+        }
+    }
+}
+
+impl From<Value> for serde_json::Value {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Literal(Literal::Null) => serde_json::Value::Null,
+            Value::Literal(Literal::Bool(b)) => serde_json::Value::Bool(b),
+            Value::Number(n) => number(&n)
+                .map(serde_json::Value::Number)
+                .unwrap_or(serde_json::Value::Null),
+            Value::String(s) => serde_json::Value::String(s),
+            Value::Array(array) => {
+                serde_json::Value::Array(array.into_iter().map(serde_json::Value::from).collect())
+            }
+            Value::Object(map) => serde_json::Value::Object(
+                map.0
+                    .into_iter()
+                    .map(|(k, v)| (k, serde_json::Value::from(v)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// `n` as a `serde_json::Number`, falling back to the nearest finite
+/// `f64` for text `serde_json` rejects, such as relaxed or huge numbers.
+fn number(n: &Number) -> Option<serde_json::Number> {
+    let text = n.as_str();
+    text.parse()
+        .ok()
+        .or_else(|| serde_json::Number::from_f64(text.parse().ok()?))
+}
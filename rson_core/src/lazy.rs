@@ -0,0 +1,199 @@
+use crate::error::Result;
+use crate::rson::Rson;
+use crate::value::Value;
+
+/// A JSON value kept as raw bytes until it is needed.
+///
+/// Looking up a member or element only scans past its siblings without
+/// building them, so pulling two fields out of an enormous document costs
+/// a single pass over the bytes that precede them plus a parse of the two
+/// fields themselves:
+///
+///     let doc = LazyValue::new(bytes);
+///     let name = doc.get("user").and_then(|u| u.get("name")).map(|n| n.parse());
+///
+/// Lookups on malformed input or on the wrong kind of value return `None`;
+/// `parse` reports syntax errors the same way `Rson::try_from_reader` does.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct LazyValue<'a> {
+    text: &'a [u8],
+}
+
+impl<'a> LazyValue<'a> {
+    /// Wraps the text of a single JSON value. Surrounding whitespace is
+    /// ignored.
+    pub fn new(text: &'a [u8]) -> Self {
+        let start = skip_white(text, 0);
+        let mut end = text.len();
+        while end > start && is_white(text[end - 1]) {
+            end -= 1;
+        }
+        Self {
+            text: &text[start..end],
+        }
+    }
+
+    /// The unparsed bytes of this value.
+    pub fn raw(&self) -> &'a [u8] {
+        self.text
+    }
+
+    pub fn is_object(&self) -> bool {
+        self.text.first() == Some(&b'{')
+    }
+
+    pub fn is_array(&self) -> bool {
+        self.text.first() == Some(&b'[')
+    }
+
+    /// Parses this value, and only this value, into a `Value`.
+    pub fn parse(&self) -> Result<Value> {
+        Rson::try_from_reader(self.text)
+    }
+
+    /// Returns the member named `key` if this is an object. Escaped keys
+    /// match their unescaped text, and of duplicate keys the last one
+    /// wins, as when parsing; so the whole object is scanned.
+    pub fn get(&self, key: &str) -> Option<LazyValue<'a>> {
+        self.members()?
+            .filter(|(name, _)| key_matches(name, key))
+            .last()
+            .map(|(_, value)| value)
+    }
+
+    /// Returns the element at `index` if this is an array.
+    pub fn get_index(&self, index: usize) -> Option<LazyValue<'a>> {
+        self.elements()?.nth(index)
+    }
+
+    /// Iterates over the raw members of an object as `(key, value)` pairs.
+    /// Keys are the bytes between the quotes, escapes and all.
+    pub fn members(&self) -> Option<impl Iterator<Item = (&'a [u8], LazyValue<'a>)>> {
+        if !self.is_object() {
+            return None;
+        }
+        let text = self.text;
+        let mut pos = 1;
+        Some(std::iter::from_fn(move || {
+            pos = skip_white(text, pos);
+            if text.get(pos) != Some(&b'"') {
+                return None;
+            }
+            let key_end = skip_value(text, pos)?;
+            let key = &text[pos + 1..key_end - 1];
+
+            pos = skip_white(text, key_end);
+            if text.get(pos) != Some(&b':') {
+                return None;
+            }
+            let start = skip_white(text, pos + 1);
+            let end = skip_value(text, start)?;
+            pos = skip_separator(text, end);
+            Some((key, LazyValue::new(&text[start..end])))
+        }))
+    }
+
+    /// Iterates over the raw elements of an array.
+    pub fn elements(&self) -> Option<impl Iterator<Item = LazyValue<'a>>> {
+        if !self.is_array() {
+            return None;
+        }
+        let text = self.text;
+        let mut pos = 1;
+        Some(std::iter::from_fn(move || {
+            let start = skip_white(text, pos);
+            if text.get(start).is_none_or(|&b| b == b']') {
+                return None;
+            }
+            let end = skip_value(text, start)?;
+            pos = skip_separator(text, end);
+            Some(LazyValue::new(&text[start..end]))
+        }))
+    }
+}
+
+/// Whether the raw key `name`, as `members` yields it, is `key`.
+fn key_matches(name: &[u8], key: &str) -> bool {
+    if !name.contains(&b'\\') {
+        return name == key.as_bytes();
+    }
+    let mut quoted = Vec::with_capacity(name.len() + 2);
+    quoted.push(b'"');
+    quoted.extend_from_slice(name);
+    quoted.push(b'"');
+    matches!(Rson::try_from_reader_unrecorded(&quoted[..]), Ok(Value::String(s)) if s == key)
+}
+
+fn is_white(byte: u8) -> bool {
+    matches!(byte, b' ' | b'\t' | b'\n' | b'\r')
+}
+
+fn skip_white(text: &[u8], mut pos: usize) -> usize {
+    while pos < text.len() && is_white(text[pos]) {
+        pos += 1;
+    }
+    pos
+}
+
+/// Moves past the whitespace and optional `,` that follow a value.
+fn skip_separator(text: &[u8], pos: usize) -> usize {
+    let pos = skip_white(text, pos);
+    if text.get(pos) == Some(&b',') {
+        pos + 1
+    } else {
+        pos
+    }
+}
+
+/// Returns the position just past the value starting at `pos`, without
+/// validating anything beyond what is needed to find its end: strings up
+/// to the closing quote, containers up to their matching bracket, and
+/// scalars up to the next structural character or whitespace.
+fn skip_value(text: &[u8], pos: usize) -> Option<usize> {
+    match text.get(pos)? {
+        b'"' => {
+            let mut i = pos + 1;
+            while i < text.len() {
+                match text[i] {
+                    b'\\' => i += 2,
+                    b'"' => return Some(i + 1),
+                    _ => i += 1,
+                }
+            }
+            None
+        }
+        b'{' | b'[' => {
+            let mut depth = 0usize;
+            let mut i = pos;
+            while i < text.len() {
+                match text[i] {
+                    b'"' => {
+                        i = skip_value(text, i)?;
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(i + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+            None
+        }
+        _ => {
+            let mut i = pos;
+            while i < text.len() && !is_white(text[i]) && !b",:]}".contains(&text[i]) {
+                i += 1;
+            }
+            if i == pos {
+                None
+            } else {
+                Some(i)
+            }
+        }
+    }
+}
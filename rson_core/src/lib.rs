@@ -0,0 +1,66 @@
+//! The JSON data model and parser of `rson`, without the derive macros or
+//! the `Serialize`/`Deserialize` framework. Most users want the `rson`
+//! crate, which re-exports everything here.
+
+#![allow(dead_code)]
+
+#[cfg(not(feature = "std"))]
+compile_error!("rson_core requires the `std` feature");
+
+mod appender;
+mod builder;
+mod dedup;
+mod diagnostic;
+mod error;
+mod instrument;
+#[cfg(feature = "serde")]
+mod interop;
+mod lazy;
+mod minify;
+mod partial;
+mod pointer;
+mod pool;
+mod refs;
+mod rson;
+mod stream;
+mod truncate;
+mod value;
+mod write;
+
+pub use appender::*;
+pub use builder::*;
+pub use dedup::*;
+pub use diagnostic::*;
+pub use error::*;
+pub use lazy::*;
+pub use minify::*;
+pub use partial::*;
+pub use pointer::*;
+pub use pool::*;
+pub use refs::*;
+pub use rson::*;
+pub use stream::*;
+pub use truncate::*;
+pub use value::*;
+pub use write::*;
+
+// Parsed documents, parsers and errors can move to and be shared with
+// other threads and async tasks; keep them that way.
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<Value>();
+    assert_send_sync::<Error>();
+    assert_send_sync::<Diagnostic>();
+    assert_send_sync::<ErrorKind>();
+    assert_send_sync::<ParseOptions>();
+    assert_send_sync::<Rson<'static, std::fs::File>>();
+    assert_send_sync::<Rson<'static, std::io::Empty>>();
+    assert_send_sync::<LazyValue<'static>>();
+    assert_send_sync::<PartialValue>();
+    assert_send_sync::<std::sync::Arc<SharedValue>>();
+    assert_send_sync::<StringPool>();
+    assert_send_sync::<JsonWriter<Vec<u8>>>();
+    assert_send_sync::<ArrayFileAppender>();
+    assert_send_sync::<ObjectBuilder>();
+};
@@ -0,0 +1,148 @@
+use crate::error::{Error, ErrorKind, Result};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+
+/// Copies JSON text from `reader` to `writer` without the whitespace
+/// between tokens, byte for byte otherwise, and without building a tree:
+///
+///     minify(File::open("assets/data.json")?, &mut out)?;
+///
+/// The input isn't validated beyond finding where strings end. Where
+/// dropping whitespace would join two tokens, e.g. `1 2`, one space is
+/// kept, so invalid input stays invalid.
+pub fn minify<R: Read, W: Write>(reader: R, writer: W) -> Result<()> {
+    Minifier::new(reader, writer, false).run()
+}
+
+/// Like `minify`, for JSONC: `//` and `/* */` comments are dropped too.
+pub fn minify_jsonc<R: Read, W: Write>(reader: R, writer: W) -> Result<()> {
+    Minifier::new(reader, writer, true).run()
+}
+
+struct Minifier<R, W: Write> {
+    bytes: io::Bytes<BufReader<R>>,
+    out: BufWriter<W>,
+    jsonc: bool,
+    /// Position of the last byte read, for errors.
+    line: usize,
+    column: usize,
+    after_newline: bool,
+    /// Whether the last byte written could run into a following number
+    /// or literal.
+    word: bool,
+    /// Whether whitespace or a comment was skipped since then.
+    space: bool,
+}
+
+impl<R: Read, W: Write> Minifier<R, W> {
+    fn new(reader: R, writer: W, jsonc: bool) -> Self {
+        Self {
+            bytes: BufReader::new(reader).bytes(),
+            out: BufWriter::new(writer),
+            jsonc,
+            line: 1,
+            column: 0,
+            after_newline: false,
+            word: false,
+            space: false,
+        }
+    }
+
+    fn run(mut self) -> Result<()> {
+        while let Some(byte) = self.next()? {
+            match byte {
+                b' ' | b'\t' | b'\n' | b'\r' => self.space = true,
+                b'/' if self.jsonc => {
+                    self.comment()?;
+                    self.space = true;
+                }
+                b'"' => {
+                    self.string()?;
+                    self.word = false;
+                    self.space = false;
+                }
+                _ => {
+                    let word = byte.is_ascii_alphanumeric()
+                        || matches!(byte, b'-' | b'+' | b'.')
+                        || !byte.is_ascii();
+                    if word && self.word && self.space {
+                        self.write(b' ')?;
+                    }
+                    self.write(byte)?;
+                    self.word = word;
+                    self.space = false;
+                }
+            }
+        }
+        self.out.flush().map_err(Error::io)
+    }
+
+    /// Copies a string whose opening quote was just read.
+    fn string(&mut self) -> Result<()> {
+        let (line, column) = (self.line, self.column);
+        let eof = || Error::syntax(ErrorKind::UnexpectedEof, "EOF in a string", line, column);
+
+        self.write(b'"')?;
+        loop {
+            let byte = self.next()?.ok_or_else(eof)?;
+            self.write(byte)?;
+            match byte {
+                b'"' => return Ok(()),
+                b'\\' => {
+                    let escaped = self.next()?.ok_or_else(eof)?;
+                    self.write(escaped)?;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Skips a comment whose `/` was just read.
+    fn comment(&mut self) -> Result<()> {
+        let (line, column) = (self.line, self.column);
+        let eof = || Error::syntax(ErrorKind::UnexpectedEof, "EOF in a comment", line, column);
+
+        match self.next()? {
+            Some(b'/') => {
+                while !matches!(self.next()?, Some(b'\n') | None) {}
+                Ok(())
+            }
+            Some(b'*') => {
+                let mut star = false;
+                loop {
+                    match self.next()?.ok_or_else(eof)? {
+                        b'/' if star => return Ok(()),
+                        byte => star = byte == b'*',
+                    }
+                }
+            }
+            Some(_) => Err(Error::syntax(
+                ErrorKind::UnexpectedToken,
+                "expected `//` or `/*`",
+                line,
+                column,
+            )),
+            None => Err(eof()),
+        }
+    }
+
+    fn next(&mut self) -> Result<Option<u8>> {
+        let byte = match self.bytes.next() {
+            None => return Ok(None),
+            Some(byte) => byte.map_err(Error::io)?,
+        };
+        if self.after_newline {
+            self.line += 1;
+            self.column = 0;
+        }
+        // Count characters, not the continuation bytes of UTF-8.
+        if byte & 0xc0 != 0x80 {
+            self.column += 1;
+        }
+        self.after_newline = byte == b'\n';
+        Ok(Some(byte))
+    }
+
+    fn write(&mut self, byte: u8) -> Result<()> {
+        self.out.write_all(&[byte]).map_err(Error::io)
+    }
+}
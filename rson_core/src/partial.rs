@@ -0,0 +1,157 @@
+use crate::error::Result;
+use crate::lazy::LazyValue;
+use crate::pointer::{index, unescape};
+use crate::rson::Rson;
+use crate::value::{Literal, Number, RsonMap, Value};
+use std::fmt;
+
+/// The text of a JSON value that hasn't been parsed, left behind by
+/// `Rson::parse_to_depth` below its depth limit.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RawValue {
+    text: String,
+}
+
+impl RawValue {
+    pub(crate) fn new(text: String) -> Self {
+        Self { text }
+    }
+
+    /// The text exactly as it appeared in the document.
+    pub fn get(&self) -> &str {
+        &self.text
+    }
+
+    pub fn parse(&self) -> Result<Value> {
+        Rson::try_from_reader_unrecorded(self.text.as_bytes())
+    }
+
+    /// Parses the top `depth` levels of this value, see
+    /// `Rson::parse_to_depth`.
+    pub fn parse_to_depth(&self, depth: usize) -> Result<PartialValue> {
+        Rson::parse_to_depth_unrecorded(self.text.as_bytes(), depth)
+    }
+
+    /// Looks into the text without parsing it, see `LazyValue`.
+    pub fn lazy(&self) -> LazyValue<'_> {
+        LazyValue::new(self.text.as_bytes())
+    }
+}
+
+impl fmt::Display for RawValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+/// A document parsed down to a limited depth by `Rson::parse_to_depth`:
+/// a `Value` whose deeper arrays and objects are still text.
+///
+///     let mut summary = Rson::parse_to_depth(file, 2)?;
+///     summary.pointer_mut("/regions/0/stats").unwrap().expand(1)?;
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum PartialValue {
+    Literal(Literal),
+    Number(Number),
+    String(String),
+    Array(Vec<PartialValue>),
+    Object(RsonMap<String, PartialValue>),
+    /// An array or object below the depth limit.
+    Raw(RawValue),
+}
+
+impl PartialValue {
+    pub fn is_raw(&self) -> bool {
+        matches!(self, PartialValue::Raw(_))
+    }
+
+    /// Parses a raw value `depth` more levels down in place. Other values
+    /// are left as they are.
+    pub fn expand(&mut self, depth: usize) -> Result<()> {
+        if let PartialValue::Raw(raw) = self {
+            *self = raw.parse_to_depth(depth)?;
+        }
+        Ok(())
+    }
+
+    /// Looks up a value by JSON Pointer like `Value::pointer`. Pointers
+    /// into a raw value find nothing until it is expanded.
+    pub fn pointer(&self, pointer: &str) -> Option<&PartialValue> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        if !pointer.starts_with('/') {
+            return None;
+        }
+        pointer
+            .split('/')
+            .skip(1)
+            .map(unescape)
+            .try_fold(self, |value, segment| match value {
+                PartialValue::Object(map) => map.0.get(&segment),
+                PartialValue::Array(array) => index(&segment).and_then(|i| array.get(i)),
+                _ => None,
+            })
+    }
+
+    /// Like `pointer`, but returns a mutable reference, e.g. to `expand`.
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut PartialValue> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        if !pointer.starts_with('/') {
+            return None;
+        }
+        pointer
+            .split('/')
+            .skip(1)
+            .map(unescape)
+            .try_fold(self, |value, segment| match value {
+                PartialValue::Object(map) => map.0.get_mut(&segment),
+                PartialValue::Array(array) => index(&segment).and_then(move |i| array.get_mut(i)),
+                _ => None,
+            })
+    }
+
+    /// Parses whatever is still raw, giving the whole document.
+    pub fn into_value(self) -> Result<Value> {
+        Ok(match self {
+            PartialValue::Literal(literal) => Value::Literal(literal),
+            PartialValue::Number(n) => Value::Number(n),
+            PartialValue::String(s) => Value::String(s),
+            PartialValue::Array(array) => Value::Array(
+                array
+                    .into_iter()
+                    .map(PartialValue::into_value)
+                    .collect::<Result<_>>()?,
+            ),
+            PartialValue::Object(map) => Value::Object(RsonMap(
+                map.0
+                    .into_iter()
+                    .map(|(k, v)| Ok((k, v.into_value()?)))
+                    .collect::<Result<_>>()?,
+            )),
+            PartialValue::Raw(raw) => raw.parse()?,
+        })
+    }
+}
+
+/// A fully parsed value, with nothing left raw.
+impl From<Value> for PartialValue {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::Literal(literal) => PartialValue::Literal(literal),
+            Value::Number(n) => PartialValue::Number(n),
+            Value::String(s) => PartialValue::String(s),
+            Value::Array(array) => {
+                PartialValue::Array(array.into_iter().map(PartialValue::from).collect())
+            }
+            Value::Object(map) => PartialValue::Object(RsonMap(
+                map.0
+                    .into_iter()
+                    .map(|(k, v)| (k, PartialValue::from(v)))
+                    .collect(),
+            )),
+        }
+    }
+}
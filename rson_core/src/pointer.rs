@@ -0,0 +1,268 @@
+use crate::error::{Error, Result};
+use crate::value::{Literal, Map, RsonMap, Value};
+
+/// How many `null`s `Value::set_at` and `Value::get_or_insert_with` may
+/// pad an array with, so an index like `/1000000000000` fails instead of
+/// trying to allocate terabytes.
+pub const MAX_ARRAY_PADDING: usize = 1024;
+
+impl Value {
+    /// Looks up a value by JSON Pointer (RFC 6901), e.g. `/users/0/name`.
+    /// `~1` and `~0` in a segment stand for `/` and `~`. The empty pointer
+    /// is the value itself.
+    pub fn pointer(&self, pointer: &str) -> Option<&Value> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        if !pointer.starts_with('/') {
+            return None;
+        }
+        pointer
+            .split('/')
+            .skip(1)
+            .map(unescape)
+            .try_fold(self, |value, segment| match value {
+                Value::Object(map) => map.0.get(&segment),
+                Value::Array(array) => index(&segment).and_then(|i| array.get(i)),
+                _ => None,
+            })
+    }
+
+    /// Like `pointer`, but returns a mutable reference.
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Value> {
+        if pointer.is_empty() {
+            return Some(self);
+        }
+        if !pointer.starts_with('/') {
+            return None;
+        }
+        pointer
+            .split('/')
+            .skip(1)
+            .map(unescape)
+            .try_fold(self, |value, segment| match value {
+                Value::Object(map) => map.0.get_mut(&segment),
+                Value::Array(array) => index(&segment).and_then(move |i| array.get_mut(i)),
+                _ => None,
+            })
+    }
+
+    /// Sets the value at `pointer`, creating the objects and arrays on the
+    /// way to it, and returns the value it replaced:
+    ///
+    ///     config.set_at("/server/listeners/0/port", 8080)?;
+    ///
+    /// A missing or `null` parent becomes an array if the segment under it
+    /// is an index or `-`, and an object otherwise. An index past the end
+    /// of an array pads it with `null`s, at most `MAX_ARRAY_PADDING` of
+    /// them, and `-` appends. Fails without changing anything if a parent
+    /// is a string, number or boolean, an array is indexed by something
+    /// other than a number or the padding would be longer.
+    pub fn set_at<V: Into<Value>>(&mut self, pointer: &str, value: V) -> Result<Option<Value>> {
+        let (slot, created) = self.create_path(pointer)?;
+        let previous = std::mem::replace(slot, value.into());
+        Ok(if created { None } else { Some(previous) })
+    }
+
+    /// Returns the value at `pointer`, first setting it to `f()` like
+    /// `set_at` if there is none. A `null` counts as a value.
+    pub fn get_or_insert_with<F: FnOnce() -> Value>(
+        &mut self,
+        pointer: &str,
+        f: F,
+    ) -> Result<&mut Value> {
+        let (slot, created) = self.create_path(pointer)?;
+        if created {
+            *slot = f();
+        }
+        Ok(slot)
+    }
+
+    /// Walks to `pointer`, creating what's missing on the way, with the
+    /// last value a new `null` if it didn't exist.
+    fn create_path(&mut self, pointer: &str) -> Result<(&mut Value, bool)> {
+        if pointer.is_empty() {
+            return Ok((self, false));
+        }
+        if !pointer.starts_with('/') {
+            return Err(
+                Error::custom("a JSON Pointer must be empty or start with `/`").at(pointer),
+            );
+        }
+
+        self.check_padding(pointer)?;
+
+        let mut value = self;
+        let mut created = false;
+        let mut parent = 0;
+        for raw in pointer.split('/').skip(1) {
+            let segment = unescape(raw);
+            if *value == Value::Literal(Literal::Null) {
+                *value = if segment == "-" || index(&segment).is_some() {
+                    Value::Array(Vec::new())
+                } else {
+                    Value::Object(RsonMap(Map::new()))
+                };
+            }
+            value = match value {
+                Value::Object(map) => {
+                    created = !map.0.contains_key(&segment);
+                    map.0
+                        .entry(segment)
+                        .or_insert(Value::Literal(Literal::Null))
+                }
+                Value::Array(array) => {
+                    let i = match segment.as_str() {
+                        "-" => array.len(),
+                        _ => index(&segment).ok_or_else(|| {
+                            Error::custom(format!("`{}` is not an array index", segment))
+                                .at(&pointer[..parent])
+                        })?,
+                    };
+                    created = i >= array.len();
+                    if created {
+                        let len = i.checked_add(1).ok_or_else(|| {
+                            Error::custom("array index overflow").at(&pointer[..parent])
+                        })?;
+                        array.resize(len, Value::Literal(Literal::Null));
+                    }
+                    &mut array[i]
+                }
+                other => {
+                    return Err(invalid_type(other, "an object or array").at(&pointer[..parent]))
+                }
+            };
+            parent += raw.len() + 1;
+        }
+        Ok((value, created))
+    }
+
+    /// Fails if `create_path` would pad an array on the way to `pointer`
+    /// with more than `MAX_ARRAY_PADDING` `null`s, before it changes
+    /// anything.
+    fn check_padding(&self, pointer: &str) -> Result<()> {
+        let mut value = Some(self);
+        let mut parent = 0;
+        for raw in pointer.split('/').skip(1) {
+            let segment = unescape(raw);
+            // Missing values and `null`s become arrays for an index.
+            let len = match value {
+                Some(Value::Array(array)) => Some(array.len()),
+                Some(Value::Literal(Literal::Null)) | None => Some(0),
+                _ => None,
+            };
+            if let (Some(len), Some(i)) = (len, index(&segment)) {
+                if i.saturating_sub(len) > MAX_ARRAY_PADDING {
+                    return Err(Error::custom(format!(
+                        "index {} is more than {} past the end of the array",
+                        i, MAX_ARRAY_PADDING
+                    ))
+                    .at(&pointer[..parent]));
+                }
+            }
+            value = match value {
+                Some(Value::Object(map)) => map.0.get(&segment),
+                Some(Value::Array(array)) => index(&segment).and_then(|i| array.get(i)),
+                _ => None,
+            };
+            parent += raw.len() + 1;
+        }
+        Ok(())
+    }
+
+    /// Returns the value at `pointer`, or an error naming the pointer.
+    pub fn expect(&self, pointer: &str) -> Result<&Value> {
+        self.pointer(pointer)
+            .ok_or_else(|| Error::custom("no such value").at(pointer))
+    }
+
+    /// Returns the string at `pointer`.
+    pub fn expect_str(&self, pointer: &str) -> Result<&str> {
+        match self.expect(pointer)? {
+            Value::String(s) => Ok(s),
+            other => Err(invalid_type(other, "a string").at(pointer)),
+        }
+    }
+
+    /// Returns the boolean at `pointer`.
+    pub fn expect_bool(&self, pointer: &str) -> Result<bool> {
+        match self.expect(pointer)? {
+            Value::Literal(Literal::Bool(b)) => Ok(*b),
+            other => Err(invalid_type(other, "a boolean").at(pointer)),
+        }
+    }
+
+    /// Returns the number at `pointer` as an `i64`, failing for fractions
+    /// and numbers out of range.
+    pub fn expect_i64(&self, pointer: &str) -> Result<i64> {
+        self.expect_number(pointer, "an i64")
+    }
+
+    /// Returns the number at `pointer` as a `u64`, failing for negative
+    /// numbers, fractions and numbers out of range.
+    pub fn expect_u64(&self, pointer: &str) -> Result<u64> {
+        self.expect_number(pointer, "a u64")
+    }
+
+    /// Returns the number at `pointer` as an `f64`.
+    pub fn expect_f64(&self, pointer: &str) -> Result<f64> {
+        self.expect_number(pointer, "an f64")
+    }
+
+    /// Returns the array at `pointer`, which must have at least `min_len`
+    /// elements.
+    pub fn expect_array(&self, pointer: &str, min_len: usize) -> Result<&Vec<Value>> {
+        match self.expect(pointer)? {
+            Value::Array(array) if array.len() >= min_len => Ok(array),
+            Value::Array(array) => Err(Error::invalid_length(
+                array.len(),
+                &format!(
+                    "at least {} element{}",
+                    min_len,
+                    if min_len == 1 { "" } else { "s" }
+                ),
+            )
+            .at(pointer)),
+            other => Err(invalid_type(other, "an array").at(pointer)),
+        }
+    }
+
+    /// Returns the object at `pointer`.
+    pub fn expect_object(&self, pointer: &str) -> Result<&RsonMap<String, Value>> {
+        match self.expect(pointer)? {
+            Value::Object(map) => Ok(map),
+            other => Err(invalid_type(other, "an object").at(pointer)),
+        }
+    }
+
+    fn expect_number<T: std::str::FromStr>(&self, pointer: &str, expected: &str) -> Result<T> {
+        match self.expect(pointer)? {
+            Value::Number(n) => n.as_str().parse().map_err(|_| {
+                Error::invalid_value(&format!("number `{}`", n.as_str()), expected).at(pointer)
+            }),
+            other => Err(invalid_type(other, expected).at(pointer)),
+        }
+    }
+}
+
+fn invalid_type(value: &Value, expected: &str) -> Error {
+    Error::invalid_type(&value.unexpected(), expected)
+}
+
+pub(crate) fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+pub(crate) fn unescape(segment: &str) -> String {
+    segment.replace("~1", "/").replace("~0", "~")
+}
+
+/// Array indices are plain decimal, without leading zeros or a sign.
+pub(crate) fn index(segment: &str) -> Option<usize> {
+    let canonical = segment == "0" || !segment.starts_with('0');
+    if canonical && !segment.is_empty() && segment.bytes().all(|b| b.is_ascii_digit()) {
+        segment.parse().ok()
+    } else {
+        None
+    }
+}
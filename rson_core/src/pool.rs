@@ -0,0 +1,72 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Hands out one shared allocation per distinct string, for documents
+/// full of repeated values like `"status": "ok"`. Pass the same pool to
+/// `Rson::try_from_reader_pooled` or `Value::dedup_with` for every
+/// document of a dataset to share strings across all of them, or let
+/// `Value::dedup` use a fresh one per document.
+#[derive(Debug, Default)]
+pub struct StringPool {
+    strings: HashSet<Arc<str>>,
+    stats: PoolStats,
+}
+
+/// How well a `StringPool` is doing.
+#[derive(Debug, Default, Eq, PartialEq, Copy, Clone)]
+pub struct PoolStats {
+    /// Strings looked up.
+    pub lookups: usize,
+    /// Lookups that found the string already in the pool.
+    pub hits: usize,
+    /// String bytes not allocated thanks to hits.
+    pub bytes_saved: usize,
+}
+
+impl PoolStats {
+    /// The share of lookups that were hits, from 0 to 1.
+    pub fn hit_rate(&self) -> f64 {
+        if self.lookups == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / self.lookups as f64
+    }
+}
+
+impl StringPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the pool's copy of `s`, adding it on first sight.
+    pub fn intern(&mut self, s: &str) -> Arc<str> {
+        self.stats.lookups += 1;
+        if let Some(shared) = self.strings.get(s) {
+            self.stats.hits += 1;
+            self.stats.bytes_saved += s.len();
+            return shared.clone();
+        }
+        let shared: Arc<str> = Arc::from(s);
+        self.strings.insert(shared.clone());
+        shared
+    }
+
+    /// Distinct strings in the pool.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+
+    pub fn stats(&self) -> PoolStats {
+        self.stats
+    }
+
+    /// Drops the pool's references, e.g. between batches, so strings no
+    /// document uses anymore are freed. The stats are kept.
+    pub fn clear(&mut self) {
+        self.strings.clear();
+    }
+}
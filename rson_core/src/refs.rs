@@ -0,0 +1,169 @@
+use crate::error::{Error, Result};
+use crate::value::{Literal, RsonMap, Value};
+use std::mem;
+
+/// How many bytes the result of `Value::resolve_refs` may take up.
+/// References that point to other references can multiply a small
+/// document: 30 definitions each using the next one twice expand to 2^30
+/// values, and 19 of them over a 1 MB string to half a terabyte.
+pub const MAX_RESOLVED_BYTES: usize = 64 << 20;
+
+impl Value {
+    /// Returns a copy of the document with every internal reference,
+    /// `{"$ref": "#/definitions/x"}`, replaced by the value it points to,
+    /// as found in JSON Schema and OpenAPI documents. The fragment after
+    /// `#` is a JSON Pointer into `self`; other members next to `$ref` are
+    /// dropped, as draft-07 ignores them. References to other documents
+    /// are left alone.
+    ///
+    /// A reference whose target doesn't exist, or that leads back to
+    /// itself and so can't be written out as a finite tree, makes this
+    /// fail with an error listing all such references. So does a result
+    /// of more than `MAX_RESOLVED_BYTES` bytes.
+    pub fn resolve_refs(&self) -> Result<Value> {
+        self.resolve_refs_with_limit(MAX_RESOLVED_BYTES)
+    }
+
+    /// Like `resolve_refs`, failing once the result would take up more
+    /// than `max_bytes` bytes: `size_of::<Value>()` for every value plus
+    /// the length of every string, number and key.
+    pub fn resolve_refs_with_limit(&self, max_bytes: usize) -> Result<Value> {
+        let mut resolver = Resolver {
+            root: self,
+            stack: vec![],
+            problems: vec![],
+            budget: max_bytes,
+            exhausted: false,
+        };
+        let resolved = resolver.resolve(self);
+        if resolver.exhausted {
+            return Err(Error::custom(format!(
+                "resolving $refs produces more than {} bytes",
+                max_bytes
+            )));
+        }
+        if resolver.problems.is_empty() {
+            Ok(resolved)
+        } else {
+            Err(Error::custom(format!(
+                "unresolved $ref: {}",
+                resolver.problems.join("; ")
+            )))
+        }
+    }
+}
+
+struct Resolver<'a> {
+    root: &'a Value,
+    /// The references being expanded, outermost first.
+    stack: Vec<&'a str>,
+    problems: Vec<String>,
+    /// How many more bytes may be produced.
+    budget: usize,
+    /// Set when the budget ran out, which stops resolving.
+    exhausted: bool,
+}
+
+impl<'a> Resolver<'a> {
+    fn resolve(&mut self, value: &'a Value) -> Value {
+        if internal_ref(value).is_none() {
+            let text = match value {
+                Value::String(s) => s.len(),
+                Value::Number(n) => n.as_str().len(),
+                _ => 0,
+            };
+            self.charge(mem::size_of::<Value>() + text);
+        }
+        if self.exhausted {
+            return Value::Literal(Literal::Null);
+        }
+        match value {
+            Value::Object(map) => match internal_ref(value) {
+                Some(reference) => self.follow(reference, value),
+                None => Value::Object(RsonMap(
+                    map.0
+                        .iter()
+                        .map(|(k, v)| {
+                            self.charge(k.len());
+                            (k.clone(), self.resolve(v))
+                        })
+                        .collect(),
+                )),
+            },
+            Value::Array(array) => Value::Array(array.iter().map(|v| self.resolve(v)).collect()),
+            other => other.clone(),
+        }
+    }
+
+    /// Takes `bytes` from the budget, noting when it runs out.
+    fn charge(&mut self, bytes: usize) {
+        match self.budget.checked_sub(bytes) {
+            Some(left) => self.budget = left,
+            None => self.exhausted = true,
+        }
+    }
+
+    /// Resolves the target of `reference`, leaving `value`, the object
+    /// holding it, in place when that fails.
+    fn follow(&mut self, reference: &'a str, value: &'a Value) -> Value {
+        if let Some(start) = self.stack.iter().position(|r| *r == reference) {
+            let mut cycle: Vec<String> = self.stack[start..]
+                .iter()
+                .map(|r| format!("`{}`", r))
+                .collect();
+            cycle.push(format!("`{}`", reference));
+            self.problem(format!("cycle {}", cycle.join(" -> ")));
+            return value.clone();
+        }
+
+        let target = percent_decode(&reference[1..]).and_then(|p| self.root.pointer(&p));
+        match target {
+            Some(target) => {
+                self.stack.push(reference);
+                let resolved = self.resolve(target);
+                self.stack.pop();
+                resolved
+            }
+            None => {
+                self.problem(format!("`{}` does not exist", reference));
+                value.clone()
+            }
+        }
+    }
+
+    /// Records a problem once, however many times it is reached.
+    fn problem(&mut self, problem: String) {
+        if !self.problems.contains(&problem) {
+            self.problems.push(problem);
+        }
+    }
+}
+
+/// The `$ref` of `value` if it is a reference within the same document.
+fn internal_ref(value: &Value) -> Option<&str> {
+    match value {
+        Value::Object(map) => match map.0.get("$ref") {
+            Some(Value::String(reference)) if reference.starts_with('#') => Some(reference),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Undoes the `%XX` escapes a URI fragment may use, e.g. `%25` for `%`.
+fn percent_decode(fragment: &str) -> Option<String> {
+    let bytes = fragment.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = fragment.get(i + 1..i + 3)?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(decoded).ok()
+}
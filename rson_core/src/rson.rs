@@ -0,0 +1,1101 @@
+use crate::dedup::SharedValue;
+use crate::error::{Error, ErrorKind, Result, Suggestion};
+use crate::instrument::{self, Timer};
+use crate::partial::{PartialValue, RawValue};
+use crate::pointer::escape;
+use crate::pool::StringPool;
+use crate::value::{
+    Literal, Map, Number, RsonMap, StructuralChar, Value, CARRIAGE_RETURN, NEW_LINE, SPACE, TAB,
+};
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::io::{self, BufReader, Read};
+use std::str::FromStr;
+use std::sync::Arc;
+
+/// How deeply arrays and objects may nest before parsing gives up, which
+/// keeps hostile input from overflowing the stack here or in the
+/// recursive code that later walks the value.
+pub const MAX_DEPTH: usize = 128;
+
+/// How forgiving the parser is. The default accepts some input RFC 8259
+/// doesn't; `strict` sticks to the grammar.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct ParseOptions {
+    /// Only space, tab, line feed and carriage return separate tokens,
+    /// commas must separate members and elements but not follow the last
+    /// one, control characters in strings must be escaped and an object
+    /// can't repeat a key. Otherwise any Unicode whitespace and a byte
+    /// order mark separate tokens too, commas are optional, control
+    /// characters are kept as they are and the last of repeated keys wins.
+    pub strict: bool,
+    /// Accept numbers like `+1`, `0123`, `.5` and `1.`, stored as `1`,
+    /// `123`, `0.5` and `1.0`. Numbers missing exponent digits, like `1e`,
+    /// are rejected regardless.
+    pub relaxed_numbers: bool,
+    /// Parse empty or whitespace-only input as `null` instead of failing
+    /// with `ErrorKind::EmptyInput`, like an HTTP body that was left out.
+    pub empty_as_null: bool,
+}
+
+impl ParseOptions {
+    /// Options that only accept RFC 8259 JSON.
+    pub fn strict() -> Self {
+        Self {
+            strict: true,
+            ..Self::default()
+        }
+    }
+}
+
+pub struct Rson<'a, R> {
+    names: HashSet<&'a str>,
+    options: ParseOptions,
+    reader: BufReader<R>,
+    /// Reused for the parts of literals and numbers.
+    scratch: String,
+    /// Reused for the contents of strings, which are then copied out at
+    /// their final size.
+    string_scratch: String,
+    look: Option<char>,
+    depth: usize,
+    /// Position of `look` in the input, counting from 1.
+    line: usize,
+    column: usize,
+    /// Bytes read from the current document.
+    bytes: usize,
+    /// Where lenient input departed from strict JSON, when asked for by
+    /// `strict_violations`.
+    violations: Option<Vec<Error>>,
+    /// Errors recovered from, for `validate_all`.
+    errors: Option<Vec<Error>>,
+}
+
+impl Rson<'static, io::Empty> {
+    /// A parser for many documents with the same options, like small
+    /// messages in a hot loop. It keeps its buffers from one document to
+    /// the next instead of allocating them for each.
+    ///
+    ///     let mut parser = Rson::with_options(ParseOptions::strict());
+    ///     for message in messages {
+    ///         handle(parser.parse_str(message)?);
+    ///     }
+    pub fn with_options(options: ParseOptions) -> Self {
+        Self::with_reader(BufReader::with_capacity(0, io::empty()), options)
+    }
+
+    pub fn parse_str(&mut self, text: &str) -> Result<Value> {
+        self.parse_slice(text.as_bytes())
+    }
+
+    /// Like `parse_str`, for bytes that should be UTF-8. They are read in
+    /// place, not copied.
+    pub fn parse_slice(&mut self, bytes: &[u8]) -> Result<Value> {
+        // No buffering: the input is already in memory.
+        let reader = BufReader::with_capacity(0, bytes);
+        let mut rson = Rson::with_reader(reader, self.options.clone());
+        rson.lend(self);
+        let result = rson.document(Rson::root);
+        rson.lend(self);
+        result
+    }
+}
+
+impl Value {
+    /// Checks that this value is strict RFC 8259 JSON as well, which comes
+    /// down to its numbers: those made with `Number::new` may hold any
+    /// text, like `NaN`, `0x1F` or `+1`. The error is at the first such
+    /// number, visiting object members in key order. To check the text a
+    /// value was parsed from, see `Rson::strict_violations`.
+    pub fn validate_strict(&self) -> Result<()> {
+        validate_strict_at(self, &mut String::new())
+    }
+}
+
+fn validate_strict_at(value: &Value, pointer: &mut String) -> Result<()> {
+    let len = pointer.len();
+    match value {
+        Value::Number(n) if !is_strict_number(n.as_str()) => {
+            return Err(Error::invalid_value(
+                &format!("number `{}`", n.as_str()),
+                "an RFC 8259 number",
+            )
+            .at(pointer));
+        }
+        Value::Array(array) => {
+            for (i, element) in array.iter().enumerate() {
+                pointer.push_str(&format!("/{}", i));
+                validate_strict_at(element, pointer)?;
+                pointer.truncate(len);
+            }
+        }
+        Value::Object(map) => {
+            for (key, member) in map.iter_sorted() {
+                pointer.push('/');
+                pointer.push_str(&escape(key));
+                validate_strict_at(member, pointer)?;
+                pointer.truncate(len);
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Whether `text` is a number exactly as RFC 8259 writes them.
+fn is_strict_number(text: &str) -> bool {
+    let digits = |b: &[u8]| b.iter().take_while(|c| c.is_ascii_digit()).count();
+    let b = text.strip_prefix('-').unwrap_or(text).as_bytes();
+    let int = digits(b);
+    if int == 0 || (b[0] == b'0' && int > 1) {
+        return false;
+    }
+    let mut rest = &b[int..];
+    if let Some(frac) = rest.strip_prefix(b".") {
+        let len = digits(frac);
+        if len == 0 {
+            return false;
+        }
+        rest = &frac[len..];
+    }
+    if let [b'e' | b'E', exp @ ..] = rest {
+        let exp = exp
+            .strip_prefix(b"+")
+            .or(exp.strip_prefix(b"-"))
+            .unwrap_or(exp);
+        let len = digits(exp);
+        if len == 0 {
+            return false;
+        }
+        rest = &exp[len..];
+    }
+    rest.is_empty()
+}
+
+impl<R: Read> Rson<'_, R> {
+    /// Parses a JSON document, panicking if it is invalid. See
+    /// `try_from_reader` for the error.
+    pub fn from_reader(buf: R) -> Value {
+        Self::try_from_reader(buf).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Parses a single JSON document. Errors carry an `ErrorKind` and the
+    /// line and column where parsing stopped.
+    pub fn try_from_reader(buf: R) -> Result<Value> {
+        Self::try_from_reader_with(buf, ParseOptions::default())
+    }
+
+    /// Like `try_from_reader`, but not counted in the usage metrics: for
+    /// parts of a document already counted and for snippets that aren't
+    /// documents of their own, like a key or a filter's literal.
+    #[doc(hidden)]
+    pub fn try_from_reader_unrecorded(buf: R) -> Result<Value> {
+        Self::new(buf, ParseOptions::default()).unrecorded(Self::root)
+    }
+
+    /// Like `try_from_reader`, with control over what input is accepted.
+    pub fn try_from_reader_with(buf: R, options: ParseOptions) -> Result<Value> {
+        Self::new(buf, options).document(Self::root)
+    }
+
+    /// Like `try_from_reader_with`, keeping object keys as `K` instead of
+    /// `String`: a `Box<str>` or `Arc<str>` is smaller, or an interned
+    /// symbol type with `From<String>`, for documents with millions of
+    /// small objects.
+    ///
+    ///     let value: Value<Box<str>> = Rson::try_from_reader_keyed(file, options)?;
+    pub fn try_from_reader_keyed<K>(buf: R, options: ParseOptions) -> Result<Value<K>>
+    where
+        K: From<String> + Hash + Ord,
+    {
+        Self::new(buf, options).document(Self::root)
+    }
+
+    /// Parses only the top `depth` levels of arrays and objects, keeping
+    /// the ones nested deeper as unparsed `RawValue`s, for a quick look at
+    /// the shape of an enormous document. Raw values are only checked for
+    /// matching brackets and closed strings, and can be parsed later on
+    /// their own. `depth` 0 keeps a top-level array or object raw.
+    pub fn parse_to_depth(buf: R, depth: usize) -> Result<PartialValue> {
+        Self::new(buf, ParseOptions::default()).document(|rson| rson.partial(depth))
+    }
+
+    /// Parses straight into a `SharedValue`, taking every string value
+    /// from `pool` as it is read, so repeated values like `"status": "ok"`
+    /// share one allocation and never get a `String` of their own. Pass
+    /// the same pool for every document of a dataset; its stats show the
+    /// hit rate. Identical subtrees aren't shared, see `Value::dedup_with`.
+    ///
+    ///     let record = Rson::try_from_reader_pooled(line, options, &mut pool)?;
+    pub fn try_from_reader_pooled(
+        buf: R,
+        options: ParseOptions,
+        pool: &mut StringPool,
+    ) -> Result<Arc<SharedValue>> {
+        let root = |rson: &mut Self| match rson.look {
+            None => Ok(SharedValue::Literal(Literal::Null)),
+            Some(_) => rson.pooled(pool),
+        };
+        Self::new(buf, options).document(root).map(Arc::new)
+    }
+
+    /// Backs `RawValue::parse_to_depth`, whose text was counted with the
+    /// document it came from.
+    pub(crate) fn parse_to_depth_unrecorded(buf: R, depth: usize) -> Result<PartialValue> {
+        Self::new(buf, ParseOptions::default()).unrecorded(|rson| rson.partial(depth))
+    }
+
+    /// Parses a document leniently and lists every place where it isn't
+    /// strict RFC 8259 JSON: missing and trailing commas, whitespace
+    /// other than the four RFC characters, raw control characters in
+    /// strings, relaxed numbers and repeated object keys. Each is the error
+    /// `ParseOptions::strict()` would stop at, with its line and column.
+    /// For linting configs that other parsers have to read:
+    ///
+    ///     for violation in Rson::strict_violations(file)? {
+    ///         eprintln!("{}: {}", path, violation);
+    ///     }
+    ///
+    /// Input that not even the lenient parser accepts is an error.
+    pub fn strict_violations(buf: R) -> Result<Vec<Error>> {
+        let options = ParseOptions {
+            relaxed_numbers: true,
+            ..ParseOptions::default()
+        };
+        let mut rson = Self::new(buf, options);
+        rson.violations = Some(vec![]);
+        rson.document(Self::parse::<String>)?;
+        Ok(rson.violations.unwrap_or_default())
+    }
+
+    /// Backs `validate_all`: parses like `strict_violations` but goes on
+    /// after errors in members and elements. Returns the errors, including
+    /// the one that ended parsing if any, and the strict violations.
+    pub(crate) fn check_all(buf: R) -> (Vec<Error>, Vec<Error>) {
+        let options = ParseOptions {
+            relaxed_numbers: true,
+            ..ParseOptions::default()
+        };
+        let mut rson = Self::new(buf, options);
+        rson.violations = Some(vec![]);
+        rson.errors = Some(vec![]);
+        let result = rson.document(Self::parse::<String>);
+        let mut errors = rson.errors.unwrap_or_default();
+        if let Err(e) = result {
+            errors.push(e);
+        }
+        (errors, rson.violations.unwrap_or_default())
+    }
+
+    fn new(buf: R, options: ParseOptions) -> Self {
+        Self::with_reader(BufReader::new(buf), options)
+    }
+
+    fn with_reader(reader: BufReader<R>, options: ParseOptions) -> Self {
+        Self {
+            names: HashSet::new(),
+            options,
+            reader,
+            scratch: String::new(),
+            string_scratch: String::new(),
+            look: None,
+            depth: 0,
+            line: 1,
+            column: 0,
+            bytes: 0,
+            violations: None,
+            errors: None,
+        }
+    }
+
+    /// Swaps the reusable buffers with those of `other`.
+    fn lend<S>(&mut self, other: &mut Rson<'_, S>) {
+        std::mem::swap(&mut self.scratch, &mut other.scratch);
+        std::mem::swap(&mut self.string_scratch, &mut other.string_scratch);
+    }
+
+    /// Parses one whole document with `parse`, recording it in the
+    /// usage metrics.
+    fn document<T>(&mut self, parse: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        let timer = Timer::start();
+        let result = self.unrecorded(parse);
+        instrument::parsed(&result, self.bytes, timer);
+        result
+    }
+
+    /// Parses one whole document with `parse`, leaving the metrics alone.
+    fn unrecorded<T>(&mut self, parse: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+        self.start()
+            .and_then(|()| parse(self))
+            .and_then(|value| self.end(value))
+    }
+
+    /// Reads up to the first token of a document, failing on empty input
+    /// unless `empty_as_null`. That takes one look past the leading
+    /// whitespace; nothing is buffered or read twice.
+    fn start(&mut self) -> Result<()> {
+        self.look = None;
+        self.depth = 0;
+        self.line = 1;
+        self.column = 0;
+        self.bytes = 0;
+        self.bump()?;
+        self.skip_white()?;
+        if self.look.is_none() && !self.options.empty_as_null {
+            return Err(self.error(ErrorKind::EmptyInput, "empty input"));
+        }
+        Ok(())
+    }
+
+    /// Parses the value of a whole document, which `start` only lets be
+    /// missing with `empty_as_null`.
+    fn root<K: From<String> + Hash + Ord>(&mut self) -> Result<Value<K>> {
+        match self.look {
+            None => Ok(Value::Literal(Literal::Null)),
+            Some(_) => self.parse(),
+        }
+    }
+
+    /// Checks that nothing but whitespace follows the document.
+    fn end<T>(&self, value: T) -> Result<T> {
+        match self.look {
+            Some(c) => Err(self.error(
+                ErrorKind::TrailingCharacters,
+                format!("trailing characters starting with `{}`", c),
+            )),
+            None => Ok(value),
+        }
+    }
+
+    fn from_str(text: &str) -> Value {
+        Rson::from_reader(text.as_bytes())
+    }
+
+    /// An error at the lookahead character.
+    fn error<T: std::fmt::Display>(&self, kind: ErrorKind, message: T) -> Error {
+        Error::syntax(kind, message, self.line, self.column)
+    }
+
+    /// Moves the lookahead to the next character.
+    fn bump(&mut self) -> Result<()> {
+        if self.look == Some(NEW_LINE) {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        self.look = self.get_char()?;
+        Ok(())
+    }
+
+    /// Decodes the next UTF-8 character of the input.
+    fn get_char(&mut self) -> Result<Option<char>> {
+        let first = match self.get_byte()? {
+            Some(byte) => byte,
+            None => return Ok(None),
+        };
+        let len = match first {
+            0x00..=0x7f => return Ok(Some(first as char)),
+            0xc0..=0xdf => 2,
+            0xe0..=0xef => 3,
+            0xf0..=0xf7 => 4,
+            _ => return Err(self.error(ErrorKind::InvalidUtf8, "invalid UTF-8")),
+        };
+
+        let mut buf = [first, 0, 0, 0];
+        for byte in &mut buf[1..len] {
+            *byte = self
+                .get_byte()?
+                .ok_or_else(|| self.error(ErrorKind::UnexpectedEof, "EOF in a UTF-8 sequence"))?;
+        }
+        std::str::from_utf8(&buf[..len])
+            .ok()
+            .and_then(|s| s.chars().next())
+            .map(Some)
+            .ok_or_else(|| self.error(ErrorKind::InvalidUtf8, "invalid UTF-8"))
+    }
+
+    fn get_byte(&mut self) -> Result<Option<u8>> {
+        let mut byte = [0];
+        loop {
+            match self.reader.read(&mut byte) {
+                Ok(0) => return Ok(None),
+                Ok(_) => {
+                    self.bytes += 1;
+                    return Ok(Some(byte[0]));
+                }
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(Error::io(e)),
+            }
+        }
+    }
+
+    /// Skip over leading White Space
+    fn skip_white(&mut self) -> Result<()> {
+        while self.is_white() {
+            if let Some(c) = self
+                .look
+                .filter(|c| ![TAB, SPACE, NEW_LINE, CARRIAGE_RETURN].contains(c))
+            {
+                self.relax(true, |rson| {
+                    rson.error(
+                        ErrorKind::UnexpectedToken,
+                        format!("{:?} is not JSON whitespace", c),
+                    )
+                })?;
+            }
+            self.bump()?;
+        }
+        Ok(())
+    }
+
+    /// Handles input strict JSON doesn't allow: `error` if it isn't
+    /// `allowed`, otherwise noted for `strict_violations`.
+    fn relax(&mut self, allowed: bool, error: impl FnOnce(&Self) -> Error) -> Result<()> {
+        if !allowed {
+            return Err(error(self));
+        }
+        if let Some(mut violations) = self.violations.take() {
+            violations.push(error(self));
+            self.violations = Some(violations);
+        }
+        Ok(())
+    }
+
+    /// Returns true if the lookahead character is whitespace: TAB,
+    /// SPACE, NEW_LINE or CARRIAGE_RETURN, or when not strict any other
+    /// Unicode whitespace or a byte order mark.
+    fn is_white(&mut self) -> bool {
+        match self.look {
+            Some(TAB) | Some(SPACE) | Some(NEW_LINE) | Some(CARRIAGE_RETURN) => true,
+            Some(c) if !self.options.strict => c.is_whitespace() || c == '\u{feff}',
+            _ => false,
+        }
+    }
+
+    fn match_char<T: Into<char>>(&mut self, x: T) -> Result<()> {
+        let c: char = x.into();
+        match self.look {
+            Some(look) if look != c => {
+                return Err(self.error(
+                    ErrorKind::UnexpectedToken,
+                    format!("Look: `{}`, Expected: `{}`", look, c),
+                ))
+            }
+            Some(_) => {}
+            None => {
+                return Err(self.error(
+                    ErrorKind::UnexpectedEof,
+                    format!("EOF while expecting `{}`", c),
+                ))
+            }
+        }
+        self.bump()?;
+        self.skip_white()
+    }
+
+    fn accept(&mut self, x: char) -> bool {
+        if let Some(look) = self.look {
+            if x == look {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Reads up to the next structural character or whitespace into
+    /// `scratch`.
+    fn get_token(&mut self) -> Result<()> {
+        self.scratch.clear();
+        while let Some(c) = self.look {
+            if !StructuralChar::iter().any(|&sc| {
+                let x: char = sc.into();
+                x == c
+            }) && !self.is_white()
+            {
+                self.scratch.push(c);
+            } else {
+                break;
+            }
+            self.bump()?;
+        }
+
+        Ok(())
+    }
+
+    fn parse<K: From<String> + Hash + Ord>(&mut self) -> Result<Value<K>> {
+        if self.look.is_none() {
+            return Err(self.error(ErrorKind::UnexpectedEof, "EOF while parsing a value"));
+        }
+
+        // recognize string
+        if self.accept(StructuralChar::QuotationMark.into()) {
+            return self.string();
+        }
+
+        // recognize array
+        if self.accept(StructuralChar::BeginArray.into()) {
+            return self.array();
+        }
+
+        // recognize object
+        if self.accept(StructuralChar::BeginObject.into()) {
+            return self.object();
+        }
+
+        if let Some(c) = self.look {
+            if c.is_ascii_digit() || ['-', '+', '.'].contains(&c) {
+                return self.number();
+            }
+        }
+
+        self.literal()
+    }
+
+    /// Like `parse`, for `parse_to_depth`.
+    fn partial(&mut self, limit: usize) -> Result<PartialValue> {
+        let array = self.accept(StructuralChar::BeginArray.into());
+        let object = self.accept(StructuralChar::BeginObject.into());
+        if (array || object) && self.depth >= limit {
+            return self.raw().map(PartialValue::Raw);
+        }
+
+        if array {
+            self.enter()?;
+            let array = self.elements(|rson| rson.partial(limit))?;
+            self.depth -= 1;
+            Ok(PartialValue::Array(array))
+        } else if object {
+            self.enter()?;
+            let map = self.members(|rson| rson.partial(limit))?;
+            self.depth -= 1;
+            Ok(PartialValue::Object(map))
+        } else {
+            self.parse().map(PartialValue::from)
+        }
+    }
+
+    /// Like `parse`, for `try_from_reader_pooled`.
+    fn pooled(&mut self, pool: &mut StringPool) -> Result<SharedValue> {
+        if self.accept(StructuralChar::QuotationMark.into()) {
+            self.text_with(|s| pool.intern(s)).map(SharedValue::String)
+        } else if self.accept(StructuralChar::BeginArray.into()) {
+            self.enter()?;
+            let array = self.elements(|rson| rson.pooled(pool).map(Arc::new))?;
+            self.depth -= 1;
+            Ok(SharedValue::Array(array))
+        } else if self.accept(StructuralChar::BeginObject.into()) {
+            self.enter()?;
+            let map = self.members(|rson| rson.pooled(pool).map(Arc::new))?;
+            self.depth -= 1;
+            Ok(SharedValue::Object(map))
+        } else {
+            match self.parse::<String>()? {
+                Value::Literal(literal) => Ok(SharedValue::Literal(literal)),
+                Value::Number(n) => Ok(SharedValue::Number(n)),
+                _ => unreachable!("strings, arrays and objects are handled above"),
+            }
+        }
+    }
+
+    /// Reads the array or object at the lookahead as text, checking only
+    /// that brackets match and strings are closed.
+    fn raw(&mut self) -> Result<RawValue> {
+        let mut text = String::new();
+        let mut closers = vec![];
+        let mut in_string = false;
+        loop {
+            let c = match self.look {
+                Some(c) => c,
+                None => {
+                    return Err(self.error(ErrorKind::UnexpectedEof, "EOF while parsing a value"))
+                }
+            };
+            text.push(c);
+            match c {
+                '\\' if in_string => {
+                    self.bump()?;
+                    if let Some(escaped) = self.look {
+                        text.push(escaped);
+                    }
+                }
+                '"' => in_string = !in_string,
+                _ if in_string => {}
+                '[' => closers.push(']'),
+                '{' => closers.push('}'),
+                ']' | '}' if closers.last() == Some(&c) => {
+                    closers.pop();
+                    if closers.is_empty() {
+                        break;
+                    }
+                }
+                ']' | '}' => {
+                    return Err(self.error(
+                        ErrorKind::UnexpectedToken,
+                        format!("Look: `{}`, Expected: `{}`", c, closers[closers.len() - 1]),
+                    ))
+                }
+                _ => {}
+            }
+            self.bump()?;
+        }
+        self.bump()?;
+        self.skip_white()?;
+        Ok(RawValue::new(text))
+    }
+
+    fn object<K: From<String> + Hash + Ord>(&mut self) -> Result<Value<K>> {
+        self.enter()?;
+        let map = self.members(Self::parse)?;
+        self.depth -= 1;
+        Ok(Value::Object(map))
+    }
+
+    /// Reads the members of an object, each value with `value`.
+    fn members<K: From<String> + Hash + Ord, T>(
+        &mut self,
+        mut value: impl FnMut(&mut Self) -> Result<T>,
+    ) -> Result<RsonMap<K, T>> {
+        self.match_char(StructuralChar::BeginObject)?;
+        let mut map = RsonMap(Map::new());
+
+        while !self.accept(StructuralChar::EndObject.into()) {
+            let depth = self.depth;
+            let (line, column) = (self.line, self.column);
+            match self.member(&mut value) {
+                Ok((key, value)) => {
+                    // Later duplicates replace earlier ones unless strict.
+                    let name =
+                        (self.options.strict || self.violations.is_some()).then(|| key.clone());
+                    if map.0.insert(key.into(), value).is_some() {
+                        if let Some(name) = name {
+                            self.relax(!self.options.strict, |_| {
+                                Error::syntax(
+                                    ErrorKind::DuplicateKey,
+                                    format!("duplicate key `{}`", name),
+                                    line,
+                                    column,
+                                )
+                            })?;
+                        }
+                    }
+                }
+                Err(e) => self.recover(e, depth, StructuralChar::EndObject)?,
+            }
+            // consume ValueSeperator and continue to the next
+            // key-value pair if there is any.
+            self.separator(StructuralChar::EndObject)?;
+        }
+
+        self.match_char(StructuralChar::EndObject)?;
+        Ok(map)
+    }
+
+    /// Reads one `key: value` member of an object.
+    fn member<T>(&mut self, value: &mut impl FnMut(&mut Self) -> Result<T>) -> Result<(String, T)> {
+        let key = match self.text() {
+            Err(e) if e.kind() == ErrorKind::UnexpectedToken => {
+                return Err(e.with_suggestion(match self.look {
+                    Some('\'') => Suggestion::UseDoubleQuotes,
+                    _ => Suggestion::QuoteKey,
+                }))
+            }
+            key => key?,
+        };
+        self.match_char(StructuralChar::NameSeperator)?;
+        Ok((key, value(self)?))
+    }
+
+    /// For `validate_all`, notes an error in a member or element and skips
+    /// to the `,` or `end` after it, `depth` levels deep, so parsing can
+    /// go on. Errors there is no going on from, like the input ending,
+    /// are returned.
+    fn recover(&mut self, error: Error, depth: usize, end: StructuralChar) -> Result<()> {
+        let resumable = matches!(
+            error.kind(),
+            ErrorKind::UnexpectedToken
+                | ErrorKind::InvalidEscape
+                | ErrorKind::InvalidNumber
+                | ErrorKind::InvalidUtf8
+        );
+        let mut errors = match self.errors.take() {
+            Some(errors) if resumable => errors,
+            errors => {
+                self.errors = errors;
+                return Err(error);
+            }
+        };
+        // A bad escape leaves the lookahead inside the string.
+        let mut in_string = error.kind() == ErrorKind::InvalidEscape;
+        // An error passed up from a nested value has been noted already.
+        let position = |e: &Error| (e.line(), e.column());
+        if errors.last().map(position) != Some(position(&error)) {
+            errors.push(error);
+        }
+        self.errors = Some(errors);
+        self.depth = depth;
+
+        let end = char::from(end);
+        let mut nested = 0;
+        loop {
+            let c = match self.look {
+                Some(c) => c,
+                None => {
+                    return Err(self.error(ErrorKind::UnexpectedEof, "EOF while parsing a value"))
+                }
+            };
+            if in_string {
+                match c {
+                    '\\' => self.bump()?,
+                    '"' => in_string = false,
+                    _ => {}
+                }
+            } else {
+                match c {
+                    '"' => in_string = true,
+                    '[' | '{' => nested += 1,
+                    ']' | '}' if nested > 0 => nested -= 1,
+                    ',' if nested == 0 => break,
+                    _ if c == end => break,
+                    ']' | '}' => {
+                        return Err(self.error(
+                            ErrorKind::UnexpectedToken,
+                            format!("Look: `{}`, Expected: `{}`", c, end),
+                        ))
+                    }
+                    _ => {}
+                }
+            }
+            self.bump()?;
+        }
+        self.skip_white()
+    }
+
+    fn array<K: From<String> + Hash + Ord>(&mut self) -> Result<Value<K>> {
+        self.enter()?;
+        let array = self.elements(Self::parse)?;
+        self.depth -= 1;
+        Ok(Value::Array(array))
+    }
+
+    /// Goes one array or object deeper.
+    fn enter(&mut self) -> Result<()> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err(self.error(
+                ErrorKind::DepthLimit,
+                format!("Nesting deeper than {} levels", MAX_DEPTH),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Reads the elements of an array, each with `value`.
+    fn elements<T>(&mut self, mut value: impl FnMut(&mut Self) -> Result<T>) -> Result<Vec<T>> {
+        self.match_char(StructuralChar::BeginArray)?;
+        let mut array = vec![];
+
+        while !self.accept(StructuralChar::EndArray.into()) {
+            let depth = self.depth;
+            match value(self) {
+                Ok(value) => array.push(value),
+                Err(e) => self.recover(e, depth, StructuralChar::EndArray)?,
+            }
+            // consume ValueSeperator and continue to the next
+            // value if there is any.
+            self.separator(StructuralChar::EndArray)?;
+        }
+        self.match_char(StructuralChar::EndArray)?;
+        Ok(array)
+    }
+
+    /// Consumes the `,` after a member or element. Unless strict, it may
+    /// be left out or come before `end`.
+    fn separator(&mut self, end: StructuralChar) -> Result<()> {
+        let strict = self.options.strict;
+        if self.accept(StructuralChar::ValueSeperator.into()) {
+            self.match_char(StructuralChar::ValueSeperator)?;
+            if self.accept(end.into()) {
+                self.relax(!strict, |rson| {
+                    rson.error(ErrorKind::UnexpectedToken, "trailing comma")
+                        .with_suggestion(Suggestion::RemoveTrailingComma)
+                })?;
+            }
+        } else if self.look.is_some() && !self.accept(end.into()) {
+            self.relax(!strict, |rson| {
+                rson.error(
+                    ErrorKind::UnexpectedToken,
+                    format!("expected `,` or `{}`", char::from(end)),
+                )
+                .with_suggestion(Suggestion::InsertComma)
+            })?;
+        }
+        Ok(())
+    }
+
+    fn string<K: Hash + Ord>(&mut self) -> Result<Value<K>> {
+        self.text().map(Value::String)
+    }
+
+    /// Reads a string, for a value or a key.
+    fn text(&mut self) -> Result<String> {
+        self.text_with(str::to_owned)
+    }
+
+    /// Reads a string and hands its contents to `f`, without allocating
+    /// one of its own.
+    fn text_with<T>(&mut self, f: impl FnOnce(&str) -> T) -> Result<T> {
+        // Not `match_char`, which would skip whitespace inside the string.
+        if !self.accept(StructuralChar::QuotationMark.into()) {
+            return self
+                .match_char(StructuralChar::QuotationMark)
+                .map(|_| f(""));
+        }
+        self.bump()?;
+
+        let mut token = std::mem::take(&mut self.string_scratch);
+        token.clear();
+        let result = self.string_contents(&mut token).map(|()| f(&token));
+        self.string_scratch = token;
+        result
+    }
+
+    /// Reads the characters of a string after its opening quote into
+    /// `token`, up to and including the closing quote.
+    fn string_contents(&mut self, token: &mut String) -> Result<()> {
+        while let Some(c) = self.look {
+            if c == StructuralChar::QuotationMark.into() {
+                break;
+            }
+            if c == '\\' {
+                self.bump()?;
+                token.push(self.escape()?);
+            } else {
+                if c < ' ' {
+                    self.relax(!self.options.strict, |rson| {
+                        rson.error(
+                            ErrorKind::UnexpectedToken,
+                            format!("control character {:?} in a string", c),
+                        )
+                    })?;
+                }
+                token.push(c);
+                self.bump()?;
+            }
+        }
+
+        self.match_char(StructuralChar::QuotationMark)
+    }
+
+    /// Reads the escape sequence after a `\`.
+    fn escape(&mut self) -> Result<char> {
+        let c = match self.look {
+            Some('"') => '"',
+            Some('\\') => '\\',
+            Some('/') => '/',
+            Some('b') => '\u{8}',
+            Some('f') => '\u{c}',
+            Some('n') => '\n',
+            Some('r') => '\r',
+            Some('t') => '\t',
+            Some('u') => return self.unicode_escape(),
+            Some(c) => {
+                return Err(self.error(
+                    ErrorKind::InvalidEscape,
+                    format!("invalid escape `\\{}`", c),
+                ))
+            }
+            None => return Err(self.error(ErrorKind::UnexpectedEof, "EOF in an escape")),
+        };
+        self.bump()?;
+        Ok(c)
+    }
+
+    /// Reads `\uXXXX`, or a surrogate pair of them, from the `u` on.
+    fn unicode_escape(&mut self) -> Result<char> {
+        let high = self.hex4()?;
+        if !(0xd800..0xdc00).contains(&high) {
+            return char::from_u32(high)
+                .ok_or_else(|| self.error(ErrorKind::InvalidEscape, "unpaired surrogate"));
+        }
+
+        if !self.accept('\\') {
+            return Err(self.error(ErrorKind::InvalidEscape, "unpaired surrogate"));
+        }
+        self.bump()?;
+        if !self.accept('u') {
+            return Err(self.error(ErrorKind::InvalidEscape, "unpaired surrogate"));
+        }
+        let low = self.hex4()?;
+        if !(0xdc00..0xe000).contains(&low) {
+            return Err(self.error(ErrorKind::InvalidEscape, "unpaired surrogate"));
+        }
+        let code = 0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00);
+        Ok(char::from_u32(code).expect("surrogate pairs are valid chars"))
+    }
+
+    /// Reads the four hex digits after a `u`.
+    fn hex4(&mut self) -> Result<u32> {
+        let mut code = 0;
+        for _ in 0..4 {
+            self.bump()?;
+            let digit = match self.look {
+                Some(c) => c.to_digit(16).ok_or_else(|| {
+                    self.error(
+                        ErrorKind::InvalidEscape,
+                        "expected four hex digits after `\\u`",
+                    )
+                })?,
+                None => return Err(self.error(ErrorKind::UnexpectedEof, "EOF in an escape")),
+            };
+            code = code * 16 + digit;
+        }
+        self.bump()?;
+        Ok(code)
+    }
+
+    fn literal<K: Hash + Ord>(&mut self) -> Result<Value<K>> {
+        let (line, column) = (self.line, self.column);
+        self.get_token()?;
+        match Literal::from_str(&self.scratch) {
+            Ok(val) => {
+                self.skip_white()?;
+                Ok(Value::Literal(val))
+            }
+            // Cut off by the end of the input, e.g. `tru`.
+            Err(_)
+                if self.look.is_none()
+                    && ["null", "true", "false"]
+                        .iter()
+                        .any(|literal| literal.starts_with(self.scratch.as_str())) =>
+            {
+                Err(self.error(ErrorKind::UnexpectedEof, "EOF while parsing a literal"))
+            }
+            Err(e) => {
+                let error = Error::syntax(ErrorKind::UnexpectedToken, e, line, column);
+                let suggestion = match self.scratch.as_str() {
+                    token if token.starts_with('\'') => Some(Suggestion::UseDoubleQuotes),
+                    "True" => Some(Suggestion::UseLiteral("true")),
+                    "False" => Some(Suggestion::UseLiteral("false")),
+                    "None" => Some(Suggestion::UseLiteral("null")),
+                    _ => None,
+                };
+                Err(match suggestion {
+                    Some(suggestion) => error.with_suggestion(suggestion),
+                    None => error,
+                })
+            }
+        }
+    }
+
+    /// number = [ minus ] int [ frac ] [ exp ]
+    ///
+    /// With `relaxed_numbers`, a leading `+`, leading zeros and a missing
+    /// digit on one side of the decimal point are accepted, and the
+    /// number is kept in its RFC form: `+.5` as `0.5`, `007` as `7`.
+    fn number<K: Hash + Ord>(&mut self) -> Result<Value<K>> {
+        let relaxed = self.options.relaxed_numbers;
+        let mut token = String::new();
+
+        match self.look {
+            Some('-') => {
+                token.push('-');
+                self.bump()?;
+            }
+            Some('+') => {
+                self.relax(relaxed, |rson| {
+                    rson.error(ErrorKind::InvalidNumber, "a number can't start with `+`")
+                })?;
+                self.bump()?;
+            }
+            _ => {}
+        }
+
+        let (line, column) = (self.line, self.column);
+        self.digits()?;
+        let no_int = self.scratch.is_empty();
+        if no_int {
+            if self.look != Some('.') {
+                return Err(self.missing_digit("expected a digit"));
+            }
+            self.relax(relaxed, |rson| {
+                rson.missing_digit("expected a digit before the decimal point")
+            })?;
+            token.push('0');
+        } else if self.scratch.len() > 1 && self.scratch.starts_with('0') {
+            self.relax(relaxed, |_| {
+                Error::syntax(
+                    ErrorKind::InvalidNumber,
+                    "leading zeros are not allowed in a number",
+                    line,
+                    column + 1,
+                )
+            })?;
+            match self.scratch.trim_start_matches('0') {
+                "" => token.push('0'),
+                digits => token.push_str(digits),
+            }
+        } else {
+            token.push_str(&self.scratch);
+        }
+
+        if self.accept('.') {
+            self.bump()?;
+            self.digits()?;
+            if self.scratch.is_empty() {
+                self.relax(relaxed && !no_int, |rson| {
+                    rson.missing_digit("expected a digit after the decimal point")
+                })?;
+            }
+            token.push('.');
+            token.push_str(if self.scratch.is_empty() {
+                "0"
+            } else {
+                &self.scratch
+            });
+        }
+
+        if let Some(e @ ('e' | 'E')) = self.look {
+            token.push(e);
+            self.bump()?;
+            if let Some(sign @ ('+' | '-')) = self.look {
+                token.push(sign);
+                self.bump()?;
+            }
+            self.digits()?;
+            if self.scratch.is_empty() {
+                return Err(self.missing_digit("expected a digit in the exponent"));
+            }
+            token.push_str(&self.scratch);
+        }
+
+        if let Some(c) = self.look.filter(|c| c.is_ascii_alphanumeric() || *c == '.') {
+            return Err(self.error(
+                ErrorKind::InvalidNumber,
+                format!("unexpected character `{}` in number", c),
+            ));
+        }
+
+        self.skip_white()?;
+        Ok(Value::Number(Number::new(token)))
+    }
+
+    /// Consumes a run of ASCII digits into `scratch`.
+    fn digits(&mut self) -> Result<()> {
+        self.scratch.clear();
+        while let Some(c) = self.look.filter(char::is_ascii_digit) {
+            self.scratch.push(c);
+            self.bump()?;
+        }
+        Ok(())
+    }
+
+    /// The error for a number missing a digit at the lookahead, which is
+    /// only the end of the input if it was cut short.
+    fn missing_digit(&self, message: &str) -> Error {
+        match self.look {
+            None => self.error(ErrorKind::UnexpectedEof, "EOF while parsing a number"),
+            Some(_) => self.error(ErrorKind::InvalidNumber, message),
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use crate::error::{Error, Result};
+use crate::value::Value;
+use crate::write::{write_str, ValueWriter};
+use std::io::Write;
+
+/// Writes one JSON document piece by piece, so a large response can be
+/// streamed out without building a `Value` for all of it first:
+///
+///     let mut w = JsonWriter::new(io::stdout());
+///     w.begin_object()?.key("items")?.begin_array()?;
+///     for item in items {
+///         w.value(&item)?;
+///     }
+///     w.end_array()?.end_object()?;
+///     w.finish()?;
+///
+/// Calls that would produce malformed JSON, like a value in an object
+/// without a key or closing an array with `end_object`, are errors and
+/// write nothing. `object` and `array` return scoped writers that only
+/// offer the calls valid inside them.
+pub struct JsonWriter<W> {
+    writer: W,
+    stack: Vec<Frame>,
+    /// Whether the top-level value has been started.
+    started: bool,
+}
+
+enum Frame {
+    Object { first: bool, has_key: bool },
+    Array { first: bool },
+}
+
+impl<W: Write> JsonWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            stack: Vec::new(),
+            started: false,
+        }
+    }
+
+    pub fn begin_object(&mut self) -> Result<&mut Self> {
+        self.before_value()?;
+        self.write(b"{")?;
+        self.stack.push(Frame::Object {
+            first: true,
+            has_key: false,
+        });
+        Ok(self)
+    }
+
+    pub fn end_object(&mut self) -> Result<&mut Self> {
+        match self.stack.last() {
+            Some(Frame::Object { has_key: false, .. }) => {}
+            Some(Frame::Object { has_key: true, .. }) => {
+                return Err(Error::custom("end_object() after a key without a value"))
+            }
+            Some(Frame::Array { .. }) => {
+                return Err(Error::custom("end_object() while an array is open"))
+            }
+            None => return Err(Error::custom("end_object() without begin_object()")),
+        }
+        self.stack.pop();
+        self.write(b"}")?;
+        Ok(self)
+    }
+
+    pub fn begin_array(&mut self) -> Result<&mut Self> {
+        self.before_value()?;
+        self.write(b"[")?;
+        self.stack.push(Frame::Array { first: true });
+        Ok(self)
+    }
+
+    pub fn end_array(&mut self) -> Result<&mut Self> {
+        match self.stack.last() {
+            Some(Frame::Array { .. }) => {}
+            Some(Frame::Object { .. }) => {
+                return Err(Error::custom("end_array() while an object is open"))
+            }
+            None => return Err(Error::custom("end_array() without begin_array()")),
+        }
+        self.stack.pop();
+        self.write(b"]")?;
+        Ok(self)
+    }
+
+    /// Writes the key of the next member of the open object.
+    pub fn key(&mut self, key: &str) -> Result<&mut Self> {
+        let first = match self.stack.last_mut() {
+            Some(Frame::Object {
+                first,
+                has_key: has_key @ false,
+            }) => {
+                *has_key = true;
+                std::mem::replace(first, false)
+            }
+            Some(Frame::Object { has_key: true, .. }) => {
+                return Err(Error::custom(format!(
+                    "key `{}` after a key without a value",
+                    key
+                )))
+            }
+            Some(Frame::Array { .. }) => {
+                return Err(Error::custom(format!("key `{}` inside an array", key)))
+            }
+            None => return Err(Error::custom(format!("key `{}` outside an object", key))),
+        };
+        if !first {
+            self.write(b",")?;
+        }
+        write_str(&mut self.writer, key).map_err(Error::io)?;
+        self.write(b":")?;
+        Ok(self)
+    }
+
+    /// Writes a whole value, e.g. `w.value(&42.into())`.
+    pub fn value(&mut self, value: &Value) -> Result<&mut Self> {
+        self.before_value()?;
+        ValueWriter::new(&mut self.writer, false)
+            .value(value)
+            .map_err(Error::io)?;
+        Ok(self)
+    }
+
+    /// Writes a string value without copying it into a `Value`.
+    pub fn string(&mut self, s: &str) -> Result<&mut Self> {
+        self.before_value()?;
+        write_str(&mut self.writer, s).map_err(Error::io)?;
+        Ok(self)
+    }
+
+    /// Starts an object, returning a writer that closes it with `end`.
+    pub fn object(&mut self) -> Result<ObjectWriter<'_, W>> {
+        self.begin_object()?;
+        let depth = self.stack.len();
+        Ok(ObjectWriter {
+            writer: self,
+            depth,
+        })
+    }
+
+    /// Starts an array, returning a writer that closes it with `end`.
+    pub fn array(&mut self) -> Result<ArrayWriter<'_, W>> {
+        self.begin_array()?;
+        let depth = self.stack.len();
+        Ok(ArrayWriter {
+            writer: self,
+            depth,
+        })
+    }
+
+    /// Checks that the document is complete, flushes it and returns the
+    /// underlying writer.
+    pub fn finish(mut self) -> Result<W> {
+        match self.stack.last() {
+            Some(Frame::Object { .. }) => return Err(Error::custom("unclosed object")),
+            Some(Frame::Array { .. }) => return Err(Error::custom("unclosed array")),
+            None if !self.started => return Err(Error::custom("no value was written")),
+            None => {}
+        }
+        self.writer.flush().map_err(Error::io)?;
+        Ok(self.writer)
+    }
+
+    /// Checks that a value may be written here, and writes the separator
+    /// before it.
+    fn before_value(&mut self) -> Result<()> {
+        let comma = match self.stack.last_mut() {
+            None if self.started => {
+                return Err(Error::custom("a document has only one top-level value"))
+            }
+            None => {
+                self.started = true;
+                false
+            }
+            Some(Frame::Array { first }) => !std::mem::replace(first, false),
+            Some(Frame::Object { has_key, .. }) => {
+                if !std::mem::replace(has_key, false) {
+                    return Err(Error::custom("a value in an object needs a key first"));
+                }
+                false
+            }
+        };
+        if comma {
+            self.write(b",")?;
+        }
+        Ok(())
+    }
+
+    fn write(&mut self, bytes: &[u8]) -> Result<()> {
+        self.writer.write_all(bytes).map_err(Error::io)
+    }
+}
+
+/// An object being written by `JsonWriter::object`. Every value it writes
+/// comes with its key, and the object stays open until `end`.
+pub struct ObjectWriter<'a, W> {
+    writer: &'a mut JsonWriter<W>,
+    depth: usize,
+}
+
+impl<W: Write> ObjectWriter<'_, W> {
+    pub fn entry(&mut self, key: &str, value: &Value) -> Result<&mut Self> {
+        self.writer()?.key(key)?.value(value)?;
+        Ok(self)
+    }
+
+    pub fn string(&mut self, key: &str, value: &str) -> Result<&mut Self> {
+        self.writer()?.key(key)?.string(value)?;
+        Ok(self)
+    }
+
+    /// Starts an object under `key`.
+    pub fn object(&mut self, key: &str) -> Result<ObjectWriter<'_, W>> {
+        self.writer()?.key(key)?.object()
+    }
+
+    /// Starts an array under `key`.
+    pub fn array(&mut self, key: &str) -> Result<ArrayWriter<'_, W>> {
+        self.writer()?.key(key)?.array()
+    }
+
+    pub fn end(mut self) -> Result<()> {
+        self.writer()?.end_object()?;
+        Ok(())
+    }
+
+    fn writer(&mut self) -> Result<&mut JsonWriter<W>> {
+        unclosed_child(self.writer, self.depth)
+    }
+}
+
+/// An array being written by `JsonWriter::array`, open until `end`.
+pub struct ArrayWriter<'a, W> {
+    writer: &'a mut JsonWriter<W>,
+    depth: usize,
+}
+
+impl<W: Write> ArrayWriter<'_, W> {
+    pub fn element(&mut self, value: &Value) -> Result<&mut Self> {
+        self.writer()?.value(value)?;
+        Ok(self)
+    }
+
+    pub fn string(&mut self, value: &str) -> Result<&mut Self> {
+        self.writer()?.string(value)?;
+        Ok(self)
+    }
+
+    pub fn object(&mut self) -> Result<ObjectWriter<'_, W>> {
+        self.writer()?.object()
+    }
+
+    pub fn array(&mut self) -> Result<ArrayWriter<'_, W>> {
+        self.writer()?.array()
+    }
+
+    pub fn end(mut self) -> Result<()> {
+        self.writer()?.end_array()?;
+        Ok(())
+    }
+
+    fn writer(&mut self) -> Result<&mut JsonWriter<W>> {
+        unclosed_child(self.writer, self.depth)
+    }
+}
+
+/// The borrow checker keeps a scoped writer from being used while a child
+/// is alive, but a child dropped without `end` leaves its object or array
+/// open; this catches that before the parent writes into it.
+fn unclosed_child<W>(writer: &mut JsonWriter<W>, depth: usize) -> Result<&mut JsonWriter<W>> {
+    if writer.stack.len() != depth {
+        return Err(Error::custom("a nested object or array was not ended"));
+    }
+    Ok(writer)
+}
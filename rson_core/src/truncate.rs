@@ -0,0 +1,102 @@
+use crate::value::{Map, RsonMap, Value};
+
+/// Marks content left out by `Value::truncate_for_log`.
+pub const ELLIPSIS: &str = "…";
+
+impl Value {
+    /// Returns a shortened copy of `self` that is safe to log:
+    ///
+    /// - strings longer than `max_string_len` characters are cut and end
+    ///   with `… (N chars)`, object keys included;
+    /// - arrays keep their first `max_array_items` elements followed by a
+    ///   `"… N more items"` string, and objects likewise keep that many
+    ///   members, in key order, plus a `"…": "N more members"` entry;
+    /// - arrays and objects nested deeper than `max_depth` are replaced by
+    ///   a summary such as `"[… 12 items]"`. The root is at depth 0, so a
+    ///   `max_depth` of 0 summarizes a top-level container.
+    ///
+    /// The result is only meant for humans: markers are ordinary strings
+    /// and can't be told apart from data.
+    pub fn truncate_for_log(
+        &self,
+        max_string_len: usize,
+        max_array_items: usize,
+        max_depth: usize,
+    ) -> Value {
+        let limits = Limits {
+            max_string_len,
+            max_array_items,
+            max_depth,
+        };
+        limits.truncate(self, 0)
+    }
+}
+
+struct Limits {
+    max_string_len: usize,
+    max_array_items: usize,
+    max_depth: usize,
+}
+
+impl Limits {
+    fn truncate(&self, value: &Value, depth: usize) -> Value {
+        match value {
+            Value::String(s) => Value::String(self.truncate_str(s)),
+            Value::Array(array) if depth >= self.max_depth => {
+                Value::String(format!("[{} {}]", ELLIPSIS, count(array.len(), "item")))
+            }
+            Value::Object(map) if depth >= self.max_depth => {
+                Value::String(format!("{{{} {}}}", ELLIPSIS, count(map.0.len(), "member")))
+            }
+            Value::Array(array) => {
+                let mut truncated: Vec<Value> = array
+                    .iter()
+                    .take(self.max_array_items)
+                    .map(|v| self.truncate(v, depth + 1))
+                    .collect();
+                if array.len() > self.max_array_items {
+                    let rest = array.len() - self.max_array_items;
+                    truncated.push(Value::String(format!(
+                        "{} {}",
+                        ELLIPSIS,
+                        count(rest, "more item")
+                    )));
+                }
+                Value::Array(truncated)
+            }
+            Value::Object(map) => {
+                let mut keys: Vec<&String> = map.0.keys().collect();
+                keys.sort();
+                let mut truncated: Map<String, Value> = keys
+                    .iter()
+                    .take(self.max_array_items)
+                    .map(|&k| (self.truncate_str(k), self.truncate(&map.0[k], depth + 1)))
+                    .collect();
+                if keys.len() > self.max_array_items {
+                    let rest = keys.len() - self.max_array_items;
+                    truncated.insert(
+                        ELLIPSIS.to_string(),
+                        Value::String(count(rest, "more member")),
+                    );
+                }
+                Value::Object(RsonMap(truncated))
+            }
+            Value::Literal(_) | Value::Number(_) => value.clone(),
+        }
+    }
+
+    fn truncate_str(&self, s: &str) -> String {
+        match s.char_indices().nth(self.max_string_len) {
+            Some((end, _)) => format!("{}{} ({} chars)", &s[..end], ELLIPSIS, s.chars().count()),
+            None => s.to_string(),
+        }
+    }
+}
+
+fn count(n: usize, noun: &str) -> String {
+    if n == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", n, noun)
+    }
+}
@@ -0,0 +1,482 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::mem::size_of;
+use std::ops::Index;
+use std::str::FromStr;
+/// JSON Grammar:
+///     JSON-text = ws value ws
+///
+///     ws = *( SPACE | TAB | LINE_FEED/NEW_LINE | CR )
+///     
+///     Structural Characters:
+///         begin-array = ws [ ws
+///         begin-object = ws { ws
+///         end-array = ws ] ws
+///         end-object = ws } ws
+///         name-separator = ws : ws
+///         value-seperator = ws , ws
+///     
+///     Values:
+///         Value = (false | null | true | object | array | number | string)
+///
+///     Objects:
+///         Object = begin-object [ member *( value-seperator member ) ]
+///                  end- object
+///
+///         member = string name-seperator value
+///
+///     Arrays:
+///         array = begin-array [ value *( value-seperator value ) ] end-array
+///
+///     Numbers:
+///         number = [ minus ] int [ frac ] [ exp ]
+///
+///         decimal-point = .
+///
+///         digit1-9 = 1-9
+///
+///         e = (e | E) ; lowercase | uppercase
+///
+///         exp = e [ minus | plus ] 1*DIGIT
+///
+///         frac = decimal-point 1*DIGIT
+///
+///         int = zero / (digit1-9 *DIGIT)
+///
+///         minus = -
+///
+///         plus = +
+///
+///         zero = 0
+///
+///     Strings:
+///         string = quotation-mark *char quotation-mark
+///         char = unescaped | escape ( " | \ | / | b | f | n | r | t | uXXXX )
+///         escape = \
+///         quotation-mark = "
+///         unescaped = a-z | A-Z | %x5D-10FFFF
+///
+/// From the abover Grammar, we can represent a JSON Value as below. Object
+/// keys are `String`s unless `K` says otherwise; see
+/// `Rson::try_from_reader_keyed` and `Value::map_keys`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Value<K: Hash + Ord = String> {
+    Literal(Literal),
+    Number(Number),
+    String(String),
+    Array(Vec<Value<K>>),
+    Object(RsonMap<K, Value<K>>),
+}
+
+impl<K: Hash + Ord> Value<K> {
+    /// Converts every object key with `f`, e.g. to `Box<str>` to shrink a
+    /// document kept in memory, to keys from a `StringPool` or back to
+    /// `String`s:
+    ///
+    ///     let compact = value.map_keys(String::into_boxed_str);
+    pub fn map_keys<L: Hash + Ord>(self, mut f: impl FnMut(K) -> L) -> Value<L> {
+        self.map_keys_with(&mut f)
+    }
+
+    fn map_keys_with<L: Hash + Ord>(self, f: &mut impl FnMut(K) -> L) -> Value<L> {
+        match self {
+            Value::Literal(literal) => Value::Literal(literal),
+            Value::Number(n) => Value::Number(n),
+            Value::String(s) => Value::String(s),
+            Value::Array(array) => {
+                Value::Array(array.into_iter().map(|v| v.map_keys_with(f)).collect())
+            }
+            Value::Object(map) => Value::Object(RsonMap(
+                map.0
+                    .into_iter()
+                    .map(|(k, v)| (f(k), v.map_keys_with(f)))
+                    .collect(),
+            )),
+        }
+    }
+}
+
+impl Value {
+    /// Returns the number of heap bytes owned by this value and everything
+    /// below it: string and vector capacities plus the bucket storage of
+    /// every map. The inline size of `self` is not included, so a value
+    /// held in a `Vec` costs `size_of::<Value>() + deep_size_of()`.
+    ///
+    /// Map storage is estimated from the map's capacity since the exact
+    /// layout belongs to the standard library.
+    pub fn deep_size_of(&self) -> usize {
+        match self {
+            Value::Literal(_) => 0,
+            Value::Number(n) => n.value.capacity(),
+            Value::String(s) => s.capacity(),
+            Value::Array(array) => {
+                array.capacity() * size_of::<Value>()
+                    + array.iter().map(Value::deep_size_of).sum::<usize>()
+            }
+            Value::Object(map) => {
+                // One control byte per bucket on top of the entry itself.
+                map.0.capacity() * (size_of::<(String, Value)>() + 1)
+                    + map
+                        .0
+                        .iter()
+                        .map(|(k, v)| k.capacity() + v.deep_size_of())
+                        .sum::<usize>()
+            }
+        }
+    }
+
+    /// Releases the spare capacity the parser leaves behind while growing
+    /// strings, arrays and maps, recursively. Worth calling on documents
+    /// that are kept around, e.g. in a cache.
+    pub fn shrink_to_fit(&mut self) {
+        match self {
+            Value::Literal(_) => {}
+            Value::Number(n) => n.value.shrink_to_fit(),
+            Value::String(s) => s.shrink_to_fit(),
+            Value::Array(array) => {
+                array.iter_mut().for_each(Value::shrink_to_fit);
+                array.shrink_to_fit();
+            }
+            Value::Object(map) => {
+                // Keys can't be mutated in place, so rebuild the map with
+                // shrunk keys and an exact capacity.
+                let mut shrunk = Map::with_capacity(map.0.len());
+                for (mut key, mut value) in std::mem::take(&mut map.0) {
+                    key.shrink_to_fit();
+                    value.shrink_to_fit();
+                    shrunk.insert(key, value);
+                }
+                map.0 = shrunk;
+            }
+        }
+    }
+}
+
+impl Value {
+    /// The entries of an object in key order, like `RsonMap::iter_sorted`,
+    /// or `None` if this isn't an object.
+    pub fn entries_sorted(&self) -> Option<impl ExactSizeIterator<Item = (&String, &Value)> + '_> {
+        match self {
+            Value::Object(map) => Some(map.iter_sorted()),
+            _ => None,
+        }
+    }
+
+    /// Looks up `key` in an object ignoring ASCII case, for producers
+    /// that vary the casing of keys like HTTP header names. An exact match
+    /// is preferred; among other matches, the smallest key wins, so the
+    /// result doesn't depend on the map's order.
+    pub fn get_ignore_case(&self, key: &str) -> Option<&Value> {
+        let map = match self {
+            Value::Object(map) => map,
+            _ => return None,
+        };
+        map.0.get(key).or_else(|| {
+            map.0
+                .iter()
+                .filter(|(k, _)| k.eq_ignore_ascii_case(key))
+                .min_by(|a, b| a.0.cmp(b.0))
+                .map(|(_, v)| v)
+        })
+    }
+}
+
+impl Value {
+    /// Describes this value for error messages, e.g. ``number `7` ``.
+    #[doc(hidden)]
+    pub fn unexpected(&self) -> String {
+        match self {
+            Value::Literal(Literal::Null) => "null".to_string(),
+            Value::Literal(Literal::Bool(b)) => format!("boolean `{}`", b),
+            Value::Number(n) => format!("number `{}`", n.as_str()),
+            Value::String(s) => format!("string {:?}", s),
+            Value::Array(_) => "array".to_string(),
+            Value::Object(_) => "object".to_string(),
+        }
+    }
+}
+
+impl Index<&'static str> for Value {
+    type Output = Value;
+
+    fn index(&self, name: &str) -> &Self::Output {
+        match self {
+            Value::Object(map) => map.0.get(name).unwrap(),
+            _ => panic!("Index not supported!"),
+        }
+    }
+}
+
+// Conversions from Rust values, used by `rson!` interpolation.
+
+impl From<Literal> for Value {
+    fn from(literal: Literal) -> Self {
+        Value::Literal(literal)
+    }
+}
+
+impl From<Number> for Value {
+    fn from(number: Number) -> Self {
+        Value::Number(number)
+    }
+}
+
+impl From<RsonMap<String, Value>> for Value {
+    fn from(map: RsonMap<String, Value>) -> Self {
+        Value::Object(map)
+    }
+}
+
+impl From<()> for Value {
+    fn from(_: ()) -> Self {
+        Value::Literal(Literal::Null)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(b: bool) -> Self {
+        Value::Literal(Literal::Bool(b))
+    }
+}
+
+impl From<String> for Value {
+    fn from(s: String) -> Self {
+        Value::String(s)
+    }
+}
+
+impl From<&str> for Value {
+    fn from(s: &str) -> Self {
+        Value::String(s.to_string())
+    }
+}
+
+impl From<char> for Value {
+    fn from(c: char) -> Self {
+        Value::String(c.to_string())
+    }
+}
+
+macro_rules! from_integer {
+    ($($ty:ty)*) => {
+        $(
+            impl From<$ty> for Value {
+                fn from(n: $ty) -> Self {
+                    Value::Number(Number::new(n.to_string()))
+                }
+            }
+        )*
+    };
+}
+
+from_integer!(i8 i16 i32 i64 i128 isize u8 u16 u32 u64 u128 usize);
+
+macro_rules! from_float {
+    ($($ty:ty)*) => {
+        $(
+            /// JSON has no NaN or infinity, so non-finite floats become
+            /// `null`.
+            impl From<$ty> for Value {
+                fn from(n: $ty) -> Self {
+                    if n.is_finite() {
+                        // `Debug` prints the shortest text that round-trips.
+                        Value::Number(Number::new(format!("{:?}", n)))
+                    } else {
+                        Value::Literal(Literal::Null)
+                    }
+                }
+            }
+        )*
+    };
+}
+
+from_float!(f32 f64);
+
+impl<T: Into<Value>> From<Option<T>> for Value {
+    fn from(option: Option<T>) -> Self {
+        match option {
+            Some(value) => value.into(),
+            None => Value::Literal(Literal::Null),
+        }
+    }
+}
+
+impl<T: Into<Value>> From<Vec<T>> for Value {
+    fn from(values: Vec<T>) -> Self {
+        Value::Array(values.into_iter().map(Into::into).collect())
+    }
+}
+
+impl<T: Clone + Into<Value>> From<&[T]> for Value {
+    fn from(values: &[T]) -> Self {
+        Value::Array(values.iter().cloned().map(Into::into).collect())
+    }
+}
+
+impl<V: Into<Value>> From<HashMap<String, V>> for Value {
+    fn from(map: HashMap<String, V>) -> Self {
+        Value::Object(RsonMap(
+            map.into_iter().map(|(k, v)| (k, v.into())).collect(),
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub enum Literal {
+    Null,
+    Bool(bool),
+}
+
+impl FromStr for Literal {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "null" => Ok(Literal::Null),
+            "true" => Ok(Literal::Bool(true)),
+            "false" => Ok(Literal::Bool(false)),
+            _ => Err(format!("Expected a literal. Found: `{}`", s)),
+        }
+    }
+}
+
+/// The map behind `RsonMap`: a `HashMap`, or with the `preserve_order`
+/// feature an `IndexMap`, which iterates in insertion order.
+#[cfg(not(feature = "preserve_order"))]
+pub type Map<K, V> = HashMap<K, V>;
+#[cfg(feature = "preserve_order")]
+pub type Map<K, V> = indexmap::IndexMap<K, V>;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct RsonMap<K, V>(pub Map<K, V>)
+where
+    K: Hash + std::cmp::Ord;
+
+impl<K: Hash + Ord, V> RsonMap<K, V> {
+    /// Iterates over the entries in key order, whatever order the map
+    /// keeps them in, for output that must not change between runs.
+    pub fn iter_sorted(&self) -> impl ExactSizeIterator<Item = (&K, &V)> + '_ {
+        let mut entries: Vec<_> = self.0.iter().collect();
+        entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+        entries.into_iter()
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Number {
+    value: String,
+}
+
+impl Number {
+    pub fn new(value: String) -> Self {
+        Self { value }
+    }
+
+    /// Returns the number exactly as it appeared in the source text.
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
+
+    /// Whether both numbers are the same decimal however they are written,
+    /// like `1.50` and `15e-1`. Unlike comparing `f64`s this tells
+    /// `12345678901234567890` from `12345678901234567891`. Text that isn't
+    /// a plain decimal, like `NaN`, only equals the same text.
+    pub fn same_value(&self, other: &Number) -> bool {
+        match (decimal_parts(&self.value), decimal_parts(&other.value)) {
+            (Some(a), Some(b)) => a == b,
+            _ => self.value == other.value,
+        }
+    }
+
+    /// The number as an `f64` if that holds it exactly, like `0.5`, `1e300`
+    /// or `9007199254740992`. Numbers it would round, like `0.1` or the
+    /// 20-digit `12345678901234567890`, and ones out of its range give
+    /// `None`.
+    pub fn try_into_f64_lossless(&self) -> Option<f64> {
+        let float: f64 = self.value.parse().ok()?;
+        if !float.is_finite() {
+            return None;
+        }
+        // Every finite f64 is a decimal of at most 767 significant digits.
+        let exact = format!("{:.767e}", float);
+        match (decimal_parts(&exact), decimal_parts(&self.value)) {
+            (Some(exact), Some(source)) if exact == source => Some(float),
+            _ => None,
+        }
+    }
+}
+
+/// Splits decimal number text into its sign, significant digits and
+/// exponent, with the value `0.digits * 10^exponent`, so equal numbers
+/// written differently (`1.50`, `15e-1`) have equal parts.
+fn decimal_parts(text: &str) -> Option<(bool, String, i64)> {
+    let (negative, text) = match text.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, text),
+    };
+    let (mantissa, exponent) = match text.find(['e', 'E']) {
+        Some(i) => (&text[..i], text[i + 1..].parse::<i64>().ok()?),
+        None => (text, 0),
+    };
+    let (int, frac) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if !int.chars().chain(frac.chars()).all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+
+    let digits = format!("{}{}", int, frac);
+    let significant = digits.trim_start_matches('0');
+    let exponent = exponent
+        .checked_add(int.len() as i64)?
+        .checked_sub((digits.len() - significant.len()) as i64)?;
+    let significant = significant.trim_end_matches('0');
+    if significant.is_empty() {
+        return Some((false, String::new(), 0));
+    }
+    Some((negative, significant.to_string(), exponent))
+}
+
+// Constant declarations
+pub const TAB: char = '\t';
+pub const NEW_LINE: char = '\n';
+pub const CARRIAGE_RETURN: char = '\r';
+pub const SPACE: char = ' ';
+
+#[derive(Eq, PartialEq, Copy, Clone)]
+#[repr(u8)]
+pub enum StructuralChar {
+    BeginArray = b'[',
+    EndArray = b']',
+    BeginObject = b'{',
+    EndObject = b'}',
+    NameSeperator = b':',
+    ValueSeperator = b',',
+    QuotationMark = b'"',
+}
+
+impl StructuralChar {
+    pub fn iter() -> std::slice::Iter<'static, StructuralChar> {
+        [
+            StructuralChar::BeginArray,
+            StructuralChar::BeginObject,
+            StructuralChar::EndArray,
+            StructuralChar::EndObject,
+            StructuralChar::NameSeperator,
+            StructuralChar::ValueSeperator,
+            StructuralChar::QuotationMark,
+        ]
+        .iter()
+    }
+}
+
+impl From<StructuralChar> for char {
+    fn from(sc: StructuralChar) -> Self {
+        match sc {
+            StructuralChar::BeginArray => '[',
+            StructuralChar::EndArray => ']',
+            StructuralChar::BeginObject => '{',
+            StructuralChar::EndObject => '}',
+            StructuralChar::NameSeperator => ':',
+            StructuralChar::ValueSeperator => ',',
+            StructuralChar::QuotationMark => '"',
+        }
+    }
+}
@@ -0,0 +1,181 @@
+use crate::instrument::{self, Timer};
+use crate::value::{Literal, Value};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io::{self, Write};
+
+/// Serializes a `Value` as compact JSON text. For values with keys other
+/// than `String`, like `Value<Box<str>>`, use their `Display` impl.
+pub fn to_string(value: &Value) -> String {
+    let mut out = Vec::new();
+    to_writer(&mut out, value).expect("writing to a Vec never fails");
+    String::from_utf8(out).expect("serializer emits valid UTF-8")
+}
+
+/// Serializes a `Value` as compact JSON text into `writer`.
+pub fn to_writer<W: Write>(writer: W, value: &Value) -> io::Result<()> {
+    write_document(writer, value, false)
+}
+
+/// Serializes a `Value` in canonical form: object keys sorted by their
+/// UTF-8 bytes and no insignificant whitespace. Values that compare equal
+/// always produce identical canonical text, whatever order their keys
+/// were inserted in. Numbers are written as they were parsed.
+pub fn to_canonical_string(value: &Value) -> String {
+    let mut out = Vec::new();
+    write_document(&mut out, value, true).expect("writing to a Vec never fails");
+    String::from_utf8(out).expect("serializer emits valid UTF-8")
+}
+
+/// Writes a whole document, recording it in the usage metrics.
+fn write_document<W: Write>(writer: W, value: &Value, canonical: bool) -> io::Result<()> {
+    let timer = Timer::start();
+    let mut writer = CountingWriter(writer, 0);
+    ValueWriter::new(&mut writer, canonical).value(value)?;
+    instrument::serialized(writer.1, timer);
+    Ok(())
+}
+
+/// Counts the bytes written through it.
+struct CountingWriter<W>(W, usize);
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.0.write(buf)?;
+        self.1 += written;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+/// Compact JSON text, like `to_string`, for any key type.
+impl<K: AsRef<str> + Hash + Ord> fmt::Display for Value<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut out = Vec::new();
+        ValueWriter::new(&mut out, false)
+            .value(self)
+            .expect("writing to a Vec never fails");
+        f.write_str(std::str::from_utf8(&out).expect("serializer emits valid UTF-8"))
+    }
+}
+
+impl Value {
+    /// Hashes the canonical serialization of this value, so two documents
+    /// that differ only in key order hash the same:
+    ///
+    ///     let key = value.canonical_hash::<DefaultHasher>();
+    pub fn canonical_hash<H: Hasher + Default>(&self) -> u64 {
+        let mut hasher = H::default();
+        ValueWriter::new(HashWriter(&mut hasher), true)
+            .value(self)
+            .expect("hashing never fails");
+        hasher.finish()
+    }
+
+    /// Lowercase hex SHA-256 digest of the canonical serialization.
+    #[cfg(feature = "sha256")]
+    pub fn sha256_hex(&self) -> String {
+        use sha2::{Digest, Sha256};
+
+        let digest = Sha256::digest(to_canonical_string(self).as_bytes());
+        digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+}
+
+/// Adapts a `Hasher` so the serializer can stream into it without
+/// building the canonical text first.
+struct HashWriter<'a, H>(&'a mut H);
+
+impl<H: Hasher> Write for HashWriter<'_, H> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.write(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+pub(crate) struct ValueWriter<W> {
+    writer: W,
+    canonical: bool,
+}
+
+impl<W: Write> ValueWriter<W> {
+    pub(crate) fn new(writer: W, canonical: bool) -> Self {
+        Self { writer, canonical }
+    }
+
+    pub(crate) fn value<K: AsRef<str> + Hash + Ord>(&mut self, value: &Value<K>) -> io::Result<()> {
+        match value {
+            Value::Literal(Literal::Null) => self.writer.write_all(b"null"),
+            Value::Literal(Literal::Bool(true)) => self.writer.write_all(b"true"),
+            Value::Literal(Literal::Bool(false)) => self.writer.write_all(b"false"),
+            Value::Number(n) => self.writer.write_all(n.as_str().as_bytes()),
+            Value::String(s) => write_str(&mut self.writer, s),
+            Value::Array(array) => {
+                self.writer.write_all(b"[")?;
+                for (i, value) in array.iter().enumerate() {
+                    if i > 0 {
+                        self.writer.write_all(b",")?;
+                    }
+                    self.value(value)?;
+                }
+                self.writer.write_all(b"]")
+            }
+            Value::Object(map) => {
+                let mut entries: Vec<_> = map.0.iter().collect();
+                if self.canonical {
+                    entries.sort_unstable_by(|a, b| a.0.as_ref().cmp(b.0.as_ref()));
+                }
+
+                self.writer.write_all(b"{")?;
+                for (i, (key, value)) in entries.into_iter().enumerate() {
+                    if i > 0 {
+                        self.writer.write_all(b",")?;
+                    }
+                    write_str(&mut self.writer, key.as_ref())?;
+                    self.writer.write_all(b":")?;
+                    self.value(value)?;
+                }
+                self.writer.write_all(b"}")
+            }
+        }
+    }
+}
+
+/// Writes `s` as a quoted JSON string, escaping `"`, `\` and control
+/// characters.
+pub(crate) fn write_str<W: Write>(writer: &mut W, s: &str) -> io::Result<()> {
+    writer.write_all(b"\"")?;
+
+    let mut start = 0;
+    for (i, c) in s.char_indices() {
+        let escape = match c {
+            '"' => "\\\"",
+            '\\' => "\\\\",
+            '\n' => "\\n",
+            '\r' => "\\r",
+            '\t' => "\\t",
+            '\u{8}' => "\\b",
+            '\u{c}' => "\\f",
+            c if c < ' ' => "",
+            _ => continue,
+        };
+
+        writer.write_all(&s.as_bytes()[start..i])?;
+        if escape.is_empty() {
+            write!(writer, "\\u{:04x}", c as u32)?;
+        } else {
+            writer.write_all(escape.as_bytes())?;
+        }
+        start = i + c.len_utf8();
+    }
+
+    writer.write_all(&s.as_bytes()[start..])?;
+    writer.write_all(b"\"")
+}
@@ -0,0 +1,25 @@
+#![cfg(feature = "preserve_order")]
+
+use rson_core::{to_canonical_string, to_string, Rson, Value};
+
+#[test]
+fn test_preserve_order() {
+    let text = r#"{"zebra": 1, "apple": {"b": 2, "a": 3}, "mango": [4]}"#;
+    let value = Rson::from_reader(text.as_bytes());
+
+    match &value {
+        Value::Object(map) => {
+            let keys: Vec<&str> = map.0.keys().map(String::as_str).collect();
+            assert_eq!(keys, ["zebra", "apple", "mango"]);
+        }
+        other => panic!("expected an object, got {:?}", other),
+    }
+    assert_eq!(
+        to_string(&value),
+        r#"{"zebra":1,"apple":{"b":2,"a":3},"mango":[4]}"#
+    );
+    assert_eq!(
+        to_canonical_string(&value),
+        r#"{"apple":{"a":3,"b":2},"mango":[4],"zebra":1}"#
+    );
+}
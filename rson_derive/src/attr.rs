@@ -0,0 +1,232 @@
+use crate::case::RenameRule;
+use syn::ext::IdentExt;
+use syn::{
+    Attribute, Data, DataEnum, DeriveInput, Field, Fields, Ident, Lit, Meta, NestedMeta, Path,
+};
+
+/// Options set with `#[rson(...)]` on a struct or enum.
+#[derive(Default)]
+pub struct ContainerAttrs {
+    /// `rename_all = "..."`: the naming convention of enum variants.
+    pub rename_all: Option<RenameRule>,
+    /// `repr_int`: write an enum as its discriminant instead of its
+    /// variant name. Holds the integer type to use, taken from the enum's
+    /// `#[repr(...)]` and `i64` without one.
+    pub repr_int: Option<Ident>,
+}
+
+impl ContainerAttrs {
+    pub fn from_input(input: &DeriveInput) -> syn::Result<Self> {
+        let mut attrs = ContainerAttrs::default();
+        for meta in rson_metas(&input.attrs)? {
+            match &meta {
+                Meta::NameValue(nv) if nv.path.is_ident("rename_all") => {
+                    let rule = match &nv.lit {
+                        Lit::Str(s) => RenameRule::from_name(&s.value()),
+                        _ => None,
+                    };
+                    attrs.rename_all = Some(rule.ok_or_else(|| {
+                        syn::Error::new_spanned(
+                            &nv.lit,
+                            format!("expected one of \"{}\"", RenameRule::NAMES.join("\", \"")),
+                        )
+                    })?);
+                }
+                Meta::Path(path) if path.is_ident("repr_int") => {
+                    attrs.repr_int = Some(repr_type(&input.attrs)?);
+                }
+                _ => return Err(unknown(&meta)),
+            }
+        }
+        if !matches!(input.data, Data::Enum(_)) {
+            let option = match (&attrs.rename_all, &attrs.repr_int) {
+                (Some(_), _) => Some("rename_all"),
+                (_, Some(_)) => Some("repr_int"),
+                _ => None,
+            };
+            if let Some(option) = option {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    format!("`{}` is only supported on enums", option),
+                ));
+            }
+        }
+        if attrs.rename_all.is_some() && attrs.repr_int.is_some() {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`rename_all` has no effect on `repr_int` enums",
+            ));
+        }
+        Ok(attrs)
+    }
+}
+
+/// The integer type named by `#[repr(...)]`, or `i64`.
+fn repr_type(attrs: &[Attribute]) -> syn::Result<Ident> {
+    const INTEGERS: &[&str] = &[
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+    ];
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("repr")) {
+        if let Meta::List(list) = attr.parse_meta()? {
+            for nested in list.nested {
+                if let NestedMeta::Meta(Meta::Path(path)) = nested {
+                    match path.get_ident() {
+                        Some(ident) if INTEGERS.contains(&ident.to_string().as_str()) => {
+                            return Ok(ident.clone())
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+    }
+    Ok(Ident::new("i64", proc_macro2::Span::call_site()))
+}
+
+/// A variant of a unit-only enum, with the string it is written as.
+pub struct UnitVariant<'a> {
+    pub ident: &'a Ident,
+    pub name: String,
+    /// `#[rson(other)]`: absorbs strings matching no other variant.
+    pub other: bool,
+}
+
+/// Checks that every variant of `data` is a unit variant and works out
+/// their names.
+pub fn unit_variants<'a>(
+    data: &'a DataEnum,
+    container: &ContainerAttrs,
+    derive: &str,
+) -> syn::Result<Vec<UnitVariant<'a>>> {
+    let mut variants: Vec<UnitVariant> = vec![];
+    for variant in &data.variants {
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                &variant.ident,
+                format!(
+                    "{} can only be derived for enums whose variants have no fields",
+                    derive
+                ),
+            ));
+        }
+
+        let mut other = false;
+        for meta in rson_metas(&variant.attrs)? {
+            match &meta {
+                Meta::Path(path) if path.is_ident("other") => other = true,
+                _ => return Err(unknown(&meta)),
+            }
+        }
+        if other && variants.iter().any(|v| v.other) {
+            return Err(syn::Error::new_spanned(
+                &variant.ident,
+                "only one variant can be `#[rson(other)]`",
+            ));
+        }
+
+        let name = variant.ident.unraw().to_string();
+        let name = match container.rename_all {
+            Some(rule) => rule.apply(&name),
+            None => name,
+        };
+        variants.push(UnitVariant {
+            ident: &variant.ident,
+            name,
+            other,
+        });
+    }
+    Ok(variants)
+}
+
+/// Options set with `#[rson(...)]` on a field.
+#[derive(Default)]
+pub struct FieldAttrs {
+    /// `with = "module"`: a module whose `serialize` and `deserialize`
+    /// functions replace the field type's own impls.
+    pub with: Option<Path>,
+    /// `number_fallback = "field"`: with `DeserializeOptions::number_fallback`,
+    /// a number this field's type can't hold goes to `field` instead.
+    pub number_fallback: Option<Ident>,
+}
+
+impl FieldAttrs {
+    pub fn from_field(field: &Field) -> syn::Result<Self> {
+        let mut attrs = FieldAttrs::default();
+        for meta in rson_metas(&field.attrs)? {
+            match &meta {
+                Meta::NameValue(nv) if nv.path.is_ident("with") => {
+                    attrs.with = Some(parse_path(&nv.lit)?);
+                }
+                Meta::NameValue(nv) if nv.path.is_ident("number_fallback") => {
+                    attrs.number_fallback = Some(match &nv.lit {
+                        Lit::Str(s) => s.parse()?,
+                        lit => return Err(syn::Error::new_spanned(lit, "expected a field name")),
+                    });
+                }
+                _ => return Err(unknown(&meta)),
+            }
+        }
+        if let (Some(with), Some(_)) = (&attrs.with, &attrs.number_fallback) {
+            return Err(syn::Error::new_spanned(
+                with,
+                "`with` and `number_fallback` can't be used together",
+            ));
+        }
+        Ok(attrs)
+    }
+
+    /// Rejects options that only make sense on named fields.
+    pub fn only_named(field: &Field) -> syn::Result<()> {
+        let attrs = Self::from_field(field)?;
+        if let Some(path) = attrs.with {
+            return Err(syn::Error::new_spanned(
+                path,
+                "`with` is only supported on named fields",
+            ));
+        }
+        if let Some(ident) = attrs.number_fallback {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "`number_fallback` is only supported on named fields",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The items of every `#[rson(...)]` attribute in `attrs`.
+fn rson_metas(attrs: &[Attribute]) -> syn::Result<Vec<Meta>> {
+    let mut metas = vec![];
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("rson")) {
+        match attr.parse_meta()? {
+            Meta::List(list) => {
+                for nested in list.nested {
+                    match nested {
+                        NestedMeta::Meta(meta) => metas.push(meta),
+                        NestedMeta::Lit(lit) => {
+                            return Err(syn::Error::new_spanned(lit, "expected an rson option"))
+                        }
+                    }
+                }
+            }
+            meta => return Err(syn::Error::new_spanned(meta, "expected `#[rson(...)]`")),
+        }
+    }
+    Ok(metas)
+}
+
+fn parse_path(lit: &Lit) -> syn::Result<Path> {
+    match lit {
+        Lit::Str(s) => s.parse(),
+        _ => Err(syn::Error::new_spanned(lit, "expected a string literal")),
+    }
+}
+
+fn unknown(meta: &Meta) -> syn::Error {
+    let name = meta
+        .path()
+        .get_ident()
+        .map(ToString::to_string)
+        .unwrap_or_default();
+    syn::Error::new_spanned(meta, format!("unknown rson attribute `{}`", name))
+}
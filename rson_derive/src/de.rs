@@ -0,0 +1,404 @@
+use crate::attr::{self, ContainerAttrs, FieldAttrs};
+use crate::dispatch;
+use proc_macro2::{Group, Literal, TokenStream, TokenTree};
+use quote::{format_ident, quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::{
+    parse_quote, Data, DataEnum, DeriveInput, Fields, FieldsNamed, FieldsUnnamed, Generics, Ident,
+};
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let generics = add_bounds(&input.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let container = ContainerAttrs::from_input(input)?;
+
+    if let Data::Struct(data) = &input.data {
+        if let Fields::Unnamed(fields) = &data.fields {
+            fields.unnamed.iter().try_for_each(FieldAttrs::only_named)?;
+        }
+    }
+
+    let body = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => named_struct(input, &generics, fields)?,
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => quote! {
+                ::rson::Deserialize::deserialize(__deserializer).map(#name)
+            },
+            Fields::Unnamed(fields) => tuple_struct(input, &generics, fields),
+            Fields::Unit => unit_struct(input, &generics),
+        },
+        Data::Enum(data) => match &container.repr_int {
+            Some(repr) => int_enum(input, data, &container, repr)?,
+            None => unit_enum(input, &generics, data, &container)?,
+        },
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "Deserialize can't be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::rson::Deserialize for #name #ty_generics #where_clause {
+            fn deserialize<__D>(__deserializer: __D) -> ::rson::Result<Self>
+            where
+                __D: ::rson::Deserializer,
+            {
+                #body
+            }
+        }
+    })
+}
+
+/// Requires every type parameter to be `Deserialize` itself.
+fn add_bounds(generics: &Generics) -> Generics {
+    let mut generics = generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(::rson::Deserialize));
+    }
+    generics
+}
+
+/// Declares `__Visitor` carrying the derive input's generics, so the
+/// visitor impl can name `Self::Value` for generic structs.
+fn visitor(input: &DeriveInput, generics: &Generics, methods: TokenStream) -> TokenStream {
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let expecting = match input.data {
+        Data::Enum(_) => format!("enum {}", name),
+        _ => format!("struct {}", name),
+    };
+
+    quote! {
+        struct __Visitor #impl_generics #where_clause (
+            ::std::marker::PhantomData<#name #ty_generics>
+        );
+
+        impl #impl_generics ::rson::Visitor for __Visitor #ty_generics #where_clause {
+            type Value = #name #ty_generics;
+
+            fn expecting(&self) -> &'static str {
+                #expecting
+            }
+
+            #methods
+        }
+    }
+}
+
+fn named_struct(
+    input: &DeriveInput,
+    generics: &Generics,
+    fields: &FieldsNamed,
+) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let name_str = name.to_string();
+
+    let idents: Vec<_> = fields
+        .named
+        .iter()
+        .map(|f| f.ident.as_ref().unwrap())
+        .collect();
+    let keys: Vec<String> = idents.iter().map(|i| i.unraw().to_string()).collect();
+    let self_ty = {
+        let (_, ty_generics, _) = input.generics.split_for_impl();
+        quote!(#name #ty_generics)
+    };
+    let types: Vec<_> = fields
+        .named
+        .iter()
+        .map(|f| replace_self(f.ty.to_token_stream(), &self_ty))
+        .collect();
+    let slots: Vec<_> = (0..idents.len())
+        .map(|i| format_ident!("__field{}", i))
+        .collect();
+    let indices: Vec<_> = (0..idents.len()).map(Literal::usize_unsuffixed).collect();
+    let field_index = dispatch::field_index(&keys);
+
+    // How each field is read, and what it becomes when it's absent.
+    let mut reads = vec![];
+    let mut defaults = vec![];
+    // Numbers captured by `number_fallback` fields, and the slots of the
+    // fields they go to.
+    let mut captures = vec![];
+    let mut targets = vec![];
+    for (i, (field, key)) in fields.named.iter().zip(&keys).enumerate() {
+        let attrs = FieldAttrs::from_field(field)?;
+        if let Some(target) = &attrs.number_fallback {
+            let index = idents
+                .iter()
+                .position(|ident| *ident == target)
+                .filter(|&index| index != i)
+                .ok_or_else(|| {
+                    syn::Error::new_spanned(target, "expected another field of this struct")
+                })?;
+            let capture = format_ident!("__number{}", i);
+            reads.push(quote!({
+                let (__value, __number) = ::rson::MapAccess::next_value_with(
+                    &mut __map,
+                    ::rson::__private::number_with_fallback,
+                )?;
+                #capture = __number;
+                __value
+            }));
+            defaults.push(quote!(::rson::__private::missing_field(#key)?));
+            captures.push(capture);
+            targets.push(slots[index].clone());
+            continue;
+        }
+        match attrs.with {
+            Some(with) => {
+                reads.push(
+                    quote!(::rson::MapAccess::next_value_with(&mut __map, #with::deserialize)?),
+                );
+                defaults
+                    .push(quote!(::rson::__private::missing_field_with(#key, #with::deserialize)?));
+            }
+            None => {
+                reads.push(quote!(::rson::MapAccess::next_value(&mut __map)?));
+                defaults.push(quote!(::rson::__private::missing_field(#key)?));
+            }
+        }
+    }
+
+    let visitor = visitor(
+        input,
+        generics,
+        quote! {
+            fn visit_map<__A>(self, mut __map: __A) -> ::rson::Result<Self::Value>
+            where
+                __A: ::rson::MapAccess,
+            {
+                #(let mut #slots: ::std::option::Option<#types> = ::std::option::Option::None;)*
+                #(
+                    let mut #captures: ::std::option::Option<::rson::Number> =
+                        ::std::option::Option::None;
+                )*
+
+                while let ::std::option::Option::Some(__key) = ::rson::MapAccess::next_key(&mut __map)? {
+                    match __field_index(&__key) {
+                        #(
+                            ::std::option::Option::Some(#indices) => {
+                                if #slots.is_some() {
+                                    return ::std::result::Result::Err(
+                                        ::rson::Error::duplicate_field(#keys),
+                                    );
+                                }
+                                #slots = ::std::option::Option::Some(#reads);
+                            }
+                        )*
+                        _ => {
+                            ::rson::MapAccess::next_value::<::rson::Value>(&mut __map)?;
+                        }
+                    }
+                }
+
+                #(
+                    if let ::std::option::Option::Some(__number) = #captures {
+                        if #targets.is_none() {
+                            #targets = ::std::option::Option::Some(
+                                ::rson::__private::number_fallback_value(__number)?,
+                            );
+                        }
+                    }
+                )*
+
+                ::std::result::Result::Ok(#name {
+                    #(
+                        #idents: match #slots {
+                            ::std::option::Option::Some(__value) => __value,
+                            ::std::option::Option::None => #defaults,
+                        },
+                    )*
+                })
+            }
+        },
+    );
+
+    Ok(quote! {
+        const FIELDS: &[&str] = &[#(#keys),*];
+
+        fn __field_index(key: &str) -> ::std::option::Option<usize> {
+            #field_index
+        }
+
+        #visitor
+
+        ::rson::Deserializer::deserialize_struct(
+            __deserializer,
+            #name_str,
+            FIELDS,
+            __Visitor(::std::marker::PhantomData),
+        )
+    })
+}
+
+/// Spells out `Self` in a field type, which would otherwise name the
+/// visitor inside the generated visitor impl.
+fn replace_self(tokens: TokenStream, self_ty: &TokenStream) -> TokenStream {
+    tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Ident(ident) if ident == "Self" => self_ty.clone(),
+            TokenTree::Group(group) => {
+                let mut replaced =
+                    Group::new(group.delimiter(), replace_self(group.stream(), self_ty));
+                replaced.set_span(group.span());
+                TokenTree::Group(replaced).into()
+            }
+            other => other.into(),
+        })
+        .collect()
+}
+
+fn tuple_struct(input: &DeriveInput, generics: &Generics, fields: &FieldsUnnamed) -> TokenStream {
+    let name = &input.ident;
+    let len = fields.unnamed.len();
+    let expecting = format!("tuple struct {} with {} elements", name, len);
+    let values = (0..len).map(|i| {
+        quote! {
+            match ::rson::SeqAccess::next_element(&mut __seq)? {
+                ::std::option::Option::Some(__value) => __value,
+                ::std::option::Option::None => {
+                    return ::std::result::Result::Err(::rson::Error::invalid_length(#i, #expecting));
+                }
+            }
+        }
+    });
+
+    let visitor = visitor(
+        input,
+        generics,
+        quote! {
+            fn visit_seq<__A>(self, mut __seq: __A) -> ::rson::Result<Self::Value>
+            where
+                __A: ::rson::SeqAccess,
+            {
+                let __value = #name(#(#values),*);
+                let mut __extra = 0;
+                while ::rson::SeqAccess::next_element::<::rson::Value>(&mut __seq)?.is_some() {
+                    __extra += 1;
+                }
+                if __extra > 0 {
+                    return ::std::result::Result::Err(
+                        ::rson::Error::invalid_length(#len + __extra, #expecting),
+                    );
+                }
+                ::std::result::Result::Ok(__value)
+            }
+        },
+    );
+
+    quote! {
+        #visitor
+        ::rson::Deserializer::deserialize_any(__deserializer, __Visitor(::std::marker::PhantomData))
+    }
+}
+
+fn unit_struct(input: &DeriveInput, generics: &Generics) -> TokenStream {
+    let name = &input.ident;
+    let visitor = visitor(
+        input,
+        generics,
+        quote! {
+            fn visit_null(self) -> ::rson::Result<Self::Value> {
+                ::std::result::Result::Ok(#name)
+            }
+        },
+    );
+
+    quote! {
+        #visitor
+        ::rson::Deserializer::deserialize_any(__deserializer, __Visitor(::std::marker::PhantomData))
+    }
+}
+
+/// Unit-only enums are read from their variant names.
+fn unit_enum(
+    input: &DeriveInput,
+    generics: &Generics,
+    data: &DataEnum,
+    container: &ContainerAttrs,
+) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let name_str = name.to_string();
+    let variants = attr::unit_variants(data, container, "Deserialize")?;
+
+    let known = variants.iter().filter(|v| !v.other);
+    let strings: Vec<_> = known.clone().map(|v| &v.name).collect();
+    let idents = known.map(|v| v.ident);
+    let fallback = match variants.iter().find(|v| v.other) {
+        Some(other) => {
+            let ident = other.ident;
+            quote!(::std::result::Result::Ok(#name::#ident))
+        }
+        None => quote! {
+            ::std::result::Result::Err(::rson::Error::unknown_variant(__other, VARIANTS))
+        },
+    };
+
+    let visitor = visitor(
+        input,
+        generics,
+        quote! {
+            fn visit_string(self, __value: ::std::string::String) -> ::rson::Result<Self::Value> {
+                match __value.as_str() {
+                    #(#strings => ::std::result::Result::Ok(#name::#idents),)*
+                    __other => #fallback,
+                }
+            }
+        },
+    );
+
+    Ok(quote! {
+        const VARIANTS: &[&str] = &[#(#strings),*];
+
+        #visitor
+
+        ::rson::Deserializer::deserialize_enum(
+            __deserializer,
+            #name_str,
+            VARIANTS,
+            __Visitor(::std::marker::PhantomData),
+        )
+    })
+}
+
+/// `repr_int` enums are read from their discriminants. Reading the repr
+/// type first leaves range errors to its own `Deserialize` impl.
+fn int_enum(
+    input: &DeriveInput,
+    data: &DataEnum,
+    container: &ContainerAttrs,
+    repr: &Ident,
+) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let expecting = format!("a discriminant of enum {}", name);
+    let variants = attr::unit_variants(data, container, "Deserialize")?;
+
+    let idents = variants.iter().filter(|v| !v.other).map(|v| v.ident);
+    let fallback = match variants.iter().find(|v| v.other) {
+        Some(other) => {
+            let ident = other.ident;
+            quote!(::std::result::Result::Ok(#name::#ident))
+        }
+        None => quote! {
+            ::std::result::Result::Err(::rson::Error::invalid_value(
+                &::std::format!("number `{}`", __value),
+                #expecting,
+            ))
+        },
+    };
+
+    Ok(quote! {
+        let __value = <#repr as ::rson::Deserialize>::deserialize(__deserializer)?;
+        #(
+            if __value == #name::#idents as #repr {
+                return ::std::result::Result::Ok(#name::#idents);
+            }
+        )*
+        #fallback
+    })
+}
@@ -0,0 +1,60 @@
+use proc_macro2::{Literal, Span, TokenStream};
+use quote::quote;
+use std::collections::BTreeMap;
+use syn::LitByteStr;
+
+/// Field names keyed by `(length, first byte)`, each with its field index.
+type Buckets<'a> = BTreeMap<(usize, Option<u8>), Vec<(usize, &'a str)>>;
+
+/// Generates the body of `fn(key: &str) -> Option<usize>` mapping an
+/// incoming key to the index of the field it names.
+///
+/// Instead of comparing the key against every field name in turn, keys are
+/// first bucketed by `(length, first byte)`, which the compiler lowers to a
+/// jump table; only the handful of names sharing a bucket (usually one) are
+/// then compared in full. Unknown keys are mostly rejected without touching
+/// their bytes beyond the first.
+pub fn field_index(names: &[String]) -> TokenStream {
+    let mut buckets = Buckets::new();
+    for (index, name) in names.iter().enumerate() {
+        let first = name.as_bytes().first().copied();
+        buckets
+            .entry((name.len(), first))
+            .or_default()
+            .push((index, name));
+    }
+
+    let arms = buckets.iter().map(|((len, first), fields)| {
+        let len = Literal::usize_unsuffixed(*len);
+        let first = match first {
+            Some(byte) => {
+                let byte = Literal::u8_unsuffixed(*byte);
+                quote!(::std::option::Option::Some(#byte))
+            }
+            None => quote!(::std::option::Option::None),
+        };
+        let checks = fields.iter().map(|(index, name)| {
+            let index = Literal::usize_unsuffixed(*index);
+            let bytes = LitByteStr::new(name.as_bytes(), Span::call_site());
+            quote! {
+                if __bytes == #bytes {
+                    return ::std::option::Option::Some(#index);
+                }
+            }
+        });
+        quote! {
+            (#len, #first) => {
+                #(#checks)*
+                ::std::option::Option::None
+            }
+        }
+    });
+
+    quote! {
+        let __bytes = key.as_bytes();
+        match (__bytes.len(), __bytes.first().copied()) {
+            #(#arms)*
+            _ => ::std::option::Option::None,
+        }
+    }
+}
@@ -0,0 +1,72 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use rson_core::{Literal, Rson, Value};
+use std::path::PathBuf;
+use syn::LitStr;
+
+/// Expands `include_rson!("path")`. The path is relative to the manifest
+/// directory of the crate using the macro, like `include_str!` relative to
+/// the source file.
+pub fn expand(path: &LitStr) -> syn::Result<TokenStream> {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let full_path = PathBuf::from(manifest_dir).join(path.value());
+    let display = full_path.display().to_string();
+
+    let text = std::fs::read_to_string(&full_path)
+        .map_err(|e| syn::Error::new(path.span(), format!("couldn't read {}: {}", display, e)))?;
+    let json = Rson::try_from_reader(text.as_bytes())
+        .map_err(|e| syn::Error::new(path.span(), format!("{}: {}", display, e)))?;
+    let value = value(&json);
+
+    // `include_bytes!` registers the file with cargo so editing it triggers
+    // a rebuild.
+    Ok(quote! {
+        {
+            const _: &[u8] = ::std::include_bytes!(#display);
+            #value
+        }
+    })
+}
+
+/// Builds the expression constructing `json` as an `rson::Value`.
+pub fn value(json: &Value) -> TokenStream {
+    match json {
+        Value::Literal(Literal::Null) => quote!(::rson::Value::<::std::string::String>::Literal(
+            ::rson::Literal::Null
+        )),
+        Value::Literal(Literal::Bool(b)) => {
+            quote!(::rson::Value::<::std::string::String>::Literal(::rson::Literal::Bool(#b)))
+        }
+        Value::Number(n) => {
+            let n = n.as_str();
+            quote!(::rson::Value::<::std::string::String>::Number(::rson::Number::new(::std::string::String::from(#n))))
+        }
+        Value::String(s) => {
+            quote!(::rson::Value::<::std::string::String>::String(::std::string::String::from(#s)))
+        }
+        Value::Array(elements) => {
+            let elements = elements.iter().map(value);
+            quote!(::rson::Value::<::std::string::String>::Array(
+                ::std::vec![#(#elements),*]
+            ))
+        }
+        Value::Object(members) => {
+            let mut members: Vec<_> = members.0.iter().collect();
+            // Without `preserve_order` the parsed members are in hash order;
+            // sort them so every build expands the same way.
+            if !cfg!(feature = "preserve_order") {
+                members.sort_unstable_by(|a, b| a.0.cmp(b.0));
+            }
+            let len = members.len();
+            let keys = members.iter().map(|(k, _)| k);
+            let values = members.iter().map(|(_, v)| value(v));
+            quote! {
+                {
+                    let mut map = ::rson::Map::with_capacity(#len);
+                    #(map.insert(::std::string::String::from(#keys), #values);)*
+                    ::rson::Value::<::std::string::String>::Object(::rson::RsonMap(map))
+                }
+            }
+        }
+    }
+}
@@ -1,23 +1,77 @@
-use proc_macro2::TokenStream;
-use quote::{quote, quote_spanned};
-use syn::spanned::Spanned;
-use syn::{
-    parse_macro_input, parse_quote, Data, DeriveInput, Fields, GenericParam, Generics, Index,
-};
-
-#[proc_macro_derive(Deserialize)]
+use syn::{parse_macro_input, DeriveInput, LitStr};
+
+mod attr;
+mod case;
+mod de;
+mod dispatch;
+mod include;
+mod literal;
+mod schema;
+mod ser;
+
+#[proc_macro_derive(Deserialize, attributes(rson))]
 pub fn derive_deserialize(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     // Parse the input tokens into a syntax tree.
     let input = parse_macro_input!(input as DeriveInput);
 
-    // Used in the quasi-quotation below as `#name`.
-    let name = input.ident;
+    de::expand(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(Serialize, attributes(rson))]
+pub fn derive_serialize(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    ser::expand(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
 
-    let expanded = quote! {
-       fn hello() -> String {
-           unimplemented!("derive Deserialize macro");
-       }
-    };
+/// Implements `rson::RsonSchema`, describing the type as a JSON Schema.
+/// Doc comments on the type and its fields become `description`s, and
+/// fields that aren't `Option`s are `required`.
+#[proc_macro_derive(RsonSchema, attributes(rson))]
+pub fn derive_rson_schema(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    schema::expand(&input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+/// Parses a JSON file at compile time and expands to an expression that
+/// builds it as a `rson::Value`:
+///
+///     let defaults: Value = include_rson!("config/defaults.json");
+///
+/// A file that doesn't parse fails the build with the line and column of
+/// the problem.
+#[proc_macro]
+pub fn include_rson(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+
+    include::expand(&path)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
 
-    proc_macro::TokenStream::from(expanded)
+/// Builds a `rson::Value` from JSON written inline, checking literals at
+/// compile time and interpolating Rust expressions:
+///
+///     let id = 7;
+///     let value = rson!({
+///         "id": id,
+///         "tags": ["a", "b"],
+///         (key_name): null,
+///         ..defaults
+///     })?;
+///
+/// With a `..expr` spread the result is a `rson::Result<Value>`, an error
+/// if `expr` isn't an object.
+#[proc_macro]
+pub fn rson(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    literal::expand(input.into())
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
 }
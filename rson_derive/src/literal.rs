@@ -0,0 +1,190 @@
+use proc_macro2::{Delimiter, Spacing, Span, TokenStream, TokenTree};
+use quote::{quote, quote_spanned};
+use rson_core::{ParseOptions, Rson, Value};
+use syn::spanned::Spanned;
+use syn::{Expr, Lit};
+
+/// Expands `rson!(...)`. The input is JSON written as Rust tokens, where
+/// any value may instead be a Rust expression:
+///
+/// - `null`, `true`, `false`, string literals and JSON numbers are taken
+///   literally; other Rust literals (`'c'`, `1u8`, `0x1f`, `1_000`) are
+///   rejected at their span.
+/// - Object keys are string literals, or a parenthesized expression for a
+///   computed key.
+/// - `..expr` inside an object merges the members of another object.
+///   Since `expr` may turn out not to be an object, a macro call with a
+///   spread anywhere in it evaluates to `rson::Result<Value>`.
+/// - Anything else is an expression converted with `Value::from`, so its
+///   type is checked where it is written. Expressions with a top-level
+///   comma (e.g. a turbofish with two parameters) need parentheses.
+pub fn expand(input: TokenStream) -> syn::Result<TokenStream> {
+    let tokens: Vec<TokenTree> = input.into_iter().collect();
+    let mut spread = false;
+    let value = value(&tokens, Span::call_site(), &mut spread)?;
+    if !spread {
+        return Ok(value);
+    }
+    // A failed spread breaks out of this block with its error.
+    Ok(quote! {
+        '__rson_spread: {
+            ::std::result::Result::<::rson::Value, ::rson::Error>::Ok(#value)
+        }
+    })
+}
+
+/// Builds the expression for one value, setting `spread` if it contains
+/// a `..expr`.
+fn value(tokens: &[TokenTree], span: Span, spread: &mut bool) -> syn::Result<TokenStream> {
+    match tokens {
+        [] => Err(syn::Error::new(span, "expected a JSON value or expression")),
+        [TokenTree::Ident(ident)] if ident == "null" => Ok(
+            quote_spanned!(ident.span()=> ::rson::Value::<::std::string::String>::Literal(::rson::Literal::Null)),
+        ),
+        [TokenTree::Ident(ident)] if ident == "true" || ident == "false" => Ok(
+            quote_spanned!(ident.span()=> ::rson::Value::<::std::string::String>::Literal(::rson::Literal::Bool(#ident))),
+        ),
+        [TokenTree::Literal(literal)] => literal_value(literal, ""),
+        [TokenTree::Punct(minus), TokenTree::Literal(literal)] if minus.as_char() == '-' => {
+            literal_value(literal, "-")
+        }
+        [TokenTree::Group(group)] if group.delimiter() == Delimiter::Bracket => {
+            let elements = split(group.stream(), ',', "a JSON value or expression")?
+                .iter()
+                .map(|element| value(element, group.span(), spread))
+                .collect::<syn::Result<Vec<_>>>()?;
+            Ok(quote!(::rson::Value::<::std::string::String>::Array(
+                ::std::vec![#(#elements),*]
+            )))
+        }
+        [TokenTree::Group(group)] if group.delimiter() == Delimiter::Brace => {
+            object(group.stream(), spread)
+        }
+        _ => {
+            let mut expr: Expr = syn::parse2(tokens.iter().cloned().collect())?;
+            // Parentheses are only there to keep the value out of the JSON
+            // grammar; drop them so they don't trip `unused_parens`.
+            while let Expr::Paren(paren) = expr {
+                expr = *paren.expr;
+            }
+            Ok(quote_spanned!(expr.span()=> ::rson::Value::<::std::string::String>::from(#expr)))
+        }
+    }
+}
+
+fn literal_value(literal: &proc_macro2::Literal, sign: &str) -> syn::Result<TokenStream> {
+    let span = literal.span();
+    match Lit::new(literal.clone()) {
+        Lit::Str(s) if sign.is_empty() => {
+            let s = s.value();
+            Ok(
+                quote_spanned!(span=> ::rson::Value::<::std::string::String>::String(::std::string::String::from(#s))),
+            )
+        }
+        Lit::Int(_) | Lit::Float(_) => {
+            let text = format!("{}{}", sign, literal);
+            match Rson::try_from_reader_with(text.as_bytes(), ParseOptions::strict()) {
+                Ok(Value::Number(_)) => Ok(quote_spanned!(span=>
+                    ::rson::Value::<::std::string::String>::Number(::rson::Number::new(::std::string::String::from(#text)))
+                )),
+                _ => Err(syn::Error::new(
+                    span,
+                    format!("`{}` is not a valid JSON number", text),
+                )),
+            }
+        }
+        _ => Err(syn::Error::new(
+            span,
+            "expected a JSON literal: a string, a number, `true`, `false` or `null`",
+        )),
+    }
+}
+
+fn object(stream: TokenStream, spread: &mut bool) -> syn::Result<TokenStream> {
+    let mut members = vec![];
+    for member in split(stream, ',', "an object member")? {
+        match member.as_slice() {
+            [TokenTree::Punct(a), TokenTree::Punct(b), rest @ ..]
+                if a.as_char() == '.' && b.as_char() == '.' && a.spacing() == Spacing::Joint =>
+            {
+                let expr: Expr = syn::parse2(rest.iter().cloned().collect())?;
+                *spread = true;
+                members.push(quote_spanned!(expr.span()=>
+                    if let ::std::result::Result::Err(e) = ::rson::__private::spread(&mut map, #expr) {
+                        break '__rson_spread ::std::result::Result::Err(e);
+                    }
+                ));
+            }
+            [key, TokenTree::Punct(colon), rest @ ..]
+                if colon.as_char() == ':' && colon.spacing() == Spacing::Alone =>
+            {
+                let key = object_key(key)?;
+                let value = value(rest, colon.span(), spread)?;
+                members.push(quote!(map.insert(#key, #value);));
+            }
+            [key, ..] => {
+                return Err(syn::Error::new(
+                    key.span(),
+                    "expected `\"key\": value`, `(expr): value` or `..expr`",
+                ))
+            }
+            [] => unreachable!("split rejects empty members"),
+        }
+    }
+
+    Ok(quote! {
+        {
+            let mut map = ::rson::Map::<::std::string::String, ::rson::Value>::new();
+            #(#members)*
+            ::rson::Value::<::std::string::String>::Object(::rson::RsonMap(map))
+        }
+    })
+}
+
+fn object_key(key: &TokenTree) -> syn::Result<TokenStream> {
+    match key {
+        TokenTree::Literal(literal) => match Lit::new(literal.clone()) {
+            Lit::Str(s) => {
+                let s = s.value();
+                Ok(quote!(::std::string::String::from(#s)))
+            }
+            _ => Err(syn::Error::new(
+                literal.span(),
+                "object keys must be string literals",
+            )),
+        },
+        TokenTree::Group(group) if group.delimiter() == Delimiter::Parenthesis => {
+            let expr: Expr = syn::parse2(group.stream())?;
+            Ok(quote_spanned!(expr.span()=> ::std::string::String::from(#expr)))
+        }
+        _ => Err(syn::Error::new(
+            key.span(),
+            "object keys must be string literals; wrap a computed key in parentheses",
+        )),
+    }
+}
+
+/// Splits a token stream on a top-level punctuation character. A single
+/// trailing separator is allowed; any other empty piece, as in `[1,,2]`,
+/// is an error at the separator after it.
+fn split(stream: TokenStream, separator: char, what: &str) -> syn::Result<Vec<Vec<TokenTree>>> {
+    let mut pieces = vec![vec![]];
+    for token in stream {
+        match &token {
+            TokenTree::Punct(punct) if punct.as_char() == separator => {
+                if pieces.last().unwrap().is_empty() {
+                    return Err(syn::Error::new(
+                        punct.span(),
+                        format!("expected {} before `{}`", what, separator),
+                    ));
+                }
+                pieces.push(vec![]);
+            }
+            _ => pieces.last_mut().unwrap().push(token),
+        }
+    }
+    if pieces.last().unwrap().is_empty() {
+        pieces.pop();
+    }
+    Ok(pieces)
+}
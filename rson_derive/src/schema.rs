@@ -0,0 +1,236 @@
+use crate::attr::{self, ContainerAttrs, FieldAttrs};
+use proc_macro2::{TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::ext::IdentExt;
+use syn::{
+    parse_quote, Attribute, Data, DataEnum, DeriveInput, Fields, Generics, Ident, Lit, Meta,
+    MetaNameValue, Type,
+};
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let generics = add_bounds(&input.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let description = doc_comment(&input.attrs);
+    let container = ContainerAttrs::from_input(input)?;
+
+    let body = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => {
+                let title = name.unraw().to_string();
+                let mut properties = vec![];
+                for field in &fields.named {
+                    let key = field.ident.as_ref().unwrap().unraw().to_string();
+                    let ty = &field.ty;
+                    // A `with` module may use any representation, so its
+                    // fields accept anything.
+                    let schema = match FieldAttrs::from_field(field)?.with {
+                        Some(_) => quote!(__gen.subschema_for::<::rson::Value>()),
+                        None => quote!(__gen.subschema_for::<#ty>()),
+                    };
+                    let schema = described(schema, &doc_comment(&field.attrs));
+                    properties.push(quote! {
+                        __properties.insert(::std::string::String::from(#key), #schema);
+                        if !<#ty as ::rson::RsonSchema>::is_optional() {
+                            __required.push(::rson::Value::from(#key));
+                        }
+                    });
+                }
+                quote! {
+                    let mut __properties = ::std::collections::HashMap::new();
+                    let mut __required = ::std::vec::Vec::new();
+                    #(#properties)*
+                    ::rson::__private::struct_schema(#title, #description, __properties, __required)
+                }
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                let ty = &fields.unnamed[0].ty;
+                described(quote!(__gen.subschema_for::<#ty>()), &description)
+            }
+            Fields::Unnamed(fields) => {
+                let len = fields.unnamed.len();
+                let types = fields.unnamed.iter().map(|f| &f.ty);
+                described(
+                    quote! {
+                        ::rson::Value::from({
+                            let mut __schema = ::std::collections::HashMap::new();
+                            __schema.insert(::std::string::String::from("type"), ::rson::Value::from("array"));
+                            __schema.insert(
+                                ::std::string::String::from("items"),
+                                ::rson::Value::Array(::std::vec![#(__gen.subschema_for::<#types>()),*]),
+                            );
+                            __schema.insert(::std::string::String::from("minItems"), ::rson::Value::from(#len));
+                            __schema.insert(::std::string::String::from("maxItems"), ::rson::Value::from(#len));
+                            __schema
+                        })
+                    },
+                    &description,
+                )
+            }
+            Fields::Unit => described(
+                quote!(::rson::Value::from({
+                    let mut __schema = ::std::collections::HashMap::new();
+                    __schema.insert(
+                        ::std::string::String::from("type"),
+                        ::rson::Value::from("null"),
+                    );
+                    __schema
+                })),
+                &description,
+            ),
+        },
+        Data::Enum(data) => unit_enum(input, data, &container, &description)?,
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "RsonSchema can't be derived for unions",
+            ))
+        }
+    };
+
+    // Generic types are inlined: their name alone doesn't identify the
+    // schema, since it depends on the type arguments. Inlining a generic
+    // type that contains itself would never end though, so those are
+    // named after their type arguments too, e.g. `List<String>`.
+    let schema_name = if input.generics.type_params().next().is_none() {
+        let name = name.unraw().to_string();
+        quote! {
+            fn schema_name() -> ::std::option::Option<::std::string::String> {
+                ::std::option::Option::Some(::std::string::String::from(#name))
+            }
+        }
+    } else if is_recursive(input) {
+        quote! {
+            fn schema_name() -> ::std::option::Option<::std::string::String> {
+                ::std::option::Option::Some(::rson::__private::short_type_name::<Self>())
+            }
+        }
+    } else {
+        quote!()
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::rson::RsonSchema for #name #ty_generics #where_clause {
+            fn schema(__gen: &mut ::rson::SchemaGenerator) -> ::rson::Value {
+                #body
+            }
+
+            #schema_name
+        }
+    })
+}
+
+/// Unit-only enums are described by the names of their variants, or their
+/// discriminants for `repr_int` enums. With an `#[rson(other)]` fallback
+/// any string or integer is accepted, so these are only listed as
+/// `examples`.
+fn unit_enum(
+    input: &DeriveInput,
+    data: &DataEnum,
+    container: &ContainerAttrs,
+    description: &str,
+) -> syn::Result<TokenStream> {
+    let variants = attr::unit_variants(data, container, "RsonSchema")?;
+    let known = variants.iter().filter(|v| !v.other);
+    let (ty, values) = match &container.repr_int {
+        Some(repr) => {
+            let name = &input.ident;
+            let idents = known.map(|v| v.ident);
+            (
+                "integer",
+                quote!(#(::rson::Value::from(#name::#idents as #repr)),*),
+            )
+        }
+        None => {
+            let names = known.map(|v| &v.name);
+            ("string", quote!(#(::rson::Value::from(#names)),*))
+        }
+    };
+    let keyword = if variants.iter().any(|v| v.other) {
+        "examples"
+    } else {
+        "enum"
+    };
+    let title = input.ident.unraw().to_string();
+
+    Ok(described(
+        quote! {
+            ::rson::Value::from({
+                let mut __schema = ::std::collections::HashMap::new();
+                __schema.insert(::std::string::String::from("title"), ::rson::Value::from(#title));
+                __schema.insert(::std::string::String::from("type"), ::rson::Value::from(#ty));
+                __schema.insert(
+                    ::std::string::String::from(#keyword),
+                    ::rson::Value::Array(::std::vec![#values]),
+                );
+                __schema
+            })
+        },
+        description,
+    ))
+}
+
+/// Whether a field of `input` mentions the type itself, by name or as
+/// `Self`.
+fn is_recursive(input: &DeriveInput) -> bool {
+    fn mentions(tokens: TokenStream, name: &Ident) -> bool {
+        tokens.into_iter().any(|token| match token {
+            TokenTree::Ident(ident) => ident == *name || ident == "Self",
+            TokenTree::Group(group) => mentions(group.stream(), name),
+            _ => false,
+        })
+    }
+
+    let types: Vec<&Type> = match &input.data {
+        Data::Struct(data) => data.fields.iter().map(|f| &f.ty).collect(),
+        Data::Enum(data) => data
+            .variants
+            .iter()
+            .flat_map(|v| v.fields.iter().map(|f| &f.ty))
+            .collect(),
+        Data::Union(_) => vec![],
+    };
+    types
+        .into_iter()
+        .any(|ty| mentions(ty.to_token_stream(), &input.ident))
+}
+
+/// Wraps `schema` in `describe` when there is a doc comment.
+fn described(schema: TokenStream, description: &str) -> TokenStream {
+    if description.is_empty() {
+        schema
+    } else {
+        quote!(::rson::__private::describe(#schema, #description))
+    }
+}
+
+/// Joins the `///` lines of an item, dropping the space rustdoc leaves
+/// after the slashes.
+fn doc_comment(attrs: &[Attribute]) -> String {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("doc"))
+        .filter_map(|attr| match attr.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue {
+                lit: Lit::Str(s), ..
+            })) => Some(s.value()),
+            _ => None,
+        })
+        .map(|line| {
+            line.strip_prefix(' ')
+                .unwrap_or(&line)
+                .trim_end()
+                .to_string()
+        })
+        .collect();
+    lines.join("\n").trim().to_string()
+}
+
+/// Requires every type parameter to be `RsonSchema` itself.
+fn add_bounds(generics: &Generics) -> Generics {
+    let mut generics = generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(::rson::RsonSchema));
+    }
+    generics
+}
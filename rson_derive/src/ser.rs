@@ -0,0 +1,113 @@
+use crate::attr::{self, ContainerAttrs, FieldAttrs};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{parse_quote, Data, DeriveInput, Fields, Generics, Index};
+
+pub fn expand(input: &DeriveInput) -> syn::Result<TokenStream> {
+    let name = &input.ident;
+    let generics = add_bounds(&input.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let container = ContainerAttrs::from_input(input)?;
+
+    if let Data::Struct(data) = &input.data {
+        if let Fields::Unnamed(fields) = &data.fields {
+            fields.unnamed.iter().try_for_each(FieldAttrs::only_named)?;
+        }
+    }
+
+    let body = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => {
+                let len = fields.named.len();
+                let mut entries = vec![];
+                for field in &fields.named {
+                    let ident = field.ident.as_ref().unwrap();
+                    let key = ident.unraw().to_string();
+                    entries.push(match FieldAttrs::from_field(field)?.with {
+                        // `serialize_entry` wants a `Serialize` value, so run
+                        // the `with` module into a `Value` first.
+                        Some(with) => quote! {
+                            let __value: ::rson::Value = #with::serialize(&self.#ident, ::rson::ValueSerializer::new())?;
+                            ::rson::SerializeMap::serialize_entry(&mut __map, #key, &__value)?;
+                        },
+                        None => quote! {
+                            ::rson::SerializeMap::serialize_entry(&mut __map, #key, &self.#ident)?;
+                        },
+                    });
+                }
+                quote! {
+                    let mut __map = ::rson::Serializer::serialize_map(
+                        __serializer,
+                        ::std::option::Option::Some(#len),
+                    )?;
+                    #(#entries)*
+                    ::rson::SerializeMap::end(__map)
+                }
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => quote! {
+                ::rson::Serialize::serialize(&self.0, __serializer)
+            },
+            Fields::Unnamed(fields) => {
+                let len = fields.unnamed.len();
+                let indices = (0..len).map(Index::from);
+                quote! {
+                    let mut __seq = ::rson::Serializer::serialize_seq(
+                        __serializer,
+                        ::std::option::Option::Some(#len),
+                    )?;
+                    #(::rson::SerializeSeq::serialize_element(&mut __seq, &self.#indices)?;)*
+                    ::rson::SerializeSeq::end(__seq)
+                }
+            }
+            Fields::Unit => quote! {
+                ::rson::Serializer::serialize_null(__serializer)
+            },
+        },
+        Data::Enum(data) => {
+            let variants = attr::unit_variants(data, &container, "Serialize")?;
+            let idents: Vec<_> = variants.iter().map(|v| v.ident).collect();
+            match &container.repr_int {
+                Some(repr) => quote! {
+                    match self {
+                        #(#name::#idents => ::rson::Serialize::serialize(&(#name::#idents as #repr), __serializer),)*
+                    }
+                },
+                None => {
+                    let strings = variants.iter().map(|v| &v.name);
+                    quote! {
+                        match self {
+                            #(#name::#idents => ::rson::Serializer::serialize_str(__serializer, #strings),)*
+                        }
+                    }
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "Serialize can't be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::rson::Serialize for #name #ty_generics #where_clause {
+            fn serialize<__S>(&self, __serializer: __S) -> ::rson::Result<__S::Ok>
+            where
+                __S: ::rson::Serializer,
+            {
+                #body
+            }
+        }
+    })
+}
+
+/// Requires every type parameter to be `Serialize` itself.
+fn add_bounds(generics: &Generics) -> Generics {
+    let mut generics = generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(::rson::Serialize));
+    }
+    generics
+}